@@ -9,10 +9,9 @@
 // and rust integration. See there for details on the panic-handler and entry
 // point configuration.
 //
-// Note that UEFI uses UTF-16 strings. Since rust literals are UTF-8, we have
-// to use an open-coded, zero-terminated, UTF-16 array as argument to
-// `output_string()`. Similarly to the panic handler, real applications should
-// rather use UTF-16 modules.
+// Note that UEFI uses UCS-2 strings. Since rust literals are UTF-8, we use the
+// `ucs2!()` macro to convert the literal into a zero-terminated UCS-2 array at
+// compile time, which can then be passed to `output_string()`.
 
 #![no_main]
 #![no_std]
@@ -26,14 +25,7 @@ fn panic_handler(_info: &core::panic::PanicInfo) -> ! {
 
 #[export_name = "efi_main"]
 pub extern "C" fn main(_h: efi::Handle, st: *mut efi::SystemTable) -> efi::Status {
-    let s = [
-        0x0048u16, 0x0065u16, 0x006cu16, 0x006cu16, 0x006fu16, // "Hello"
-        0x0020u16, //                                             " "
-        0x0057u16, 0x006fu16, 0x0072u16, 0x006cu16, 0x0064u16, // "World"
-        0x0021u16, //                                             "!"
-        0x000au16, //                                             "\n"
-        0x0000u16, //                                             NUL
-    ];
+    let s = r_efi::ucs2!("Hello World!\n");
 
     // Print "Hello World!".
     let r =
@@ -288,6 +288,41 @@ pub type Char8 = u8;
 /// The `Char16` type represents dual-byte characters. UEFI defines them to be UCS-2 encoded.
 pub type Char16 = u16;
 
+/// Borrowed UCS-2 Strings
+///
+/// The `CStr16` type is a borrowed view of a NUL-terminated string of
+/// `Char16`. It is the UCS-2 equivalent of `core::ffi::CStr`. The view is
+/// validated on creation: it contains exactly one NUL character as its last
+/// element, and no UTF-16 surrogates (which are not valid UCS-2).
+///
+/// Use the `cstr16!()` macro to create static strings from Rust literals, or
+/// `from_ptr()` to wrap strings returned by UEFI. Use `as_ptr()` to pass
+/// strings to UEFI functions (casting to `*mut` as required by the
+/// prototypes; UEFI never modifies input strings).
+#[repr(transparent)]
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CStr16([Char16]);
+
+/// UCS-2 String Errors
+///
+/// This is returned when creating a `CStr16` fails. The position of the
+/// offending character is included, where applicable. Positions count
+/// UCS-2 characters when validating `Char16` data, and bytes when converting
+/// from UTF-8.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum CStr16Error {
+    /// The string is not NUL-terminated.
+    NotNulTerminated,
+    /// The string contains a NUL character before its end.
+    InteriorNul(usize),
+    /// The string contains a character that cannot be represented in UCS-2.
+    InvalidChar(usize),
+    /// The provided buffer is too small to hold the string and its NUL
+    /// terminator.
+    BufferTooSmall,
+}
+
 /// Status Codes
 ///
 /// UEFI uses the `Status` type to represent all kinds of status codes. This includes return codes
@@ -300,6 +335,31 @@ pub type Char16 = u16;
 #[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Status(usize);
 
+/// Status Errors
+///
+/// This wraps a `Status` that is known to be an error code. It is the error
+/// type of `Status::to_result()` and similar helpers, and implements
+/// `core::error::Error` so it can be propagated with `?` alongside other
+/// errors. It is binary compatible to `Status`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct StatusError(Status);
+
+/// Successful Completion
+///
+/// This is the success type of `Status::to_result()` and similar helpers. It
+/// carries the value produced by the operation, as well as the status code
+/// the operation completed with. The latter is either `Status::SUCCESS` or a
+/// warning. Unlike errors, warnings carry valid output and should thus not be
+/// discarded silently.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Completion<T = ()> {
+    pub status: Status,
+    pub value: T,
+}
+
 /// Object Handles
 ///
 /// Handles represent access to an opaque object. Handles are untyped by default, but get a
@@ -452,6 +512,45 @@ pub union IpAddress {
     pub v6: Ipv6Address,
 }
 
+/// MAC Address Formatter
+///
+/// This formats the first `len` bytes of a MAC address as colon-separated
+/// hexadecimal bytes (e.g., `00:11:22:aa:bb:cc`). See `MacAddress::display()`.
+#[derive(Clone, Copy, Debug)]
+pub struct MacAddressDisplay<'a> {
+    addr: &'a MacAddress,
+    len: usize,
+}
+
+/// IP Address Views
+///
+/// A tagged view of the `IpAddress` union. Since the union carries no tag,
+/// the caller must provide the address family, usually taken from the
+/// surrounding configuration structure. See `IpAddress::view()`.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum IpAddressView {
+    V4(Ipv4Address),
+    V6(Ipv6Address),
+}
+
+/// IP Address Family Errors
+///
+/// This is returned when converting an IP address of one family into an
+/// address type of the other family.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct IpFamilyError(());
+
+/// Guid Parser Errors
+///
+/// This is returned when parsing a Guid from its text representation fails.
+/// The only accepted format is the canonical `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`
+/// representation with hexadecimal digits in either case.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct ParseGuidError(());
+
 impl Boolean {
     /// Literal False
     ///
@@ -708,6 +807,167 @@ impl Status {
     }
 }
 
+impl Status {
+    /// Convert to Result
+    ///
+    /// This converts the status code into a `Result`, mapping error codes to
+    /// `StatusError`, and success and warning codes to `Completion`. Unlike
+    /// the `From` conversion to `Result<Status, Status>`, the error type
+    /// is guaranteed to carry an error, and the success type retains the
+    /// warning.
+    pub fn to_result(self) -> Result<Completion, StatusError> {
+        self.to_result_with(|| ())
+    }
+
+    /// Convert to Result with Value
+    ///
+    /// This works like `to_result()`, but produces a value on success. The
+    /// closure `f` is only invoked if the status code is not an error, since
+    /// output parameters of UEFI functions must be considered invalid on
+    /// error.
+    pub fn to_result_with<T, F: FnOnce() -> T>(self, f: F) -> Result<Completion<T>, StatusError> {
+        if self.is_error() {
+            Err(StatusError(self))
+        } else {
+            Ok(Completion {
+                status: self,
+                value: f(),
+            })
+        }
+    }
+}
+
+impl StatusError {
+    /// Create Status Error
+    ///
+    /// Wrap the status code `status` in a `StatusError`. If the status code
+    /// is not an error, `None` is returned.
+    pub fn new(status: Status) -> Option<Self> {
+        if status.is_error() {
+            Some(Self(status))
+        } else {
+            None
+        }
+    }
+
+    /// Return the Status Code
+    pub const fn status(&self) -> Status {
+        self.0
+    }
+
+    /// Check for `BUFFER_TOO_SMALL`
+    pub fn is_buffer_too_small(&self) -> bool {
+        self.0 == Status::BUFFER_TOO_SMALL
+    }
+}
+
+impl From<StatusError> for Status {
+    fn from(v: StatusError) -> Self {
+        v.0
+    }
+}
+
+impl core::fmt::Display for StatusError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl core::error::Error for StatusError {}
+
+impl<T> Completion<T> {
+    /// Check whether this Completed with a Warning
+    pub fn is_warning(&self) -> bool {
+        self.status.is_warning()
+    }
+
+    /// Map the Completion Value
+    ///
+    /// Apply `f` to the value, retaining the status code.
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Completion<U> {
+        Completion {
+            status: self.status,
+            value: f(self.value),
+        }
+    }
+
+    /// Unwrap the Completion Value
+    ///
+    /// Return the value, discarding any warning.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+/// Retry Operations on `BUFFER_TOO_SMALL`
+///
+/// Many UEFI functions take a caller-provided buffer together with its size.
+/// If the buffer is too small, they return `BUFFER_TOO_SMALL` and update the
+/// size to the required size. This helper implements the retry loop of this
+/// pattern.
+///
+/// The function `call` is invoked with `buffer` and an in/out size. If it
+/// returns `BUFFER_TOO_SMALL` and updated the size to a bigger value, the
+/// function `resize` is invoked with `buffer` and the new size, before `call`
+/// is invoked again. Any error returned by `resize` is propagated. The loop
+/// starts by invoking `call` with a size of `size`, which must not be bigger
+/// than the current buffer.
+///
+/// If `BUFFER_TOO_SMALL` is returned without increasing the size, it is
+/// returned as error to avoid endless loops. On completion, the final size
+/// as reported by `call` is returned as value.
+///
+/// ```
+/// use r_efi::efi;
+///
+/// let mut buffer = Vec::new();
+/// let r = efi::retry_buffer_too_small(
+///     &mut buffer,
+///     0,
+///     |buffer, size| {
+///         buffer.resize(size, 0u8);
+///         Ok(())
+///     },
+///     |buffer, size| {
+///         if *size < 16 {
+///             *size = 16;
+///             return efi::Status::BUFFER_TOO_SMALL;
+///         }
+///         buffer[..16].fill(0xff);
+///         *size = 16;
+///         efi::Status::SUCCESS
+///     },
+/// );
+///
+/// assert_eq!(r.unwrap().value, 16);
+/// assert_eq!(buffer, [0xff; 16]);
+/// ```
+pub fn retry_buffer_too_small<B, R, C>(
+    buffer: &mut B,
+    size: usize,
+    mut resize: R,
+    mut call: C,
+) -> Result<Completion<usize>, StatusError>
+where
+    B: ?Sized,
+    R: FnMut(&mut B, usize) -> Result<(), StatusError>,
+    C: FnMut(&mut B, &mut usize) -> Status,
+{
+    let mut current = size;
+
+    loop {
+        let mut v = current;
+        let r = call(buffer, &mut v);
+
+        if r == Status::BUFFER_TOO_SMALL && v > current {
+            resize(buffer, v)?;
+            current = v;
+        } else {
+            return r.to_result_with(|| v);
+        }
+    }
+}
+
 impl From<Status> for Result<Status, Status> {
     fn from(status: Status) -> Self {
         if status.is_error() {
@@ -822,6 +1082,699 @@ impl Guid {
     pub const fn as_bytes(&self) -> &[u8; 16] {
         unsafe { core::mem::transmute::<&Guid, &[u8; 16]>(self) }
     }
+
+    const fn hex_from_ascii(c: u8) -> Option<u8> {
+        match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'a'..=b'f' => Some(c - b'a' + 10),
+            b'A'..=b'F' => Some(c - b'A' + 10),
+            _ => None,
+        }
+    }
+
+    /// Parse a Guid from its text representation
+    ///
+    /// This parses the canonical text representation of a Guid, as it is
+    /// printed in the specification (e.g., `8be4df61-93ca-11d2-aa0d-00e098032b8c`).
+    /// Hexadecimal digits can be in either case. No surrounding braces or
+    /// whitespace are allowed.
+    ///
+    /// The text representation lists the individual fields in big-endian
+    /// order, so this is equivalent to `from_fields()` with the fields copied
+    /// from the text. This is a `const fn`, so it can be used to initialize
+    /// constants. See the `guid!()` macro for a convenient wrapper.
+    pub const fn parse(s: &str) -> Result<Guid, ParseGuidError> {
+        let s = s.as_bytes();
+        let mut bytes = [0u8; 16];
+        let mut i = 0;
+        let mut n = 0;
+
+        if s.len() != 36 {
+            return Err(ParseGuidError(()));
+        }
+
+        while i < 36 {
+            if i == 8 || i == 13 || i == 18 || i == 23 {
+                if s[i] != b'-' {
+                    return Err(ParseGuidError(()));
+                }
+                i += 1;
+                continue;
+            }
+
+            let (hi, lo) = match (Self::hex_from_ascii(s[i]), Self::hex_from_ascii(s[i + 1])) {
+                (Some(hi), Some(lo)) => (hi, lo),
+                _ => return Err(ParseGuidError(())),
+            };
+            bytes[n] = (hi << 4) | lo;
+            n += 1;
+            i += 2;
+        }
+
+        Ok(Self::from_fields(
+            u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            u16::from_be_bytes([bytes[4], bytes[5]]),
+            u16::from_be_bytes([bytes[6], bytes[7]]),
+            bytes[8],
+            bytes[9],
+            &[bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15]],
+        ))
+    }
+
+    fn fmt_text(&self, f: &mut core::fmt::Formatter, upper: bool) -> core::fmt::Result {
+        let (time_low, time_mid, time_hi_and_version, clk_seq_hi_res, clk_seq_low, node) =
+            self.as_fields();
+
+        if upper {
+            write!(
+                f,
+                "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}",
+                time_low, time_mid, time_hi_and_version, clk_seq_hi_res, clk_seq_low,
+                node[0], node[1], node[2], node[3], node[4], node[5],
+            )
+        } else {
+            write!(
+                f,
+                "{:08x}-{:04x}-{:04x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+                time_low, time_mid, time_hi_and_version, clk_seq_hi_res, clk_seq_low,
+                node[0], node[1], node[2], node[3], node[4], node[5],
+            )
+        }
+    }
+}
+
+impl core::fmt::Display for Guid {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.fmt_text(f, false)
+    }
+}
+
+impl core::fmt::LowerHex for Guid {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.fmt_text(f, false)
+    }
+}
+
+impl core::fmt::UpperHex for Guid {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.fmt_text(f, true)
+    }
+}
+
+impl core::str::FromStr for Guid {
+    type Err = ParseGuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl core::fmt::Display for ParseGuidError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        "invalid Guid syntax".fmt(f)
+    }
+}
+
+/// Guid Literals
+///
+/// Create a constant `Guid` from its canonical text representation, as it is
+/// printed in the specification. The string is parsed at compile time, and
+/// compilation fails if it is not a valid Guid. See `Guid::parse()` for the
+/// accepted format.
+///
+/// ```
+/// use r_efi::{efi, guid};
+///
+/// const GUID: efi::Guid = guid!("8be4df61-93ca-11d2-aa0d-00e098032b8c");
+///
+/// assert_eq!(GUID, efi::GLOBAL_VARIABLE);
+/// ```
+#[macro_export]
+macro_rules! guid {
+    ($s:expr) => {{
+        const GUID: $crate::base::Guid = match $crate::base::Guid::parse($s) {
+            Ok(v) => v,
+            Err(_) => panic!("invalid Guid literal"),
+        };
+        GUID
+    }};
+}
+
+impl MacAddress {
+    /// Create a MAC Address from a Hardware Address
+    ///
+    /// Copy `addr` into the start of a new MAC address and clear the
+    /// remaining bytes. `None` is returned if `addr` is longer than 32 bytes.
+    pub fn from_hw_address(addr: &[u8]) -> Option<Self> {
+        let mut v = Self { addr: [0; 32] };
+        v.addr.get_mut(..addr.len())?.copy_from_slice(addr);
+        Some(v)
+    }
+
+    /// Format a MAC Address
+    ///
+    /// Return a formatter that prints the first `hw_address_size` bytes of
+    /// the address, usually taken from `simple_network::Mode`. The size is
+    /// clamped to the size of the address buffer.
+    pub fn display(&self, hw_address_size: u32) -> MacAddressDisplay<'_> {
+        MacAddressDisplay {
+            addr: self,
+            len: (hw_address_size as usize).min(self.addr.len()),
+        }
+    }
+}
+
+impl From<[u8; 6]> for MacAddress {
+    fn from(v: [u8; 6]) -> Self {
+        let mut addr = [0; 32];
+        addr[..6].copy_from_slice(&v);
+        Self { addr }
+    }
+}
+
+impl core::fmt::Display for MacAddressDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        for (i, v) in self.addr.addr[..self.len].iter().enumerate() {
+            if i > 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{:02x}", v)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<core::net::Ipv4Addr> for Ipv4Address {
+    fn from(v: core::net::Ipv4Addr) -> Self {
+        Self { addr: v.octets() }
+    }
+}
+
+impl From<Ipv4Address> for core::net::Ipv4Addr {
+    fn from(v: Ipv4Address) -> Self {
+        v.addr.into()
+    }
+}
+
+impl core::convert::TryFrom<core::net::IpAddr> for Ipv4Address {
+    type Error = IpFamilyError;
+
+    fn try_from(v: core::net::IpAddr) -> Result<Self, Self::Error> {
+        match v {
+            core::net::IpAddr::V4(v) => Ok(v.into()),
+            core::net::IpAddr::V6(_) => Err(IpFamilyError(())),
+        }
+    }
+}
+
+impl core::fmt::Display for Ipv4Address {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::net::Ipv4Addr::from(*self).fmt(f)
+    }
+}
+
+impl From<core::net::Ipv6Addr> for Ipv6Address {
+    fn from(v: core::net::Ipv6Addr) -> Self {
+        Self { addr: v.octets() }
+    }
+}
+
+impl From<Ipv6Address> for core::net::Ipv6Addr {
+    fn from(v: Ipv6Address) -> Self {
+        v.addr.into()
+    }
+}
+
+impl core::convert::TryFrom<core::net::IpAddr> for Ipv6Address {
+    type Error = IpFamilyError;
+
+    fn try_from(v: core::net::IpAddr) -> Result<Self, Self::Error> {
+        match v {
+            core::net::IpAddr::V4(_) => Err(IpFamilyError(())),
+            core::net::IpAddr::V6(v) => Ok(v.into()),
+        }
+    }
+}
+
+impl core::fmt::Display for Ipv6Address {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::net::Ipv6Addr::from(*self).fmt(f)
+    }
+}
+
+impl IpAddress {
+    /// Create an IP Address from an IPv4 Address
+    ///
+    /// The trailing bytes not covered by the IPv4 address are cleared.
+    pub const fn from_v4(v: Ipv4Address) -> Self {
+        let mut addr = [0u8; 16];
+        addr[0] = v.addr[0];
+        addr[1] = v.addr[1];
+        addr[2] = v.addr[2];
+        addr[3] = v.addr[3];
+        Self { v6: Ipv6Address { addr } }
+    }
+
+    /// Create an IP Address from an IPv6 Address
+    pub const fn from_v6(v: Ipv6Address) -> Self {
+        Self { v6: v }
+    }
+
+    /// Access the IPv4 Address
+    ///
+    /// Interpret the address as IPv4 address. This is always safe, since
+    /// any bit pattern is a valid address.
+    pub const fn v4(&self) -> Ipv4Address {
+        unsafe { self.v4 }
+    }
+
+    /// Access the IPv6 Address
+    ///
+    /// Interpret the address as IPv6 address. This is always safe, since
+    /// any bit pattern is a valid address.
+    pub const fn v6(&self) -> Ipv6Address {
+        unsafe { self.v6 }
+    }
+
+    /// Create a Tagged View
+    ///
+    /// Interpret the address as IPv6 address if `is_v6` is true, otherwise as
+    /// IPv4 address.
+    pub const fn view(&self, is_v6: bool) -> IpAddressView {
+        if is_v6 {
+            IpAddressView::V6(self.v6())
+        } else {
+            IpAddressView::V4(self.v4())
+        }
+    }
+
+    /// Convert to a Rust IP Address
+    ///
+    /// Convert to `core::net::IpAddr`, using the address family as selected
+    /// by `is_v6`. See `view()` for details.
+    pub fn to_ip_addr(&self, is_v6: bool) -> core::net::IpAddr {
+        self.view(is_v6).into()
+    }
+}
+
+impl From<Ipv4Address> for IpAddress {
+    fn from(v: Ipv4Address) -> Self {
+        Self::from_v4(v)
+    }
+}
+
+impl From<Ipv6Address> for IpAddress {
+    fn from(v: Ipv6Address) -> Self {
+        Self::from_v6(v)
+    }
+}
+
+impl From<IpAddressView> for IpAddress {
+    fn from(v: IpAddressView) -> Self {
+        match v {
+            IpAddressView::V4(v) => Self::from_v4(v),
+            IpAddressView::V6(v) => Self::from_v6(v),
+        }
+    }
+}
+
+impl From<core::net::IpAddr> for IpAddress {
+    fn from(v: core::net::IpAddr) -> Self {
+        IpAddressView::from(v).into()
+    }
+}
+
+impl core::fmt::Debug for IpAddress {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("IpAddress").field("addr", &self.v6().addr).finish()
+    }
+}
+
+impl From<core::net::IpAddr> for IpAddressView {
+    fn from(v: core::net::IpAddr) -> Self {
+        match v {
+            core::net::IpAddr::V4(v) => Self::V4(v.into()),
+            core::net::IpAddr::V6(v) => Self::V6(v.into()),
+        }
+    }
+}
+
+impl From<IpAddressView> for core::net::IpAddr {
+    fn from(v: IpAddressView) -> Self {
+        match v {
+            IpAddressView::V4(v) => Self::V4(v.into()),
+            IpAddressView::V6(v) => Self::V6(v.into()),
+        }
+    }
+}
+
+impl core::fmt::Display for IpAddressView {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            IpAddressView::V4(v) => v.fmt(f),
+            IpAddressView::V6(v) => v.fmt(f),
+        }
+    }
+}
+
+impl core::fmt::Display for IpFamilyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        "IP address family mismatch".fmt(f)
+    }
+}
+
+impl core::error::Error for IpFamilyError {}
+
+impl CStr16 {
+    const fn is_surrogate(c: Char16) -> bool {
+        c >= 0xd800 && c <= 0xdfff
+    }
+
+    /// Create a UCS-2 String from a Slice
+    ///
+    /// Validate that `slice` is a NUL-terminated UCS-2 string and return a
+    /// `CStr16` view of it. The NUL terminator must be the last element of
+    /// the slice.
+    pub fn from_slice_with_nul(slice: &[Char16]) -> Result<&Self, CStr16Error> {
+        match slice.iter().position(|v| *v == 0) {
+            None => return Err(CStr16Error::NotNulTerminated),
+            Some(i) if i + 1 != slice.len() => return Err(CStr16Error::InteriorNul(i)),
+            Some(_) => {},
+        }
+
+        if let Some(i) = slice.iter().position(|v| Self::is_surrogate(*v)) {
+            return Err(CStr16Error::InvalidChar(i));
+        }
+
+        // SAFETY: Validated above.
+        Ok(unsafe { Self::from_slice_with_nul_unchecked(slice) })
+    }
+
+    /// Create a UCS-2 String from a Slice without Validation
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `slice` is a NUL-terminated UCS-2
+    /// string with no interior NUL characters.
+    pub const unsafe fn from_slice_with_nul_unchecked(slice: &[Char16]) -> &Self {
+        unsafe { &*(slice as *const [Char16] as *const Self) }
+    }
+
+    /// Create a UCS-2 String from a Raw Pointer
+    ///
+    /// Scan the string at `ptr` for its NUL terminator and return a validated
+    /// `CStr16` view of it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `ptr` is non-null, properly aligned,
+    /// and points to a NUL-terminated array of `Char16`, which is valid for
+    /// reads and not modified for the lifetime `'a`.
+    pub unsafe fn from_ptr<'a>(ptr: *const Char16) -> Result<&'a Self, CStr16Error> {
+        let mut n = 0;
+        // SAFETY: The caller guarantees the array is aligned and readable up
+        //         to and including its NUL terminator.
+        while unsafe { ptr.add(n).read() } != 0 {
+            n += 1;
+        }
+
+        // SAFETY: See above.
+        Self::from_slice_with_nul(unsafe { core::slice::from_raw_parts(ptr, n + 1) })
+    }
+
+    /// Create a UCS-2 String from UTF-8
+    ///
+    /// Convert the UTF-8 string `s` to UCS-2 and store it, including the NUL
+    /// terminator, in `buffer`. The converted string is returned as a view
+    /// into `buffer`. Characters outside of the Basic Multilingual Plane and
+    /// NUL characters are rejected.
+    pub fn from_str_with_buf<'a>(
+        s: &str,
+        buffer: &'a mut [Char16],
+    ) -> Result<&'a Self, CStr16Error> {
+        let mut n = 0;
+
+        for (i, c) in s.char_indices() {
+            let v = match c as u32 {
+                0 => return Err(CStr16Error::InteriorNul(i)),
+                v @ 0x0001..=0xffff => v as Char16,
+                _ => return Err(CStr16Error::InvalidChar(i)),
+            };
+
+            *buffer.get_mut(n).ok_or(CStr16Error::BufferTooSmall)? = v;
+            n += 1;
+        }
+
+        *buffer.get_mut(n).ok_or(CStr16Error::BufferTooSmall)? = 0;
+
+        // SAFETY: The string was validated during conversion.
+        Ok(unsafe { Self::from_slice_with_nul_unchecked(&buffer[..n + 1]) })
+    }
+
+    /// Return a Raw Pointer to the String
+    ///
+    /// The pointer is valid for as long as `self`. It points to the first
+    /// character of the NUL-terminated string.
+    pub const fn as_ptr(&self) -> *const Char16 {
+        self.0.as_ptr()
+    }
+
+    /// Access the String without its NUL Terminator
+    pub fn as_slice(&self) -> &[Char16] {
+        &self.0[..self.0.len() - 1]
+    }
+
+    /// Access the String including its NUL Terminator
+    pub const fn as_slice_with_nul(&self) -> &[Char16] {
+        &self.0
+    }
+
+    /// Return the Length of the String
+    ///
+    /// This is the number of characters, not counting the NUL terminator.
+    pub const fn len(&self) -> usize {
+        self.0.len() - 1
+    }
+
+    /// Check whether the String is Empty
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate the Characters of the String
+    ///
+    /// This yields the characters of the string, not including the NUL
+    /// terminator, converted to Rust `char`.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.as_slice()
+            .iter()
+            .map(|v| char::from_u32(*v as u32).unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+}
+
+impl AsRef<[Char16]> for CStr16 {
+    fn as_ref(&self) -> &[Char16] {
+        self.as_slice()
+    }
+}
+
+impl core::fmt::Debug for CStr16 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        f.write_char('"')?;
+        for c in self.chars() {
+            for e in c.escape_debug() {
+                f.write_char(e)?;
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+impl core::fmt::Display for CStr16 {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq<str> for CStr16 {
+    fn eq(&self, other: &str) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl core::fmt::Display for CStr16Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            CStr16Error::NotNulTerminated => write!(f, "string is not NUL-terminated"),
+            CStr16Error::InteriorNul(i) => write!(f, "interior NUL at position {}", i),
+            CStr16Error::InvalidChar(i) => write!(f, "invalid UCS-2 character at position {}", i),
+            CStr16Error::BufferTooSmall => write!(f, "buffer too small"),
+        }
+    }
+}
+
+impl core::error::Error for CStr16Error {}
+
+// Compile-time UCS-2 Encoding
+//
+// These helpers implement the `ucs2!()` macro. They are public so the macro
+// can use them from other crates, but they are not part of the API. The
+// input is valid UTF-8, so only the lead bytes need to be inspected. Any
+// character that cannot be represented in UCS-2 (i.e., 4-byte sequences),
+// as well as NUL characters, are rejected at compile time.
+
+#[doc(hidden)]
+pub const fn ucs2_len(s: &str) -> usize {
+    let s = s.as_bytes();
+    let mut i = 0;
+    let mut n = 0;
+
+    while i < s.len() {
+        i += match s[i] {
+            0x00 => panic!("NUL character in UCS-2 literal"),
+            0x01..=0x7f => 1,
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            _ => panic!("character outside of UCS-2 in literal"),
+        };
+        n += 1;
+    }
+
+    // Include the NUL terminator.
+    n + 1
+}
+
+#[doc(hidden)]
+pub const fn ucs2_encode<const N: usize>(s: &str) -> [Char16; N] {
+    let s = s.as_bytes();
+    let mut r = [0; N];
+    let mut i = 0;
+    let mut n = 0;
+
+    while i < s.len() {
+        let (v, l) = match s[i] {
+            0x01..=0x7f => (s[i] as Char16, 1),
+            0xc0..=0xdf => ((((s[i] & 0x1f) as Char16) << 6) | (s[i + 1] & 0x3f) as Char16, 2),
+            0xe0..=0xef => (
+                (((s[i] & 0x0f) as Char16) << 12)
+                    | (((s[i + 1] & 0x3f) as Char16) << 6)
+                    | (s[i + 2] & 0x3f) as Char16,
+                3,
+            ),
+            _ => panic!("character outside of UCS-2 in literal"),
+        };
+        r[n] = v;
+        n += 1;
+        i += l;
+    }
+
+    if n + 1 != N {
+        panic!("invalid UCS-2 literal length");
+    }
+
+    r
+}
+
+/// UCS-2 String Literals
+///
+/// Convert a string literal to a NUL-terminated array of `Char16` at compile
+/// time. The resulting array has type `[Char16; N]`, where `N` includes the
+/// NUL terminator. Compilation fails if the literal contains NUL characters or
+/// characters outside of the Basic Multilingual Plane.
+///
+/// ```
+/// use r_efi::{efi, ucs2};
+///
+/// const HELLO: [efi::Char16; 6] = ucs2!("Hello");
+///
+/// assert_eq!(HELLO, [0x48, 0x65, 0x6c, 0x6c, 0x6f, 0x00]);
+/// ```
+///
+/// Characters outside of UCS-2 are rejected:
+///
+/// ```compile_fail
+/// use r_efi::ucs2;
+///
+/// let _ = ucs2!("\u{1f600}");
+/// ```
+#[macro_export]
+macro_rules! ucs2 {
+    ($s:expr) => {{
+        const S: &str = $s;
+        const N: usize = $crate::base::ucs2_len(S);
+        const V: [$crate::base::Char16; N] = $crate::base::ucs2_encode::<N>(S);
+        V
+    }};
+}
+
+/// UCS-2 String Literal Views
+///
+/// Convert a string literal to a `&'static CStr16` at compile time. See
+/// `ucs2!()` for details on the conversion.
+///
+/// ```
+/// use r_efi::{cstr16, efi};
+///
+/// let s: &efi::CStr16 = cstr16!("Hello World!");
+///
+/// assert_eq!(s.len(), 12);
+/// assert_eq!(s, "Hello World!");
+/// ```
+#[macro_export]
+macro_rules! cstr16 {
+    ($s:expr) => {{
+        const S: &str = $s;
+        const N: usize = $crate::base::ucs2_len(S);
+        const V: &[$crate::base::Char16] = &$crate::base::ucs2_encode::<N>(S);
+        // SAFETY: `ucs2!()` produces NUL-terminated UCS-2 strings without
+        //         interior NUL characters.
+        const C: &$crate::base::CStr16 = unsafe {
+            $crate::base::CStr16::from_slice_with_nul_unchecked(V)
+        };
+        C
+    }};
+}
+
+// Lookup table of the reflected CRC32 polynomial `0xedb88320` (IEEE 802.3),
+// as used by `crc32()`.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut v = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            v = if v & 1 != 0 { (v >> 1) ^ 0xedb88320 } else { v >> 1 };
+            j += 1;
+        }
+        table[i] = v;
+        i += 1;
+    }
+    table
+};
+
+/// Calculate CRC32
+///
+/// Calculate the CRC32 of `data`, as `calculate_crc32()` of the boot services
+/// does. This is the checksum used by table headers, partition tables, and
+/// capsules. The previous result can be passed as `crc` to continue a running
+/// checksum over multiple buffers. Pass 0 to start a new one. This can be
+/// evaluated in const context.
+pub const fn crc32(crc: u32, data: &[u8]) -> u32 {
+    let mut v = !crc;
+    let mut i = 0;
+    while i < data.len() {
+        v = CRC32_TABLE[((v ^ (data[i] as u32)) & 0xff) as usize] ^ (v >> 8);
+        i += 1;
+    }
+    !v
 }
 
 #[cfg(test)]
@@ -1037,6 +1990,205 @@ mod tests {
         assert_eq!(format!("{}", &status), "000000C8");
     }
 
+    // Verify Network Addresses
+    //
+    // Test the conversions between the UEFI network address types and their
+    // `core::net` counterparts, as well as their formatting.
+    #[test]
+    fn network_addresses() {
+        use core::convert::TryFrom;
+        use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+        let v4 = Ipv4Addr::new(192, 168, 0, 1);
+        let v6 = Ipv6Addr::new(0xfe80, 0, 0, 0, 0x0211, 0x22ff, 0xfe33, 0x4455);
+
+        let a4 = Ipv4Address::from(v4);
+        assert_eq!(a4.addr, [192, 168, 0, 1]);
+        assert_eq!(Ipv4Addr::from(a4), v4);
+        assert_eq!(format!("{}", a4), "192.168.0.1");
+        assert_eq!(Ipv4Address::try_from(IpAddr::V4(v4)), Ok(a4));
+        assert!(Ipv4Address::try_from(IpAddr::V6(v6)).is_err());
+
+        let a6 = Ipv6Address::from(v6);
+        assert_eq!(a6.addr[..2], [0xfe, 0x80]);
+        assert_eq!(Ipv6Addr::from(a6), v6);
+        assert_eq!(format!("{}", a6), "fe80::211:22ff:fe33:4455");
+        assert_eq!(Ipv6Address::try_from(IpAddr::V6(v6)), Ok(a6));
+        assert!(Ipv6Address::try_from(IpAddr::V4(v4)).is_err());
+
+        let ip = IpAddress::from(IpAddr::V4(v4));
+        assert_eq!(ip.v4(), a4);
+        assert_eq!(ip.v6().addr[4..], [0; 12]);
+        assert_eq!(ip.view(false), IpAddressView::V4(a4));
+        assert_eq!(ip.to_ip_addr(false), IpAddr::V4(v4));
+        assert_eq!(format!("{}", ip.view(false)), "192.168.0.1");
+
+        let ip = IpAddress::from(a6);
+        assert_eq!(ip.view(true), IpAddressView::V6(a6));
+        assert_eq!(ip.to_ip_addr(true), IpAddr::V6(v6));
+        assert_eq!(IpAddress::from(ip.view(true)).v6(), a6);
+        assert!(format!("{:?}", ip).starts_with("IpAddress { addr: [254, 128, "));
+
+        let mac = MacAddress::from([0x00, 0x11, 0x22, 0xaa, 0xbb, 0xcc]);
+        assert_eq!(mac.addr[6..], [0; 26]);
+        assert_eq!(format!("{}", mac.display(6)), "00:11:22:aa:bb:cc");
+        assert_eq!(format!("{}", mac.display(2)), "00:11");
+        assert_eq!(format!("{}", mac.display(0)), "");
+        assert_eq!(format!("{}", mac.display(64)).len(), 32 * 3 - 1);
+        assert_eq!(MacAddress::from_hw_address(&[0x00, 0x11, 0x22, 0xaa, 0xbb, 0xcc]), Some(mac));
+        assert_eq!(MacAddress::from_hw_address(&[0xff; 32]).unwrap().addr, [0xff; 32]);
+        assert_eq!(MacAddress::from_hw_address(&[0xff; 33]), None);
+    }
+
+    // Verify UCS-2 Strings
+    //
+    // Test the compile-time literal macros, as well as the validation and
+    // conversions of `CStr16`.
+    #[test]
+    fn cstr16() {
+        const HELLO: [Char16; 6] = ucs2!("Hello");
+        assert_eq!(HELLO, [0x48, 0x65, 0x6c, 0x6c, 0x6f, 0x00]);
+        assert_eq!(ucs2!(""), [0]);
+        assert_eq!(ucs2!("\u{e4}\u{20ac}\u{ffff}"), [0xe4, 0x20ac, 0xffff, 0]);
+
+        let s = cstr16!("Hello \u{20ac}!");
+        assert_eq!(s.len(), 8);
+        assert!(!s.is_empty());
+        assert_eq!(s.as_slice_with_nul().len(), 9);
+        assert_eq!(s, "Hello \u{20ac}!");
+        assert_eq!(format!("{}", s), "Hello \u{20ac}!");
+        assert_eq!(format!("{:?}", cstr16!("a\"b")), "\"a\\\"b\"");
+        assert!(cstr16!("").is_empty());
+
+        let p = unsafe { CStr16::from_ptr(s.as_ptr()) }.unwrap();
+        assert_eq!(p, s);
+        assert_eq!(p.as_ptr(), s.as_ptr());
+
+        assert_eq!(CStr16::from_slice_with_nul(&HELLO).unwrap(), "Hello");
+        assert_eq!(
+            CStr16::from_slice_with_nul(&HELLO[..5]),
+            Err(CStr16Error::NotNulTerminated)
+        );
+        assert_eq!(
+            CStr16::from_slice_with_nul(&[0x41, 0, 0x41, 0]),
+            Err(CStr16Error::InteriorNul(1))
+        );
+        assert_eq!(
+            CStr16::from_slice_with_nul(&[0x41, 0xd800, 0]),
+            Err(CStr16Error::InvalidChar(1))
+        );
+        assert_eq!(
+            CStr16::from_slice_with_nul(&[]),
+            Err(CStr16Error::NotNulTerminated)
+        );
+
+        let mut buf = [0xffff; 9];
+        let v = CStr16::from_str_with_buf("Hello \u{20ac}!", &mut buf).unwrap();
+        assert_eq!(v, s);
+        let mut buf = [0; 8];
+        assert_eq!(
+            CStr16::from_str_with_buf("Hello \u{20ac}!", &mut buf),
+            Err(CStr16Error::BufferTooSmall)
+        );
+        assert_eq!(
+            CStr16::from_str_with_buf("a\u{1f600}", &mut buf),
+            Err(CStr16Error::InvalidChar(1))
+        );
+        assert_eq!(
+            CStr16::from_str_with_buf("a\0", &mut buf),
+            Err(CStr16Error::InteriorNul(1))
+        );
+    }
+
+    // Verify Status Results
+    //
+    // Test the conversion of status codes to results, distinguishing errors,
+    // warnings, and success.
+    #[test]
+    fn status_result() {
+        let r = Status::SUCCESS.to_result().unwrap();
+        assert!(!r.is_warning());
+        assert_eq!(r.status, Status::SUCCESS);
+
+        let r = Status::WARN_STALE_DATA.to_result_with(|| 71).unwrap();
+        assert!(r.is_warning());
+        assert_eq!(r.map(|v| v + 1).value, 72);
+        assert_eq!(r.into_value(), 71);
+
+        let e = Status::NOT_FOUND.to_result_with(|| -> u8 { unreachable!() }).unwrap_err();
+        assert_eq!(e.status(), Status::NOT_FOUND);
+        assert_eq!(Status::from(e), Status::NOT_FOUND);
+        assert!(!e.is_buffer_too_small());
+        assert_eq!(format!("{}", e), "Not Found");
+
+        assert_eq!(StatusError::new(Status::SUCCESS), None);
+        assert_eq!(StatusError::new(Status::WARN_FILE_SYSTEM), None);
+        assert!(StatusError::new(Status::BUFFER_TOO_SMALL).unwrap().is_buffer_too_small());
+
+        // Verify errors can be propagated as trait objects.
+        let f = || -> Result<(), Box<dyn core::error::Error>> {
+            Status::ABORTED.to_result()?;
+            Ok(())
+        };
+        assert_eq!(f().unwrap_err().to_string(), "Aborted");
+    }
+
+    // Verify BUFFER_TOO_SMALL Retries
+    //
+    // Simulate a UEFI function whose required size grows once, and verify
+    // the retry helper grows the buffer accordingly.
+    #[test]
+    fn status_retry() {
+        let mut required = 4;
+        let mut calls = 0;
+        let mut buffer = Vec::new();
+        let r = retry_buffer_too_small(
+            &mut buffer,
+            0,
+            |b: &mut Vec<u8>, size| {
+                b.resize(size, 0);
+                Ok(())
+            },
+            |b, size| {
+                calls += 1;
+                if *size < required {
+                    *size = required;
+                    required = 8;
+                    return Status::BUFFER_TOO_SMALL;
+                }
+                b[..required].fill(1);
+                *size = required;
+                Status::WARN_STALE_DATA
+            },
+        )
+        .unwrap();
+        assert_eq!(calls, 3);
+        assert_eq!(r.value, 8);
+        assert!(r.is_warning());
+        assert_eq!(buffer, [1; 8]);
+
+        // A resize failure is propagated.
+        let r = retry_buffer_too_small(
+            &mut buffer,
+            0,
+            |_, _| Err(StatusError::new(Status::OUT_OF_RESOURCES).unwrap()),
+            |_, size| {
+                *size = 1;
+                Status::BUFFER_TOO_SMALL
+            },
+        );
+        assert_eq!(r.unwrap_err().status(), Status::OUT_OF_RESOURCES);
+
+        // BUFFER_TOO_SMALL without size increase is an error.
+        let r = retry_buffer_too_small(
+            &mut buffer,
+            8,
+            |_, _| Ok(()),
+            |_, _| Status::BUFFER_TOO_SMALL,
+        );
+        assert!(r.unwrap_err().is_buffer_too_small());
+    }
+
     // Verify Guid Manipulations
     //
     // Test that creation of Guids from fields and bytes yields the expected
@@ -1068,4 +2220,51 @@ mod tests {
         assert_eq!(g_fields.as_bytes(), &bytes);
         assert_eq!(g_bytes.as_fields(), fields);
     }
+
+    // Verify Guid Text Representation
+    //
+    // Test that Guids are formatted in their canonical form, and that the
+    // parser accepts exactly that form and correctly round-trips.
+    #[test]
+    fn guid_text() {
+        let g = Guid::from_fields(
+            0x550e8400,
+            0xe29b,
+            0x41d4,
+            0xa7,
+            0x16,
+            &[0x44, 0x66, 0x55, 0x44, 0x00, 0x00],
+        );
+        const G: Guid = guid!("550e8400-e29b-41d4-a716-446655440000");
+
+        assert_eq!(format!("{}", g), "550e8400-e29b-41d4-a716-446655440000");
+        assert_eq!(format!("{:x}", g), "550e8400-e29b-41d4-a716-446655440000");
+        assert_eq!(format!("{:X}", g), "550E8400-E29B-41D4-A716-446655440000");
+        assert_eq!(G, g);
+        assert_eq!(Guid::parse("550E8400-E29B-41D4-A716-446655440000"), Ok(g));
+        assert_eq!("550e8400-e29b-41d4-a716-446655440000".parse::<Guid>(), Ok(g));
+        assert_eq!(format!("{}", guid!("00000000-0000-0000-0000-00000000000a")).parse(), Ok(
+            Guid::from_fields(0, 0, 0, 0, 0, &[0, 0, 0, 0, 0, 0x0a]),
+        ));
+
+        assert!(Guid::parse("").is_err());
+        assert!(Guid::parse("550e8400-e29b-41d4-a716-44665544000").is_err());
+        assert!(Guid::parse("550e8400-e29b-41d4-a716-4466554400000").is_err());
+        assert!(Guid::parse("550e8400-e29b-41d4-a716+446655440000").is_err());
+        assert!(Guid::parse("550e8400-e29b-41d4-a7-16446655440000").is_err());
+        assert!(Guid::parse("550e8400-e29b-41d4-a716-44665544000g").is_err());
+        assert!(Guid::parse("{50e8400-e29b-41d4-a716-44665544000}").is_err());
+        assert!(Guid::parse("550e8400-e29b-41d4-a716-4466554400\u{e9}").is_err());
+    }
+
+    // Verify the CRC32 implementation against the well-known check value,
+    // including chaining and const evaluation.
+    #[test]
+    fn crc() {
+        const CHECK: u32 = crc32(0, b"123456789");
+
+        assert_eq!(crc32(0, b""), 0);
+        assert_eq!(CHECK, 0xcbf43926);
+        assert_eq!(crc32(crc32(0, b"1234"), b"56789"), 0xcbf43926);
+    }
 }
@@ -1,7 +1,36 @@
-/// UEFI GUID Partition Tables
-///
-/// This module defines the data-types related to the new partition tables
-/// introduced with UEFI, replacing the old Master Boot Records (MBRs).
+//! UEFI GUID Partition Tables
+//!
+//! This module defines the data-types related to the new partition tables
+//! introduced with UEFI, replacing the old Master Boot Records (MBRs).
+
+pub const PARTITION_TABLE_HEADER_SIGNATURE: u64 = 0x5452415020494645u64; // "EFI PART"
+pub const PARTITION_TABLE_HEADER_REVISION: u32 = 0x00010000u32;
+
+/// Size of the partition table header as defined by the specification. The
+/// `header_size` field can be bigger, in which case the remainder of the
+/// block is reserved and must be zero.
+pub const PARTITION_TABLE_HEADER_SIZE: u32 = 92u32;
+
+/// Minimum size of the partition entry array in bytes, as mandated by the
+/// specification. This reserves space for at least 128 entries.
+pub const PARTITION_ENTRY_ARRAY_MIN_SIZE: usize = 16384;
+
+// MSRV(aligned-and-packed): `EFI_PARTITION_TABLE_HEADER` is 1-byte aligned,
+//     like `EFI_PARTITION_ENTRY`. See below for details.
+#[derive(Clone, Copy, Debug)]
+#[repr(C, packed(1))]
+pub struct PartitionTableHeader<__PrivateGuid = crate::base::Guid> {
+    pub header: crate::system::TableHeader,
+    pub my_lba: crate::base::Lba,
+    pub alternate_lba: crate::base::Lba,
+    pub first_usable_lba: crate::base::Lba,
+    pub last_usable_lba: crate::base::Lba,
+    pub disk_guid: __PrivateGuid,
+    pub partition_entry_lba: crate::base::Lba,
+    pub number_of_partition_entries: u32,
+    pub size_of_partition_entry: u32,
+    pub partition_entry_array_crc32: u32,
+}
 
 // MSRV(aligned-and-packed): `EFI_PARTITION_ENTRY` is 1-byte aligned. We need
 //     `packed(1)` to get this in Rust. Unfortunately, Rust forbids embedding
@@ -19,6 +48,1546 @@ pub struct PartitionEntry<__PrivateGuid = crate::base::Guid> {
     pub partition_name: [crate::base::Char16; 36],
 }
 
+//
+// Partition Attributes
+//
+// The attributes of a partition entry are split into a generic part defined
+// by the specification (bits 0 to 47, mostly reserved), and a type-specific
+// part (bits 48 to 63), whose meaning depends on the partition type GUID.
+// The type-specific bits of Microsoft Basic Data partitions and the Linux
+// partitions of the Discoverable Partitions Specification are provided here.
+//
+
+pub const PARTITION_ATTRIBUTE_REQUIRED: u64 = 0x0000000000000001u64;
+pub const PARTITION_ATTRIBUTE_NO_BLOCK_IO_PROTOCOL: u64 = 0x0000000000000002u64;
+pub const PARTITION_ATTRIBUTE_LEGACY_BIOS_BOOTABLE: u64 = 0x0000000000000004u64;
+pub const PARTITION_ATTRIBUTE_TYPE_SPECIFIC_MASK: u64 = 0xffff000000000000u64;
+pub const PARTITION_ATTRIBUTE_TYPE_SPECIFIC_SHIFT: u32 = 48u32;
+
+pub const PARTITION_ATTRIBUTE_MICROSOFT_READ_ONLY: u64 = 0x1000000000000000u64;
+pub const PARTITION_ATTRIBUTE_MICROSOFT_SHADOW_COPY: u64 = 0x2000000000000000u64;
+pub const PARTITION_ATTRIBUTE_MICROSOFT_HIDDEN: u64 = 0x4000000000000000u64;
+pub const PARTITION_ATTRIBUTE_MICROSOFT_NO_DRIVE_LETTER: u64 = 0x8000000000000000u64;
+
+pub const PARTITION_ATTRIBUTE_LINUX_GROW_FS: u64 = 0x0800000000000000u64;
+pub const PARTITION_ATTRIBUTE_LINUX_READ_ONLY: u64 = 0x1000000000000000u64;
+pub const PARTITION_ATTRIBUTE_LINUX_NO_AUTO: u64 = 0x8000000000000000u64;
+
+//
+// Partition Types
+//
+// The partition type GUID of a partition entry describes the content and
+// purpose of a partition. The specification only defines the unused, EFI
+// system, and legacy MBR partition types. The remaining types are defined by
+// the respective operating system vendors, or by the Discoverable Partitions
+// Specification of the UAPI group, and are provided here for convenience.
+//
+
+pub const PART_TYPE_UNUSED_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x00000000,
+    0x0000,
+    0x0000,
+    0x00,
+    0x00,
+    &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+);
+
+pub const PART_TYPE_EFI_SYSTEM_PART_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xc12a7328,
+    0xf81f,
+    0x11d2,
+    0xba,
+    0x4b,
+    &[0x00, 0xa0, 0xc9, 0x3e, 0xc9, 0x3b],
+);
+
+pub const PART_TYPE_LEGACY_MBR_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x024dee41,
+    0x33e7,
+    0x11d3,
+    0x9d,
+    0x69,
+    &[0x00, 0x08, 0xc7, 0x81, 0xf3, 0x9f],
+);
+
+pub const PART_TYPE_BIOS_BOOT_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x21686148,
+    0x6449,
+    0x6e6f,
+    0x74,
+    0x4e,
+    &[0x65, 0x65, 0x64, 0x45, 0x46, 0x49],
+);
+
+pub const PART_TYPE_MICROSOFT_RESERVED_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xe3c9e316,
+    0x0b5c,
+    0x4db8,
+    0x81,
+    0x7d,
+    &[0xf9, 0x2d, 0xf0, 0x02, 0x15, 0xae],
+);
+
+pub const PART_TYPE_MICROSOFT_BASIC_DATA_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xebd0a0a2,
+    0xb9e5,
+    0x4433,
+    0x87,
+    0xc0,
+    &[0x68, 0xb6, 0xb7, 0x26, 0x99, 0xc7],
+);
+
+pub const PART_TYPE_MICROSOFT_LDM_METADATA_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x5808c8aa,
+    0x7e8f,
+    0x42e0,
+    0x85,
+    0xd2,
+    &[0xe1, 0xe9, 0x04, 0x34, 0xcf, 0xb3],
+);
+
+pub const PART_TYPE_MICROSOFT_LDM_DATA_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xaf9b60a0,
+    0x1431,
+    0x4f62,
+    0xbc,
+    0x68,
+    &[0x33, 0x11, 0x71, 0x4a, 0x69, 0xad],
+);
+
+pub const PART_TYPE_MICROSOFT_RECOVERY_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xde94bba4,
+    0x06d1,
+    0x4d40,
+    0xa1,
+    0x6a,
+    &[0xbf, 0xd5, 0x01, 0x79, 0xd6, 0xac],
+);
+
+pub const PART_TYPE_LINUX_FILESYSTEM_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x0fc63daf,
+    0x8483,
+    0x4772,
+    0x8e,
+    0x79,
+    &[0x3d, 0x69, 0xd8, 0x47, 0x7d, 0xe4],
+);
+
+pub const PART_TYPE_LINUX_SWAP_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x0657fd6d,
+    0xa4ab,
+    0x43c4,
+    0x84,
+    0xe5,
+    &[0x09, 0x33, 0xc8, 0x4b, 0x4f, 0x4f],
+);
+
+pub const PART_TYPE_LINUX_LVM_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xe6d6d379,
+    0xf507,
+    0x44c2,
+    0xa2,
+    0x3c,
+    &[0x23, 0x8f, 0x2a, 0x3d, 0xf9, 0x28],
+);
+
+pub const PART_TYPE_LINUX_RAID_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xa19d880f,
+    0x05fc,
+    0x4d3b,
+    0xa0,
+    0x06,
+    &[0x74, 0x3f, 0x0f, 0x84, 0x91, 0x1e],
+);
+
+pub const PART_TYPE_LINUX_LUKS_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xca7d7ccb,
+    0x63ed,
+    0x4c53,
+    0x86,
+    0x1c,
+    &[0x17, 0x42, 0x53, 0x60, 0x59, 0xcc],
+);
+
+pub const PART_TYPE_LINUX_RESERVED_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x8da63339,
+    0x0007,
+    0x60c0,
+    0xc4,
+    0x36,
+    &[0x08, 0x3a, 0xc8, 0x23, 0x09, 0x08],
+);
+
+pub const PART_TYPE_LINUX_HOME_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x933ac7e1,
+    0x2eb4,
+    0x4f13,
+    0xb8,
+    0x44,
+    &[0x0e, 0x14, 0xe2, 0xae, 0xf9, 0x15],
+);
+
+pub const PART_TYPE_LINUX_SRV_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x3b8f8425,
+    0x20e0,
+    0x4f3b,
+    0x90,
+    0x7f,
+    &[0x1a, 0x25, 0xa7, 0x6f, 0x98, 0xe8],
+);
+
+pub const PART_TYPE_LINUX_VAR_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x4d21b016,
+    0xb534,
+    0x45c2,
+    0xa9,
+    0xfb,
+    &[0x5c, 0x16, 0xe0, 0x91, 0xfd, 0x2d],
+);
+
+pub const PART_TYPE_LINUX_VAR_TMP_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x7ec6f557,
+    0x3bc5,
+    0x4aca,
+    0xb2,
+    0x93,
+    &[0x16, 0xef, 0x5d, 0xf6, 0x39, 0xd1],
+);
+
+pub const PART_TYPE_LINUX_XBOOTLDR_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xbc13c2ff,
+    0x59e6,
+    0x4262,
+    0xa3,
+    0x52,
+    &[0xb2, 0x75, 0xfd, 0x6f, 0x71, 0x72],
+);
+
+pub const PART_TYPE_LINUX_ROOT_X86_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x44479540,
+    0xf297,
+    0x41b2,
+    0x9a,
+    0xf7,
+    &[0xd1, 0x31, 0xd5, 0xf0, 0x45, 0x8a],
+);
+
+pub const PART_TYPE_LINUX_ROOT_X86_64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x4f68bce3,
+    0xe8cd,
+    0x4db1,
+    0x96,
+    0xe7,
+    &[0xfb, 0xca, 0xf9, 0x84, 0xb7, 0x09],
+);
+
+pub const PART_TYPE_LINUX_ROOT_ARM_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x69dad710,
+    0x2ce4,
+    0x4e3c,
+    0xb1,
+    0x6c,
+    &[0x21, 0xa1, 0xd4, 0x9a, 0xbe, 0xd3],
+);
+
+pub const PART_TYPE_LINUX_ROOT_ARM64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xb921b045,
+    0x1df0,
+    0x41c3,
+    0xaf,
+    0x44,
+    &[0x4c, 0x6f, 0x28, 0x0d, 0x3f, 0xae],
+);
+
+pub const PART_TYPE_LINUX_ROOT_IA64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x993d8d3d,
+    0xf80e,
+    0x4225,
+    0x85,
+    0x5a,
+    &[0x9d, 0xaf, 0x8e, 0xd7, 0xea, 0x97],
+);
+
+pub const PART_TYPE_LINUX_ROOT_LOONGARCH64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x77055800,
+    0x792c,
+    0x4f94,
+    0xb3,
+    0x9a,
+    &[0x98, 0xc9, 0x1b, 0x76, 0x2b, 0xb6],
+);
+
+pub const PART_TYPE_LINUX_ROOT_RISCV32_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x60d5a7fe,
+    0x8e7d,
+    0x435c,
+    0xb7,
+    0x14,
+    &[0x3d, 0xd8, 0x16, 0x21, 0x44, 0xe1],
+);
+
+pub const PART_TYPE_LINUX_ROOT_RISCV64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x72ec70a6,
+    0xcf74,
+    0x40e6,
+    0xbd,
+    0x49,
+    &[0x4b, 0xda, 0x08, 0xe8, 0xf2, 0x24],
+);
+
+pub const PART_TYPE_LINUX_USR_X86_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x75250d76,
+    0x8cc6,
+    0x458e,
+    0xbd,
+    0x66,
+    &[0xbd, 0x47, 0xcc, 0x81, 0xa8, 0x12],
+);
+
+pub const PART_TYPE_LINUX_USR_X86_64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x8484680c,
+    0x9521,
+    0x48c6,
+    0x9c,
+    0x11,
+    &[0xb0, 0x72, 0x06, 0x56, 0xf6, 0x9e],
+);
+
+pub const PART_TYPE_LINUX_USR_ARM_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x7d0359a3,
+    0x02b3,
+    0x4f0a,
+    0x86,
+    0x5c,
+    &[0x65, 0x44, 0x03, 0xe7, 0x06, 0x25],
+);
+
+pub const PART_TYPE_LINUX_USR_ARM64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xb0e01050,
+    0xee5f,
+    0x4390,
+    0x94,
+    0x9a,
+    &[0x91, 0x01, 0xb1, 0x71, 0x04, 0xe9],
+);
+
+pub const PART_TYPE_LINUX_USR_IA64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x4301d2a6,
+    0x4e3b,
+    0x4b2a,
+    0xbb,
+    0x94,
+    &[0x9e, 0x0b, 0x2c, 0x42, 0x25, 0xea],
+);
+
+pub const PART_TYPE_LINUX_USR_LOONGARCH64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xe611c702,
+    0x575c,
+    0x4cbe,
+    0x9a,
+    0x46,
+    &[0x43, 0x4f, 0xa0, 0xbf, 0x7e, 0x3f],
+);
+
+pub const PART_TYPE_LINUX_USR_RISCV32_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xb933fb22,
+    0x5c3f,
+    0x4f91,
+    0xaf,
+    0x90,
+    &[0xe2, 0xbb, 0x0f, 0xa5, 0x07, 0x02],
+);
+
+pub const PART_TYPE_LINUX_USR_RISCV64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xbeaec34b,
+    0x8442,
+    0x439b,
+    0xa4,
+    0x0b,
+    &[0x98, 0x43, 0x81, 0xed, 0x09, 0x7d],
+);
+
+pub const PART_TYPE_LINUX_ROOT_VERITY_X86_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xd13c5d3b,
+    0xb5d1,
+    0x422a,
+    0xb2,
+    0x9f,
+    &[0x94, 0x54, 0xfd, 0xc8, 0x9d, 0x76],
+);
+
+pub const PART_TYPE_LINUX_ROOT_VERITY_X86_64_GUID: crate::base::Guid =
+    crate::base::Guid::from_fields(
+        0x2c7357ed,
+        0xebd2,
+        0x46d9,
+        0xae,
+        0xc1,
+        &[0x23, 0xd4, 0x37, 0xec, 0x2b, 0xf5],
+    );
+
+pub const PART_TYPE_LINUX_ROOT_VERITY_ARM_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x7386cdf2,
+    0x203c,
+    0x47a9,
+    0xa4,
+    0x98,
+    &[0xf2, 0xec, 0xce, 0x45, 0xa2, 0xd6],
+);
+
+pub const PART_TYPE_LINUX_ROOT_VERITY_ARM64_GUID: crate::base::Guid =
+    crate::base::Guid::from_fields(
+        0xdf3300ce,
+        0xd69f,
+        0x4c92,
+        0x97,
+        0x8c,
+        &[0x9b, 0xfb, 0x0f, 0x38, 0xd8, 0x20],
+    );
+
+pub const PART_TYPE_LINUX_ROOT_VERITY_IA64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x86ed10d5,
+    0xb607,
+    0x45bb,
+    0x89,
+    0x57,
+    &[0xd3, 0x50, 0xf2, 0x3d, 0x05, 0x71],
+);
+
+pub const PART_TYPE_LINUX_ROOT_VERITY_LOONGARCH64_GUID: crate::base::Guid =
+    crate::base::Guid::from_fields(
+        0xf3393b22,
+        0xe9af,
+        0x4613,
+        0xa9,
+        0x48,
+        &[0x9d, 0x3b, 0xfb, 0xd0, 0xc5, 0x35],
+    );
+
+pub const PART_TYPE_LINUX_ROOT_VERITY_RISCV32_GUID: crate::base::Guid =
+    crate::base::Guid::from_fields(
+        0xae0253be,
+        0x1167,
+        0x4007,
+        0xac,
+        0x68,
+        &[0x43, 0x92, 0x6c, 0x14, 0xc5, 0xde],
+    );
+
+pub const PART_TYPE_LINUX_ROOT_VERITY_RISCV64_GUID: crate::base::Guid =
+    crate::base::Guid::from_fields(
+        0xb6ed5582,
+        0x440b,
+        0x4209,
+        0xb8,
+        0xda,
+        &[0x5f, 0xf7, 0xc4, 0x19, 0xea, 0x3d],
+    );
+
+pub const PART_TYPE_LINUX_USR_VERITY_X86_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x8f461b0d,
+    0x14ee,
+    0x4e81,
+    0x9a,
+    0xa9,
+    &[0x04, 0x9b, 0x6f, 0xb9, 0x7a, 0xbd],
+);
+
+pub const PART_TYPE_LINUX_USR_VERITY_X86_64_GUID: crate::base::Guid =
+    crate::base::Guid::from_fields(
+        0x77ff5f63,
+        0xe7b6,
+        0x4633,
+        0xac,
+        0xf4,
+        &[0x15, 0x65, 0xb8, 0x64, 0xc0, 0xe6],
+    );
+
+pub const PART_TYPE_LINUX_USR_VERITY_ARM_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xc215d751,
+    0x7bcd,
+    0x4649,
+    0xbe,
+    0x90,
+    &[0x66, 0x27, 0x49, 0x0a, 0x4c, 0x05],
+);
+
+pub const PART_TYPE_LINUX_USR_VERITY_ARM64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x6e11a4e7,
+    0xfbca,
+    0x4ded,
+    0xb9,
+    0xe9,
+    &[0xe1, 0xa5, 0x12, 0xbb, 0x66, 0x4e],
+);
+
+pub const PART_TYPE_LINUX_USR_VERITY_IA64_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x6a491e03,
+    0x3be7,
+    0x4545,
+    0x8e,
+    0x38,
+    &[0x83, 0x32, 0x0e, 0x0e, 0xa8, 0x80],
+);
+
+pub const PART_TYPE_LINUX_USR_VERITY_LOONGARCH64_GUID: crate::base::Guid =
+    crate::base::Guid::from_fields(
+        0xf46b2c26,
+        0x59ae,
+        0x48f0,
+        0x91,
+        0x06,
+        &[0xc5, 0x0e, 0xd4, 0x7f, 0x67, 0x3d],
+    );
+
+pub const PART_TYPE_LINUX_USR_VERITY_RISCV32_GUID: crate::base::Guid =
+    crate::base::Guid::from_fields(
+        0xcb1ee4e3,
+        0x8cd0,
+        0x4136,
+        0xa0,
+        0xa4,
+        &[0xaa, 0x61, 0xa3, 0x2e, 0x87, 0x30],
+    );
+
+pub const PART_TYPE_LINUX_USR_VERITY_RISCV64_GUID: crate::base::Guid =
+    crate::base::Guid::from_fields(
+        0x8f1056be,
+        0x9b05,
+        0x47c4,
+        0x81,
+        0xd6,
+        &[0xbe, 0x53, 0x12, 0x8e, 0x5b, 0x54],
+    );
+
+pub const PART_TYPE_APPLE_HFS_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x48465300,
+    0x0000,
+    0x11aa,
+    0xaa,
+    0x11,
+    &[0x00, 0x30, 0x65, 0x43, 0xec, 0xac],
+);
+
+pub const PART_TYPE_APPLE_APFS_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x7c3457ef,
+    0x0000,
+    0x11aa,
+    0xaa,
+    0x11,
+    &[0x00, 0x30, 0x65, 0x43, 0xec, 0xac],
+);
+
+pub const PART_TYPE_CHROMEOS_KERNEL_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xfe3a2a5d,
+    0x4f32,
+    0x41a7,
+    0xb7,
+    0x25,
+    &[0xac, 0xcc, 0x32, 0x85, 0xa3, 0x09],
+);
+
+pub const PART_TYPE_CHROMEOS_ROOTFS_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x3cb8e202,
+    0x3b7e,
+    0x47dd,
+    0x8a,
+    0x3c,
+    &[0x7f, 0xf2, 0xa1, 0x3c, 0xfc, 0xec],
+);
+
+//
+// Master Boot Records
+//
+// GPT disks carry a protective MBR in their first block, which marks the
+// entire disk as used to legacy software. The MBR definitions are provided
+// here, so protective, hybrid and legacy MBRs can be created and inspected.
+//
+
+/// Signature of a valid MBR, stored little-endian in its last 2 bytes.
+pub const MBR_SIGNATURE: u16 = 0xaa55u16;
+
+/// OS type of an unused MBR partition record.
+pub const MBR_TYPE_UNUSED: u8 = 0x00u8;
+/// OS type of a DOS extended partition (CHS addressed).
+pub const MBR_TYPE_EXTENDED_DOS: u8 = 0x05u8;
+/// OS type of a Windows extended partition (LBA addressed).
+pub const MBR_TYPE_EXTENDED_WINDOWS: u8 = 0x0fu8;
+/// OS type of a Linux extended partition.
+pub const MBR_TYPE_EXTENDED_LINUX: u8 = 0x85u8;
+/// OS type of the partition record of a protective MBR, covering the GPT
+/// disk.
+pub const MBR_TYPE_PROTECTIVE: u8 = 0xeeu8;
+/// OS type of an EFI system partition on a legacy MBR disk.
+pub const MBR_TYPE_EFI_SYSTEM_PARTITION: u8 = 0xefu8;
+
+/// Boot indicator of a partition record marked as active (bootable).
+pub const MBR_BOOT_INDICATOR_ACTIVE: u8 = 0x80u8;
+
+/// MBR Partition Record
+///
+/// This is one of the 4 partition records of an MBR. The LBA and size are
+/// stored little-endian. Unused records have an OS type of
+/// `MBR_TYPE_UNUSED`.
+#[derive(Clone, Copy, Debug)]
+#[repr(C, packed(1))]
+pub struct MbrPartitionRecord {
+    pub boot_indicator: u8,
+    pub starting_chs: [u8; 3],
+    pub os_type: u8,
+    pub ending_chs: [u8; 3],
+    pub starting_lba: u32,
+    pub size_in_lba: u32,
+}
+
+/// Master Boot Record
+///
+/// This is the 512-byte layout of a legacy MBR, as stored in the first
+/// block of a disk. The structure is 1-byte aligned, so it can be placed at
+/// any offset. Only the first 512 bytes of the first block are used, even if
+/// the block size is bigger.
+#[derive(Clone, Copy, Debug)]
+#[repr(C, packed(1))]
+pub struct MasterBootRecord {
+    pub boot_strap_code: [u8; 440],
+    pub unique_mbr_signature: u32,
+    pub unknown: u16,
+    pub partition: [MbrPartitionRecord; 4],
+    pub signature: u16,
+}
+
+/// Partition Attributes
+///
+/// This wraps the `attributes` field of a partition entry and provides typed
+/// accessors for the individual bits. It is binary compatible to a `u64`.
+/// The type-specific bits are available as a raw 16-bit value, as well as
+/// decoded for Microsoft Basic Data partitions and Linux partitions. Note
+/// that the decoding of type-specific bits does not check the partition
+/// type. Use `PartitionEntry::basic_data_attributes()` or
+/// `PartitionEntry::linux_attributes()` for that.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct PartitionAttributes(u64);
+
+/// Microsoft Basic Data Partition Attributes
+///
+/// Type-specific attributes of partitions with type
+/// `PART_TYPE_MICROSOFT_BASIC_DATA_GUID`.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct BasicDataAttributes {
+    pub read_only: bool,
+    pub shadow_copy: bool,
+    pub hidden: bool,
+    pub no_drive_letter: bool,
+}
+
+/// Linux Partition Attributes
+///
+/// Type-specific attributes of Linux partitions as defined by the
+/// Discoverable Partitions Specification. `no_auto` suppresses automatic
+/// discovery, `read_only` requests a read-only mount, and `grow_fs` requests
+/// the file system to be grown to the partition size on first mount.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct LinuxAttributes {
+    pub no_auto: bool,
+    pub read_only: bool,
+    pub grow_fs: bool,
+}
+
+/// Discoverable Partition Types
+///
+/// The Discoverable Partitions Specification defines separate partition types
+/// for the root and `/usr` file systems, as well as their dm-verity hash
+/// partitions, for each architecture. This groups the types of a single
+/// architecture. Use `from_arch()` to look them up.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct DiscoverablePartitionTypes {
+    pub root: crate::base::Guid,
+    pub root_verity: crate::base::Guid,
+    pub usr: crate::base::Guid,
+    pub usr_verity: crate::base::Guid,
+}
+
+/// Cylinder-Head-Sector Address
+///
+/// Legacy MBR partition records store the first and last sector of a
+/// partition both as LBA and as CHS address. The CHS address is packed into
+/// 3 bytes, with a 10-bit cylinder, an 8-bit head, and a 6-bit, 1-based
+/// sector number. Most software ignores the CHS addresses, since they
+/// cannot represent disks bigger than roughly 8GiB.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Chs {
+    pub cylinder: u16,
+    pub head: u8,
+    pub sector: u8,
+}
+
+/// Partition Table Parser Errors
+///
+/// This is returned by the partition table parser if the provided data does
+/// not describe a valid GUID Partition Table.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum ParseError {
+    /// The header block is smaller than the partition table header.
+    HeaderTruncated,
+    /// The header does not carry the `EFI PART` signature.
+    Signature,
+    /// The header revision is not supported.
+    Revision,
+    /// The header size is smaller than the partition table header, or bigger
+    /// than the block it is stored in.
+    HeaderSize,
+    /// The header checksum does not match.
+    HeaderCrc32,
+    /// The partition entry size is not a multiple of 128, or zero.
+    EntrySize,
+    /// The partition entry array is smaller than announced by the header.
+    EntriesTruncated,
+    /// The partition entry array checksum does not match.
+    EntriesCrc32,
+}
+
+/// Partition Table Writer Errors
+///
+/// This is returned by the partition table writer if the requested layout
+/// cannot be written to the provided disk image.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum WriteError {
+    /// The block size is not a power of two, or smaller than 512 bytes.
+    BlockSize,
+    /// The disk image is not a multiple of the block size, or too small to
+    /// hold both partition tables.
+    ImageSize,
+    /// The partition with the given index is outside of the usable area, or
+    /// ends before it starts.
+    PartitionRange(usize),
+    /// The partitions with the given indices overlap.
+    PartitionOverlap(usize, usize),
+    /// The header size is smaller than the partition table header, or bigger
+    /// than the block it is written to.
+    HeaderSize,
+}
+
+/// Partition Entry Iterator
+///
+/// This iterates the partition entries of an entry array as described by a
+/// partition table header. Entries are copied out of the array, honoring the
+/// entry size of the header rather than the size of `PartitionEntry`. Unused
+/// entries are yielded as well. Use `PartitionEntry::is_unused()` to skip
+/// them.
+#[derive(Clone, Debug)]
+pub struct PartitionEntries<'a> {
+    entries: &'a [u8],
+    size: usize,
+}
+
+impl PartitionTableHeader {
+    const OFFSET_CRC32: usize = 16;
+
+    /// Parse a Partition Table Header
+    ///
+    /// This takes the raw content of the block holding the partition table
+    /// header (usually LBA 1) and parses the header. The signature, revision,
+    /// header size, and header checksum are verified. The partition entry
+    /// array is not verified. Use `verify_entries()` or `parse()` for that.
+    ///
+    /// The block must include the entire header as described by its
+    /// `header_size` field. Any trailing data is ignored.
+    pub fn parse_header(block: &[u8]) -> Result<Self, ParseError> {
+        if block.len() < PARTITION_TABLE_HEADER_SIZE as usize {
+            return Err(ParseError::HeaderTruncated);
+        }
+
+        // SAFETY: The structure is 1-byte aligned, has no padding, and is
+        //         valid for any bit pattern. The length was checked above.
+        let header = unsafe { core::ptr::read_unaligned(block.as_ptr() as *const Self) };
+
+        if header.header.signature != PARTITION_TABLE_HEADER_SIGNATURE {
+            return Err(ParseError::Signature);
+        }
+        if (header.header.revision >> 16) != (PARTITION_TABLE_HEADER_REVISION >> 16) {
+            return Err(ParseError::Revision);
+        }
+
+        let header_size = header.header.header_size as usize;
+        if header_size < PARTITION_TABLE_HEADER_SIZE as usize || header_size > block.len() {
+            return Err(ParseError::HeaderSize);
+        }
+
+        // The checksum is calculated with the checksum field set to 0.
+        let mut crc = crate::base::crc32(0, &block[..Self::OFFSET_CRC32]);
+        crc = crate::base::crc32(crc, &[0; 4]);
+        crc = crate::base::crc32(crc, &block[Self::OFFSET_CRC32 + 4..header_size]);
+        if crc != header.header.crc32 {
+            return Err(ParseError::HeaderCrc32);
+        }
+
+        Ok(header)
+    }
+
+    /// Parse a Partition Table
+    ///
+    /// This parses the partition table header via `parse_header()` and then
+    /// verifies the partition entry array via `verify_entries()`. The
+    /// `entries` slice must start with the first partition entry. Trailing
+    /// data is ignored.
+    pub fn parse(block: &[u8], entries: &[u8]) -> Result<Self, ParseError> {
+        let header = Self::parse_header(block)?;
+        header.verify_entries(entries)?;
+        Ok(header)
+    }
+
+    /// Size of the Partition Entry Array
+    ///
+    /// Return the size of the partition entry array in bytes, as described by
+    /// this header. `None` is returned if the size overflows.
+    pub fn entries_size(&self) -> Option<usize> {
+        (self.number_of_partition_entries as usize)
+            .checked_mul(self.size_of_partition_entry as usize)
+    }
+
+    /// Verify a Partition Entry Array
+    ///
+    /// Verify that the partition entry array in `entries` matches the size
+    /// and checksum as described by this header. Trailing data is ignored.
+    pub fn verify_entries(&self, entries: &[u8]) -> Result<(), ParseError> {
+        let size = self.size_of_partition_entry as usize;
+        if size == 0 || size % core::mem::size_of::<PartitionEntry>() != 0 {
+            return Err(ParseError::EntrySize);
+        }
+
+        let total = match self.entries_size() {
+            Some(v) if v <= entries.len() => v,
+            _ => return Err(ParseError::EntriesTruncated),
+        };
+
+        if crate::base::crc32(0, &entries[..total]) != self.partition_entry_array_crc32 {
+            return Err(ParseError::EntriesCrc32);
+        }
+
+        Ok(())
+    }
+
+    /// Iterate Partition Entries
+    ///
+    /// Return an iterator over the partition entries in `entries`, using the
+    /// entry size and count of this header. Iteration stops early if the
+    /// array is truncated. This does not verify the checksum of the array.
+    pub fn entries<'a>(&self, entries: &'a [u8]) -> PartitionEntries<'a> {
+        let size = self.size_of_partition_entry as usize;
+        let total = match self.entries_size() {
+            Some(v) if size >= core::mem::size_of::<PartitionEntry>() => v.min(entries.len()),
+            _ => 0,
+        };
+
+        PartitionEntries {
+            entries: &entries[..total],
+            size,
+        }
+    }
+
+    /// Write a Partition Table Header
+    ///
+    /// Serialize this header into `block`, which must be at least
+    /// `header_size` bytes long. The entire block is cleared first, and the
+    /// header checksum is calculated and written as part of the header. The
+    /// `crc32` field of `self` is ignored.
+    ///
+    /// If `header_size` is smaller than the partition table header, or
+    /// bigger than `block`, `WriteError::HeaderSize` is returned and `block`
+    /// is left untouched.
+    pub fn write_header(&self, block: &mut [u8]) -> Result<(), WriteError> {
+        let header_size = self.header.header_size as usize;
+        if header_size < PARTITION_TABLE_HEADER_SIZE as usize || header_size > block.len() {
+            return Err(WriteError::HeaderSize);
+        }
+
+        block.fill(0);
+
+        let mut header = *self;
+        header.header.crc32 = 0;
+        // SAFETY: The structure is 1-byte aligned and has no padding. The
+        //         length was checked above.
+        unsafe {
+            core::ptr::write_unaligned(block.as_mut_ptr() as *mut Self, header);
+        }
+
+        let crc = crate::base::crc32(0, &block[..header_size]);
+        block[Self::OFFSET_CRC32..Self::OFFSET_CRC32 + 4].copy_from_slice(&crc.to_le_bytes());
+        Ok(())
+    }
+}
+
+/// Write GUID Partition Tables
+///
+/// Write a complete GPT layout into the disk image `image`, using the block
+/// size `block_size`. This writes the protective MBR into the first block,
+/// the primary header and entry array following it, as well as the backup
+/// entry array and backup header into the last blocks of the image. All
+/// blocks used by the partition tables are overwritten. All other blocks are
+/// left untouched.
+///
+/// The partition entry array is sized to hold all entries of `partitions`,
+/// but at least `PARTITION_ENTRY_ARRAY_MIN_SIZE` bytes, and is rounded up to
+/// fill entire blocks. Unused entries are cleared to zero. The partitions are
+/// written unmodified, and must be placed within the usable area of the disk
+/// without overlapping each other. Unused entries in `partitions` are not
+/// checked.
+///
+/// On success, the primary header is returned.
+pub fn write_image(
+    image: &mut [u8],
+    block_size: usize,
+    disk_guid: crate::base::Guid,
+    partitions: &[PartitionEntry],
+) -> Result<PartitionTableHeader, WriteError> {
+    let entry_size = core::mem::size_of::<PartitionEntry>();
+
+    if block_size < 512 || !block_size.is_power_of_two() {
+        return Err(WriteError::BlockSize);
+    }
+    if image.len() % block_size != 0 {
+        return Err(WriteError::ImageSize);
+    }
+
+    // Compute the layout. The entry array must span entire blocks. The
+    // block size is a power of two, so it can be rounded up via a mask.
+    let entries_size = core::mem::size_of_val(partitions).max(PARTITION_ENTRY_ARRAY_MIN_SIZE);
+    let entries_size = (entries_size + block_size - 1) & !(block_size - 1);
+    let entries_blocks = (entries_size / block_size) as u64;
+    let blocks = (image.len() / block_size) as u64;
+    let last_lba = blocks.checked_sub(1).ok_or(WriteError::ImageSize)?;
+    let first_usable_lba = 2 + entries_blocks;
+    let last_usable_lba = last_lba
+        .checked_sub(1 + entries_blocks)
+        .filter(|v| *v >= first_usable_lba)
+        .ok_or(WriteError::ImageSize)?;
+
+    // Verify the partitions are in range and do not overlap.
+    for (i, p) in partitions.iter().enumerate() {
+        if p.is_unused() {
+            continue;
+        }
+
+        let (start, end) = (p.starting_lba, p.ending_lba);
+        if start > end || start < first_usable_lba || end > last_usable_lba {
+            return Err(WriteError::PartitionRange(i));
+        }
+
+        for (j, q) in partitions[..i].iter().enumerate() {
+            if !q.is_unused() && start <= q.ending_lba && q.starting_lba <= end {
+                return Err(WriteError::PartitionOverlap(j, i));
+            }
+        }
+    }
+
+    let block = |lba: u64| lba as usize * block_size..(lba as usize + 1) * block_size;
+
+    // Write the protective MBR. It covers the entire disk, or as much as
+    // can be represented.
+    let mbr = &mut image[block(0)];
+    mbr.fill(0);
+    mbr[..512].copy_from_slice(MasterBootRecord::protective(last_lba).as_bytes());
+
+    // Write the primary and backup entry arrays, and remember the checksum.
+    let backup_entries_lba = last_lba - entries_blocks;
+    let primary_entries = block(2).start..block(2).start + entries_size;
+    let backup_entries = block(backup_entries_lba).start;
+
+    {
+        let entries = &mut image[primary_entries.clone()];
+        entries.fill(0);
+        for (p, chunk) in partitions.iter().zip(entries.chunks_exact_mut(entry_size)) {
+            // SAFETY: The structure is 1-byte aligned and has no padding. The
+            //         chunk has exactly the size of the structure.
+            unsafe {
+                core::ptr::write_unaligned(chunk.as_mut_ptr() as *mut PartitionEntry, *p);
+            }
+        }
+    }
+    image.copy_within(primary_entries.clone(), backup_entries);
+    let entries_crc32 = crate::base::crc32(0, &image[primary_entries]);
+
+    // Write the primary and backup headers.
+    let primary = PartitionTableHeader {
+        header: crate::system::TableHeader {
+            signature: PARTITION_TABLE_HEADER_SIGNATURE,
+            revision: PARTITION_TABLE_HEADER_REVISION,
+            header_size: PARTITION_TABLE_HEADER_SIZE,
+            crc32: 0,
+            reserved: 0,
+        },
+        my_lba: 1,
+        alternate_lba: last_lba,
+        first_usable_lba,
+        last_usable_lba,
+        disk_guid,
+        partition_entry_lba: 2,
+        number_of_partition_entries: (entries_size / entry_size) as u32,
+        size_of_partition_entry: entry_size as u32,
+        partition_entry_array_crc32: entries_crc32,
+    };
+    let backup = PartitionTableHeader {
+        my_lba: last_lba,
+        alternate_lba: 1,
+        partition_entry_lba: backup_entries_lba,
+        ..primary
+    };
+
+    primary.write_header(&mut image[block(1)])?;
+    backup.write_header(&mut image[block(last_lba)])?;
+
+    Ok(primary)
+}
+
+impl PartitionEntry {
+    /// Check whether an Entry is Unused
+    ///
+    /// Unused partition entries have a partition type GUID of all zeroes
+    /// (i.e., `PART_TYPE_UNUSED_GUID`).
+    pub fn is_unused(&self) -> bool {
+        let partition_type_guid = self.partition_type_guid;
+        partition_type_guid == PART_TYPE_UNUSED_GUID
+    }
+
+    /// Check whether an Entry is a Linux Partition
+    ///
+    /// Return true if the partition type is one of the Linux file system
+    /// types, including the types of the Discoverable Partitions
+    /// Specification. Swap, LVM, RAID, and LUKS partitions are not included,
+    /// since the Linux type-specific attributes do not apply to them.
+    pub fn is_linux(&self) -> bool {
+        let partition_type_guid = self.partition_type_guid;
+        [
+            PART_TYPE_LINUX_FILESYSTEM_GUID,
+            PART_TYPE_LINUX_HOME_GUID,
+            PART_TYPE_LINUX_SRV_GUID,
+            PART_TYPE_LINUX_VAR_GUID,
+            PART_TYPE_LINUX_VAR_TMP_GUID,
+            PART_TYPE_LINUX_XBOOTLDR_GUID,
+        ]
+        .contains(&partition_type_guid)
+            || PART_TYPE_DISCOVERABLE.iter().any(|(_, v)| {
+                partition_type_guid == v.root
+                    || partition_type_guid == v.root_verity
+                    || partition_type_guid == v.usr
+                    || partition_type_guid == v.usr_verity
+            })
+    }
+
+    /// Access the Partition Attributes
+    pub fn partition_attributes(&self) -> PartitionAttributes {
+        PartitionAttributes::from_u64(self.attributes)
+    }
+
+    /// Replace the Partition Attributes
+    pub fn set_partition_attributes(&mut self, v: PartitionAttributes) {
+        self.attributes = v.as_u64();
+    }
+
+    /// Decode Microsoft Basic Data Attributes
+    ///
+    /// Return the decoded type-specific attributes, or `None` if this is not
+    /// a Microsoft Basic Data partition.
+    pub fn basic_data_attributes(&self) -> Option<BasicDataAttributes> {
+        let partition_type_guid = self.partition_type_guid;
+        if partition_type_guid == PART_TYPE_MICROSOFT_BASIC_DATA_GUID {
+            Some(self.partition_attributes().basic_data())
+        } else {
+            None
+        }
+    }
+
+    /// Decode Linux Attributes
+    ///
+    /// Return the decoded type-specific attributes, or `None` if this is not
+    /// a Linux partition as determined by `is_linux()`.
+    pub fn linux_attributes(&self) -> Option<LinuxAttributes> {
+        if self.is_linux() {
+            Some(self.partition_attributes().linux())
+        } else {
+            None
+        }
+    }
+}
+
+const PART_TYPE_NAMES: &[(crate::base::Guid, &str)] = &[
+    (PART_TYPE_UNUSED_GUID, "Unused Entry"),
+    (PART_TYPE_EFI_SYSTEM_PART_GUID, "EFI System Partition"),
+    (PART_TYPE_LEGACY_MBR_GUID, "Legacy MBR Partition"),
+    (PART_TYPE_BIOS_BOOT_GUID, "BIOS Boot Partition"),
+    (PART_TYPE_MICROSOFT_RESERVED_GUID, "Microsoft Reserved Partition"),
+    (PART_TYPE_MICROSOFT_BASIC_DATA_GUID, "Microsoft Basic Data Partition"),
+    (PART_TYPE_MICROSOFT_LDM_METADATA_GUID, "Microsoft LDM Metadata Partition"),
+    (PART_TYPE_MICROSOFT_LDM_DATA_GUID, "Microsoft LDM Data Partition"),
+    (PART_TYPE_MICROSOFT_RECOVERY_GUID, "Microsoft Recovery Partition"),
+    (PART_TYPE_LINUX_FILESYSTEM_GUID, "Linux Filesystem Data"),
+    (PART_TYPE_LINUX_SWAP_GUID, "Linux Swap"),
+    (PART_TYPE_LINUX_LVM_GUID, "Linux LVM"),
+    (PART_TYPE_LINUX_RAID_GUID, "Linux RAID"),
+    (PART_TYPE_LINUX_LUKS_GUID, "Linux LUKS"),
+    (PART_TYPE_LINUX_RESERVED_GUID, "Linux Reserved"),
+    (PART_TYPE_LINUX_HOME_GUID, "Linux Home (/home)"),
+    (PART_TYPE_LINUX_SRV_GUID, "Linux Server Data (/srv)"),
+    (PART_TYPE_LINUX_VAR_GUID, "Linux Variable Data (/var)"),
+    (PART_TYPE_LINUX_VAR_TMP_GUID, "Linux Temporary Data (/var/tmp)"),
+    (PART_TYPE_LINUX_XBOOTLDR_GUID, "Linux Extended Boot Loader Partition"),
+    (PART_TYPE_LINUX_ROOT_X86_GUID, "Linux Root (x86)"),
+    (PART_TYPE_LINUX_ROOT_X86_64_GUID, "Linux Root (x86-64)"),
+    (PART_TYPE_LINUX_ROOT_ARM_GUID, "Linux Root (32-bit ARM)"),
+    (PART_TYPE_LINUX_ROOT_ARM64_GUID, "Linux Root (64-bit ARM)"),
+    (PART_TYPE_LINUX_ROOT_IA64_GUID, "Linux Root (Itanium)"),
+    (PART_TYPE_LINUX_ROOT_LOONGARCH64_GUID, "Linux Root (LoongArch 64-bit)"),
+    (PART_TYPE_LINUX_ROOT_RISCV32_GUID, "Linux Root (RISC-V 32-bit)"),
+    (PART_TYPE_LINUX_ROOT_RISCV64_GUID, "Linux Root (RISC-V 64-bit)"),
+    (PART_TYPE_LINUX_USR_X86_GUID, "Linux /usr (x86)"),
+    (PART_TYPE_LINUX_USR_X86_64_GUID, "Linux /usr (x86-64)"),
+    (PART_TYPE_LINUX_USR_ARM_GUID, "Linux /usr (32-bit ARM)"),
+    (PART_TYPE_LINUX_USR_ARM64_GUID, "Linux /usr (64-bit ARM)"),
+    (PART_TYPE_LINUX_USR_IA64_GUID, "Linux /usr (Itanium)"),
+    (PART_TYPE_LINUX_USR_LOONGARCH64_GUID, "Linux /usr (LoongArch 64-bit)"),
+    (PART_TYPE_LINUX_USR_RISCV32_GUID, "Linux /usr (RISC-V 32-bit)"),
+    (PART_TYPE_LINUX_USR_RISCV64_GUID, "Linux /usr (RISC-V 64-bit)"),
+    (PART_TYPE_LINUX_ROOT_VERITY_X86_GUID, "Linux Root Verity (x86)"),
+    (PART_TYPE_LINUX_ROOT_VERITY_X86_64_GUID, "Linux Root Verity (x86-64)"),
+    (PART_TYPE_LINUX_ROOT_VERITY_ARM_GUID, "Linux Root Verity (32-bit ARM)"),
+    (PART_TYPE_LINUX_ROOT_VERITY_ARM64_GUID, "Linux Root Verity (64-bit ARM)"),
+    (PART_TYPE_LINUX_ROOT_VERITY_IA64_GUID, "Linux Root Verity (Itanium)"),
+    (PART_TYPE_LINUX_ROOT_VERITY_LOONGARCH64_GUID, "Linux Root Verity (LoongArch 64-bit)"),
+    (PART_TYPE_LINUX_ROOT_VERITY_RISCV32_GUID, "Linux Root Verity (RISC-V 32-bit)"),
+    (PART_TYPE_LINUX_ROOT_VERITY_RISCV64_GUID, "Linux Root Verity (RISC-V 64-bit)"),
+    (PART_TYPE_LINUX_USR_VERITY_X86_GUID, "Linux /usr Verity (x86)"),
+    (PART_TYPE_LINUX_USR_VERITY_X86_64_GUID, "Linux /usr Verity (x86-64)"),
+    (PART_TYPE_LINUX_USR_VERITY_ARM_GUID, "Linux /usr Verity (32-bit ARM)"),
+    (PART_TYPE_LINUX_USR_VERITY_ARM64_GUID, "Linux /usr Verity (64-bit ARM)"),
+    (PART_TYPE_LINUX_USR_VERITY_IA64_GUID, "Linux /usr Verity (Itanium)"),
+    (PART_TYPE_LINUX_USR_VERITY_LOONGARCH64_GUID, "Linux /usr Verity (LoongArch 64-bit)"),
+    (PART_TYPE_LINUX_USR_VERITY_RISCV32_GUID, "Linux /usr Verity (RISC-V 32-bit)"),
+    (PART_TYPE_LINUX_USR_VERITY_RISCV64_GUID, "Linux /usr Verity (RISC-V 64-bit)"),
+    (PART_TYPE_APPLE_HFS_GUID, "Apple HFS+"),
+    (PART_TYPE_APPLE_APFS_GUID, "Apple APFS"),
+    (PART_TYPE_CHROMEOS_KERNEL_GUID, "ChromeOS Kernel"),
+    (PART_TYPE_CHROMEOS_ROOTFS_GUID, "ChromeOS Root Filesystem"),
+];
+
+const PART_TYPE_DISCOVERABLE: &[(&str, DiscoverablePartitionTypes)] = &[
+    ("x86", DiscoverablePartitionTypes {
+        root: PART_TYPE_LINUX_ROOT_X86_GUID,
+        root_verity: PART_TYPE_LINUX_ROOT_VERITY_X86_GUID,
+        usr: PART_TYPE_LINUX_USR_X86_GUID,
+        usr_verity: PART_TYPE_LINUX_USR_VERITY_X86_GUID,
+    }),
+    ("x86_64", DiscoverablePartitionTypes {
+        root: PART_TYPE_LINUX_ROOT_X86_64_GUID,
+        root_verity: PART_TYPE_LINUX_ROOT_VERITY_X86_64_GUID,
+        usr: PART_TYPE_LINUX_USR_X86_64_GUID,
+        usr_verity: PART_TYPE_LINUX_USR_VERITY_X86_64_GUID,
+    }),
+    ("arm", DiscoverablePartitionTypes {
+        root: PART_TYPE_LINUX_ROOT_ARM_GUID,
+        root_verity: PART_TYPE_LINUX_ROOT_VERITY_ARM_GUID,
+        usr: PART_TYPE_LINUX_USR_ARM_GUID,
+        usr_verity: PART_TYPE_LINUX_USR_VERITY_ARM_GUID,
+    }),
+    ("aarch64", DiscoverablePartitionTypes {
+        root: PART_TYPE_LINUX_ROOT_ARM64_GUID,
+        root_verity: PART_TYPE_LINUX_ROOT_VERITY_ARM64_GUID,
+        usr: PART_TYPE_LINUX_USR_ARM64_GUID,
+        usr_verity: PART_TYPE_LINUX_USR_VERITY_ARM64_GUID,
+    }),
+    ("ia64", DiscoverablePartitionTypes {
+        root: PART_TYPE_LINUX_ROOT_IA64_GUID,
+        root_verity: PART_TYPE_LINUX_ROOT_VERITY_IA64_GUID,
+        usr: PART_TYPE_LINUX_USR_IA64_GUID,
+        usr_verity: PART_TYPE_LINUX_USR_VERITY_IA64_GUID,
+    }),
+    ("loongarch64", DiscoverablePartitionTypes {
+        root: PART_TYPE_LINUX_ROOT_LOONGARCH64_GUID,
+        root_verity: PART_TYPE_LINUX_ROOT_VERITY_LOONGARCH64_GUID,
+        usr: PART_TYPE_LINUX_USR_LOONGARCH64_GUID,
+        usr_verity: PART_TYPE_LINUX_USR_VERITY_LOONGARCH64_GUID,
+    }),
+    ("riscv32", DiscoverablePartitionTypes {
+        root: PART_TYPE_LINUX_ROOT_RISCV32_GUID,
+        root_verity: PART_TYPE_LINUX_ROOT_VERITY_RISCV32_GUID,
+        usr: PART_TYPE_LINUX_USR_RISCV32_GUID,
+        usr_verity: PART_TYPE_LINUX_USR_VERITY_RISCV32_GUID,
+    }),
+    ("riscv64", DiscoverablePartitionTypes {
+        root: PART_TYPE_LINUX_ROOT_RISCV64_GUID,
+        root_verity: PART_TYPE_LINUX_ROOT_VERITY_RISCV64_GUID,
+        usr: PART_TYPE_LINUX_USR_RISCV64_GUID,
+        usr_verity: PART_TYPE_LINUX_USR_VERITY_RISCV64_GUID,
+    }),
+];
+
+/// Look up the Name of a Partition Type
+///
+/// Return a human-readable name of the partition type `guid`, or `None` if
+/// it is not one of the partition types defined in this module.
+pub fn partition_type_name(guid: &crate::base::Guid) -> Option<&'static str> {
+    PART_TYPE_NAMES.iter().find(|v| v.0 == *guid).map(|v| v.1)
+}
+
+/// Look up the Root Partition Type of an Architecture
+///
+/// Return the discoverable root partition type for the architecture `arch`.
+/// See `DiscoverablePartitionTypes::from_arch()` for details.
+pub fn partition_type_root(arch: &str) -> Option<crate::base::Guid> {
+    DiscoverablePartitionTypes::from_arch(arch).map(|v| v.root)
+}
+
+impl DiscoverablePartitionTypes {
+    /// Look up the Partition Types of an Architecture
+    ///
+    /// Return the discoverable partition types for the architecture `arch`.
+    /// The architecture is named like the Rust `target_arch` configuration
+    /// (e.g., `x86_64`, `aarch64`, `riscv64`), so the partition types of the
+    /// running system can be found via `std::env::consts::ARCH` or similar.
+    /// Itanium is supported as `ia64`. `None` is returned for unknown
+    /// architectures.
+    pub fn from_arch(arch: &str) -> Option<Self> {
+        PART_TYPE_DISCOVERABLE.iter().find(|v| v.0 == arch).map(|v| v.1)
+    }
+}
+
+impl PartitionAttributes {
+    /// Create Partition Attributes from Integer
+    pub const fn from_u64(v: u64) -> Self {
+        Self(v)
+    }
+
+    /// Return Underlying Integer Representation
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Check whether all Bits of a Mask are Set
+    pub const fn contains(&self, mask: u64) -> bool {
+        self.0 & mask == mask
+    }
+
+    /// Set or Clear all Bits of a Mask
+    pub fn set(&mut self, mask: u64, value: bool) {
+        if value {
+            self.0 |= mask;
+        } else {
+            self.0 &= !mask;
+        }
+    }
+
+    pub const fn required(&self) -> bool {
+        self.contains(PARTITION_ATTRIBUTE_REQUIRED)
+    }
+
+    pub fn set_required(&mut self, value: bool) {
+        self.set(PARTITION_ATTRIBUTE_REQUIRED, value)
+    }
+
+    pub const fn no_block_io_protocol(&self) -> bool {
+        self.contains(PARTITION_ATTRIBUTE_NO_BLOCK_IO_PROTOCOL)
+    }
+
+    pub fn set_no_block_io_protocol(&mut self, value: bool) {
+        self.set(PARTITION_ATTRIBUTE_NO_BLOCK_IO_PROTOCOL, value)
+    }
+
+    pub const fn legacy_bios_bootable(&self) -> bool {
+        self.contains(PARTITION_ATTRIBUTE_LEGACY_BIOS_BOOTABLE)
+    }
+
+    pub fn set_legacy_bios_bootable(&mut self, value: bool) {
+        self.set(PARTITION_ATTRIBUTE_LEGACY_BIOS_BOOTABLE, value)
+    }
+
+    /// Return the Type-specific Bits
+    ///
+    /// Return bits 48 to 63 as a 16-bit integer, with bit 48 as its least
+    /// significant bit.
+    pub const fn type_specific(&self) -> u16 {
+        (self.0 >> PARTITION_ATTRIBUTE_TYPE_SPECIFIC_SHIFT) as u16
+    }
+
+    /// Replace the Type-specific Bits
+    ///
+    /// Replace bits 48 to 63 with `value`. See `type_specific()` for details.
+    pub fn set_type_specific(&mut self, value: u16) {
+        self.0 = (self.0 & !PARTITION_ATTRIBUTE_TYPE_SPECIFIC_MASK)
+            | ((value as u64) << PARTITION_ATTRIBUTE_TYPE_SPECIFIC_SHIFT);
+    }
+
+    /// Decode Microsoft Basic Data Attributes
+    pub const fn basic_data(&self) -> BasicDataAttributes {
+        BasicDataAttributes {
+            read_only: self.contains(PARTITION_ATTRIBUTE_MICROSOFT_READ_ONLY),
+            shadow_copy: self.contains(PARTITION_ATTRIBUTE_MICROSOFT_SHADOW_COPY),
+            hidden: self.contains(PARTITION_ATTRIBUTE_MICROSOFT_HIDDEN),
+            no_drive_letter: self.contains(PARTITION_ATTRIBUTE_MICROSOFT_NO_DRIVE_LETTER),
+        }
+    }
+
+    /// Encode Microsoft Basic Data Attributes
+    ///
+    /// Set the type-specific bits according to `v`. Type-specific bits not
+    /// covered by `BasicDataAttributes` are left untouched.
+    pub fn set_basic_data(&mut self, v: BasicDataAttributes) {
+        self.set(PARTITION_ATTRIBUTE_MICROSOFT_READ_ONLY, v.read_only);
+        self.set(PARTITION_ATTRIBUTE_MICROSOFT_SHADOW_COPY, v.shadow_copy);
+        self.set(PARTITION_ATTRIBUTE_MICROSOFT_HIDDEN, v.hidden);
+        self.set(PARTITION_ATTRIBUTE_MICROSOFT_NO_DRIVE_LETTER, v.no_drive_letter);
+    }
+
+    /// Decode Linux Attributes
+    pub const fn linux(&self) -> LinuxAttributes {
+        LinuxAttributes {
+            no_auto: self.contains(PARTITION_ATTRIBUTE_LINUX_NO_AUTO),
+            read_only: self.contains(PARTITION_ATTRIBUTE_LINUX_READ_ONLY),
+            grow_fs: self.contains(PARTITION_ATTRIBUTE_LINUX_GROW_FS),
+        }
+    }
+
+    /// Encode Linux Attributes
+    ///
+    /// Set the type-specific bits according to `v`. Type-specific bits not
+    /// covered by `LinuxAttributes` are left untouched.
+    pub fn set_linux(&mut self, v: LinuxAttributes) {
+        self.set(PARTITION_ATTRIBUTE_LINUX_NO_AUTO, v.no_auto);
+        self.set(PARTITION_ATTRIBUTE_LINUX_READ_ONLY, v.read_only);
+        self.set(PARTITION_ATTRIBUTE_LINUX_GROW_FS, v.grow_fs);
+    }
+}
+
+impl From<u64> for PartitionAttributes {
+    fn from(v: u64) -> Self {
+        Self(v)
+    }
+}
+
+impl From<PartitionAttributes> for u64 {
+    fn from(v: PartitionAttributes) -> Self {
+        v.0
+    }
+}
+
+impl MbrPartitionRecord {
+    /// Check whether a Record is Unused
+    ///
+    /// Unused partition records have an OS type of `MBR_TYPE_UNUSED` or a
+    /// size of 0.
+    pub fn is_unused(&self) -> bool {
+        self.os_type == MBR_TYPE_UNUSED || self.size_in_lba == 0
+    }
+
+    /// Check whether a Record is Bootable
+    ///
+    /// Return true if the boot indicator marks this partition as active
+    /// (i.e., bootable by legacy firmware).
+    pub fn is_bootable(&self) -> bool {
+        self.boot_indicator == MBR_BOOT_INDICATOR_ACTIVE
+    }
+
+    /// Check whether a Record is an Extended Partition
+    ///
+    /// Extended partitions contain a chain of further, logical partitions,
+    /// rather than a file system.
+    pub fn is_extended(&self) -> bool {
+        matches!(
+            self.os_type,
+            MBR_TYPE_EXTENDED_DOS | MBR_TYPE_EXTENDED_WINDOWS | MBR_TYPE_EXTENDED_LINUX,
+        )
+    }
+
+    /// Decode the Starting CHS Address
+    pub fn starting_chs(&self) -> Chs {
+        Chs::from_bytes(&self.starting_chs)
+    }
+
+    /// Decode the Ending CHS Address
+    pub fn ending_chs(&self) -> Chs {
+        Chs::from_bytes(&self.ending_chs)
+    }
+
+    /// Return the LBA Range of a Partition
+    ///
+    /// Return the first and last LBA of the partition described by this
+    /// record, or `None` if the record is unused.
+    pub fn lba_range(&self) -> Option<(crate::base::Lba, crate::base::Lba)> {
+        if self.is_unused() {
+            None
+        } else {
+            let start = self.starting_lba as crate::base::Lba;
+            Some((start, start + self.size_in_lba as crate::base::Lba - 1))
+        }
+    }
+}
+
+impl MasterBootRecord {
+    /// Create a Protective MBR
+    ///
+    /// Create a protective MBR for a GPT disk with the given last LBA. The
+    /// first partition record covers the entire disk, or as much of it as
+    /// can be represented. The ending CHS address is set to `0xffffff`, since
+    /// the disk geometry is not known.
+    pub fn protective(last_lba: crate::base::Lba) -> Self {
+        let mut mbr = Self::from_bytes(&[0; 512]);
+
+        mbr.partition[0] = MbrPartitionRecord {
+            boot_indicator: 0,
+            starting_chs: [0x00, 0x02, 0x00],
+            os_type: MBR_TYPE_PROTECTIVE,
+            ending_chs: [0xff, 0xff, 0xff],
+            starting_lba: 1,
+            size_in_lba: last_lba.min(u32::MAX as u64) as u32,
+        };
+        mbr.signature = MBR_SIGNATURE;
+
+        mbr
+    }
+
+    /// Initialize an MBR from its byte representation
+    ///
+    /// Create a new MBR object from its byte representation. This
+    /// reinterprets the bytes as an MBR. No validation is performed.
+    pub const fn from_bytes(bytes: &[u8; 512]) -> Self {
+        // SAFETY: The structure is `packed(1)`, has no padding, and is
+        //         exactly 512 bytes in size. It only consists of integers,
+        //         so any bit pattern is valid.
+        unsafe { core::mem::transmute::<[u8; 512], MasterBootRecord>(*bytes) }
+    }
+
+    /// Access an MBR as raw byte array
+    ///
+    /// This provides access to the MBR through a byte array. This is a simple
+    /// cast.
+    pub const fn as_bytes(&self) -> &[u8; 512] {
+        // SAFETY: The structure is `packed(1)`, has no padding, and is
+        //         exactly 512 bytes in size, so it has the same size and
+        //         alignment as the array, and every byte is initialized.
+        unsafe { core::mem::transmute::<&MasterBootRecord, &[u8; 512]>(self) }
+    }
+
+    /// Check whether the MBR Signature is Valid
+    pub fn is_valid(&self) -> bool {
+        self.signature == MBR_SIGNATURE
+    }
+
+    /// Check whether this is a Protective MBR
+    ///
+    /// A protective MBR has a valid signature and a partition record of type
+    /// `MBR_TYPE_PROTECTIVE` starting at LBA 1. Note that this also matches
+    /// hybrid MBRs, which carry additional legacy partition records. Use
+    /// `is_hybrid()` to distinguish them.
+    pub fn is_protective(&self) -> bool {
+        self.is_valid()
+            && self.partition.iter().any(|v| {
+                v.os_type == MBR_TYPE_PROTECTIVE && v.starting_lba == 1
+            })
+    }
+
+    /// Check whether this is a Hybrid MBR
+    ///
+    /// A hybrid MBR is a protective MBR with additional legacy partition
+    /// records. These are not sanctioned by the specification, but commonly
+    /// found in the wild.
+    pub fn is_hybrid(&self) -> bool {
+        self.is_protective()
+            && self.partition.iter().any(|v| {
+                !v.is_unused() && v.os_type != MBR_TYPE_PROTECTIVE
+            })
+    }
+}
+
+impl Chs {
+    /// Decode a CHS Address
+    ///
+    /// Decode a packed CHS address as stored in MBR partition records.
+    pub const fn from_bytes(bytes: &[u8; 3]) -> Self {
+        Self {
+            cylinder: (bytes[2] as u16) | (((bytes[1] as u16) & 0xc0) << 2),
+            head: bytes[0],
+            sector: bytes[1] & 0x3f,
+        }
+    }
+
+    /// Encode a CHS Address
+    ///
+    /// Encode a CHS address into its packed form as stored in MBR partition
+    /// records. Bits of the cylinder and sector that cannot be represented
+    /// are discarded.
+    pub const fn to_bytes(&self) -> [u8; 3] {
+        [
+            self.head,
+            (self.sector & 0x3f) | (((self.cylinder >> 8) as u8 & 0x03) << 6),
+            self.cylinder as u8,
+        ]
+    }
+
+    /// Convert an LBA into a CHS Address
+    ///
+    /// Convert the LBA to a CHS address using the given disk geometry, that
+    /// is the number of heads per cylinder and sectors per track. `None` is
+    /// returned if the address cannot be represented, or if the geometry is
+    /// invalid.
+    pub fn from_lba(lba: crate::base::Lba, heads: u8, sectors: u8) -> Option<Self> {
+        if heads == 0 || sectors == 0 || sectors > 63 {
+            return None;
+        }
+
+        let track = lba / sectors as u64;
+        let cylinder = track / heads as u64;
+        if cylinder > 1023 {
+            return None;
+        }
+
+        Some(Self {
+            cylinder: cylinder as u16,
+            head: (track % heads as u64) as u8,
+            sector: (lba % sectors as u64) as u8 + 1,
+        })
+    }
+
+    /// Convert a CHS Address into an LBA
+    ///
+    /// Convert the CHS address to an LBA using the given disk geometry.
+    /// `None` is returned if the address is not valid for the geometry.
+    pub fn to_lba(&self, heads: u8, sectors: u8) -> Option<crate::base::Lba> {
+        if self.sector == 0 || self.sector > sectors || self.head >= heads {
+            return None;
+        }
+
+        Some(
+            (self.cylinder as u64 * heads as u64 + self.head as u64) * sectors as u64
+                + self.sector as u64
+                - 1,
+        )
+    }
+}
+
+impl<'a> Iterator for PartitionEntries<'a> {
+    type Item = PartitionEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.entries.len() < self.size {
+            return None;
+        }
+
+        let (entry, rest) = self.entries.split_at(self.size);
+        self.entries = rest;
+
+        // SAFETY: The structure is 1-byte aligned, has no padding, and is
+        //         valid for any bit pattern. The entry size is at least the
+        //         size of the structure.
+        Some(unsafe { core::ptr::read_unaligned(entry.as_ptr() as *const PartitionEntry) })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use core::mem;
@@ -28,7 +1597,321 @@ mod test {
     // translated into Rust.
     #[test]
     fn layout() {
+        assert_eq!(mem::align_of::<PartitionTableHeader>(), 1);
+        assert_eq!(mem::size_of::<PartitionTableHeader>(), PARTITION_TABLE_HEADER_SIZE as usize);
         assert_eq!(mem::align_of::<PartitionEntry>(), 1);
         assert_eq!(mem::size_of::<PartitionEntry>(), 128);
+        assert_eq!(mem::align_of::<MbrPartitionRecord>(), 1);
+        assert_eq!(mem::size_of::<MbrPartitionRecord>(), 16);
+        assert_eq!(mem::align_of::<MasterBootRecord>(), 1);
+        assert_eq!(mem::size_of::<MasterBootRecord>(), 512);
+    }
+
+    // Write a header and entry array by hand and verify the parser accepts
+    // it, and rejects any corruption.
+    #[test]
+    fn parse() {
+        let mut entries = [0u8; 4 * 128];
+        entries[..16].copy_from_slice(&[0x11; 16]);
+        entries[32..40].copy_from_slice(&34u64.to_le_bytes());
+        entries[40..48].copy_from_slice(&2047u64.to_le_bytes());
+
+        let mut block = [0u8; 512];
+        block[0..8].copy_from_slice(&PARTITION_TABLE_HEADER_SIGNATURE.to_le_bytes());
+        block[8..12].copy_from_slice(&PARTITION_TABLE_HEADER_REVISION.to_le_bytes());
+        block[12..16].copy_from_slice(&PARTITION_TABLE_HEADER_SIZE.to_le_bytes());
+        block[24..32].copy_from_slice(&1u64.to_le_bytes());
+        block[32..40].copy_from_slice(&4095u64.to_le_bytes());
+        block[72..80].copy_from_slice(&2u64.to_le_bytes());
+        block[80..84].copy_from_slice(&4u32.to_le_bytes());
+        block[84..88].copy_from_slice(&128u32.to_le_bytes());
+        block[88..92].copy_from_slice(&crate::base::crc32(0, &entries).to_le_bytes());
+        let crc = crate::base::crc32(0, &block[..92]);
+        block[16..20].copy_from_slice(&crc.to_le_bytes());
+
+        let header = PartitionTableHeader::parse(&block, &entries).unwrap();
+        assert_eq!({ header.my_lba }, 1);
+        assert_eq!({ header.alternate_lba }, 4095);
+        assert_eq!({ header.number_of_partition_entries }, 4);
+
+        let mut iter = header.entries(&entries);
+        let entry = iter.next().unwrap();
+        assert!(!entry.is_unused());
+        assert_eq!({ entry.starting_lba }, 34);
+        assert_eq!({ entry.ending_lba }, 2047);
+        assert!(iter.all(|v| v.is_unused()));
+
+        assert_eq!(
+            PartitionTableHeader::parse(&block[..91], &entries).unwrap_err(),
+            ParseError::HeaderTruncated,
+        );
+        assert_eq!(
+            PartitionTableHeader::parse(&block, &entries[..511]).unwrap_err(),
+            ParseError::EntriesTruncated,
+        );
+
+        let mut v = block;
+        v[0] = b'X';
+        assert_eq!(PartitionTableHeader::parse(&v, &entries).unwrap_err(), ParseError::Signature);
+
+        let mut v = block;
+        v[10] = 2;
+        assert_eq!(PartitionTableHeader::parse(&v, &entries).unwrap_err(), ParseError::Revision);
+
+        let mut v = block;
+        v[12] = 91;
+        assert_eq!(PartitionTableHeader::parse(&v, &entries).unwrap_err(), ParseError::HeaderSize);
+
+        let mut v = block;
+        v[24] = 2;
+        assert_eq!(PartitionTableHeader::parse(&v, &entries).unwrap_err(), ParseError::HeaderCrc32);
+
+        let mut v = entries;
+        v[200] = 1;
+        assert_eq!(PartitionTableHeader::parse(&block, &v).unwrap_err(), ParseError::EntriesCrc32);
+    }
+
+    // Write a partition table into an in-memory image and verify both the
+    // primary and backup tables can be parsed back.
+    #[test]
+    fn write() {
+        let guid = crate::base::Guid::from_bytes(&[0x22; 16]);
+        let mut image = vec![0xffu8; 4096 * 512];
+        let mut partitions = [PartitionEntry {
+            partition_type_guid: crate::base::Guid::from_bytes(&[0x11; 16]),
+            unique_partition_guid: crate::base::Guid::from_bytes(&[0x33; 16]),
+            starting_lba: 2048,
+            ending_lba: 4061,
+            attributes: 0,
+            partition_name: [0; 36],
+        }; 2];
+        partitions[1].partition_type_guid = crate::base::Guid::from_bytes(&[0; 16]);
+
+        let primary = write_image(&mut image, 512, guid, &partitions).unwrap();
+        assert_eq!({ primary.first_usable_lba }, 34);
+        assert_eq!({ primary.last_usable_lba }, 4062);
+        assert_eq!({ primary.number_of_partition_entries }, 128);
+
+        // Protective MBR
+        assert_eq!(&image[446..462], &[
+            0x00, 0x00, 0x02, 0x00, 0xee, 0xff, 0xff, 0xff,
+            0x01, 0x00, 0x00, 0x00, 0xff, 0x0f, 0x00, 0x00,
+        ]);
+        assert_eq!(&image[462..510], &[0; 48]);
+        assert_eq!(&image[510..512], &[0x55, 0xaa]);
+
+        // Primary and backup tables
+        let p = PartitionTableHeader::parse(&image[512..1024], &image[1024..]).unwrap();
+        assert_eq!({ p.my_lba }, 1);
+        assert_eq!({ p.alternate_lba }, 4095);
+        assert_eq!({ p.disk_guid }, guid);
+        let b = PartitionTableHeader::parse(&image[4095 * 512..], &image[4063 * 512..]).unwrap();
+        assert_eq!({ b.my_lba }, 4095);
+        assert_eq!({ b.alternate_lba }, 1);
+        assert_eq!({ b.partition_entry_lba }, 4063);
+        assert_eq!({ p.partition_entry_array_crc32 }, { b.partition_entry_array_crc32 });
+
+        let entry = p.entries(&image[1024..]).next().unwrap();
+        assert_eq!({ entry.starting_lba }, 2048);
+        assert_eq!({ entry.ending_lba }, 4061);
+        assert_eq!(p.entries(&image[1024..]).filter(|v| !v.is_unused()).count(), 1);
+
+        // Partition data is untouched.
+        assert!(image[34 * 512..4063 * 512].iter().all(|v| *v == 0xff));
+
+        // Invalid header sizes
+        let mut block = [0xffu8; 512];
+        assert_eq!(p.write_header(&mut block[..91]).unwrap_err(), WriteError::HeaderSize);
+        let mut v = p;
+        v.header.header_size = 91;
+        assert_eq!(v.write_header(&mut block).unwrap_err(), WriteError::HeaderSize);
+        assert_eq!(block, [0xff; 512]);
+
+        // Invalid layouts
+        assert_eq!(
+            write_image(&mut image, 256, guid, &[]).unwrap_err(),
+            WriteError::BlockSize
+        );
+        assert_eq!(
+            write_image(&mut image[..1000], 512, guid, &[]).unwrap_err(),
+            WriteError::ImageSize
+        );
+        assert_eq!(
+            write_image(&mut image[..66 * 512], 512, guid, &[]).unwrap_err(),
+            WriteError::ImageSize
+        );
+
+        partitions[1] = partitions[0];
+        partitions[1].starting_lba = 4061;
+        partitions[1].ending_lba = 4062;
+        assert_eq!(
+            write_image(&mut image, 512, guid, &partitions).unwrap_err(),
+            WriteError::PartitionOverlap(0, 1),
+        );
+        partitions[1].starting_lba = 4062;
+        partitions[1].ending_lba = 4063;
+        assert_eq!(
+            write_image(&mut image, 512, guid, &partitions).unwrap_err(),
+            WriteError::PartitionRange(1),
+        );
+    }
+
+    // Verify MBR decoding of protective, hybrid, and legacy MBRs, as well as
+    // the CHS address conversions.
+    #[test]
+    fn mbr() {
+        let pmbr = MasterBootRecord::protective(0x1_0000_0000);
+        assert!(pmbr.is_valid());
+        assert!(pmbr.is_protective());
+        assert!(!pmbr.is_hybrid());
+        assert_eq!(pmbr.partition[0].lba_range(), Some((1, 0xffff_ffff)));
+        assert_eq!(pmbr.partition[0].starting_chs(), Chs { cylinder: 0, head: 0, sector: 2 });
+        assert!(pmbr.partition[1..].iter().all(|v| v.is_unused()));
+        assert_eq!(&pmbr.as_bytes()[510..], &[0x55, 0xaa]);
+
+        let mut bytes = [0u8; 512];
+        bytes[446..462].copy_from_slice(&[
+            0x80, 0x20, 0x21, 0x00, 0x0c, 0xfe, 0xff, 0xff,
+            0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x20, 0x00,
+        ]);
+        bytes[462..478].copy_from_slice(&[
+            0x00, 0xfe, 0xff, 0xff, 0x05, 0xfe, 0xff, 0xff,
+            0x00, 0x08, 0x20, 0x00, 0x00, 0x00, 0x10, 0x00,
+        ]);
+        bytes[510..].copy_from_slice(&[0x55, 0xaa]);
+        let mbr = MasterBootRecord::from_bytes(&bytes);
+        assert!(mbr.is_valid());
+        assert!(!mbr.is_protective());
+        assert!(mbr.partition[0].is_bootable());
+        assert!(!mbr.partition[0].is_extended());
+        assert!(mbr.partition[1].is_extended());
+        assert_eq!(mbr.partition[0].lba_range(), Some((2048, 2048 + 0x200000 - 1)));
+        assert_eq!(mbr.partition[0].starting_chs(), Chs { cylinder: 0, head: 32, sector: 33 });
+        assert_eq!(mbr.partition[0].ending_chs(), Chs { cylinder: 1023, head: 254, sector: 63 });
+        assert_eq!(mbr.partition[2].lba_range(), None);
+
+        let mut hybrid = pmbr;
+        hybrid.partition[1] = mbr.partition[0];
+        assert!(hybrid.is_hybrid());
+
+        let chs = Chs::from_lba(2048, 255, 63).unwrap();
+        assert_eq!(chs, mbr.partition[0].starting_chs());
+        assert_eq!(chs.to_lba(255, 63), Some(2048));
+        assert_eq!(Chs::from_bytes(&chs.to_bytes()), chs);
+        assert_eq!(Chs::from_lba(1024 * 255 * 63, 255, 63), None);
+        assert_eq!(Chs::from_lba(1024 * 255 * 63 - 1, 255, 63).unwrap().cylinder, 1023);
+        assert_eq!(Chs { cylinder: 0, head: 0, sector: 0 }.to_lba(255, 63), None);
+    }
+
+    // Verify the partition type catalog is consistent and the lookup helpers
+    // find the expected types.
+    #[test]
+    fn part_types() {
+        for (i, (guid, name)) in PART_TYPE_NAMES.iter().enumerate() {
+            assert!(PART_TYPE_NAMES[..i].iter().all(|v| v.0 != *guid && v.1 != *name));
+            assert_eq!(partition_type_name(guid), Some(*name));
+        }
+        for (_, types) in PART_TYPE_DISCOVERABLE {
+            assert!(partition_type_name(&types.root)
+                .unwrap()
+                .starts_with("Linux Root ("));
+            assert!(partition_type_name(&types.root_verity)
+                .unwrap()
+                .starts_with("Linux Root Verity ("));
+            assert!(partition_type_name(&types.usr)
+                .unwrap()
+                .starts_with("Linux /usr ("));
+            assert!(partition_type_name(&types.usr_verity)
+                .unwrap()
+                .starts_with("Linux /usr Verity ("));
+        }
+
+        assert_eq!(
+            PART_TYPE_EFI_SYSTEM_PART_GUID.as_bytes(),
+            &[
+                0x28, 0x73, 0x2a, 0xc1, 0x1f, 0xf8, 0xd2, 0x11,
+                0xba, 0x4b, 0x00, 0xa0, 0xc9, 0x3e, 0xc9, 0x3b,
+            ],
+        );
+        assert_eq!(
+            partition_type_name(&PART_TYPE_EFI_SYSTEM_PART_GUID),
+            Some("EFI System Partition")
+        );
+        assert_eq!(
+            partition_type_name(&crate::base::Guid::from_bytes(&[0xff; 16])),
+            None
+        );
+
+        assert_eq!(partition_type_root("x86_64"), Some(PART_TYPE_LINUX_ROOT_X86_64_GUID));
+        assert_eq!(partition_type_root("aarch64"), Some(PART_TYPE_LINUX_ROOT_ARM64_GUID));
+        assert_eq!(partition_type_root("sparc"), None);
+        assert_eq!(
+            DiscoverablePartitionTypes::from_arch("riscv64").unwrap().usr_verity,
+            PART_TYPE_LINUX_USR_VERITY_RISCV64_GUID,
+        );
+        for arch in ["x86", "arm", "ia64", "loongarch64", "riscv32"] {
+            assert!(partition_type_root(arch).is_some());
+        }
+        assert_eq!(partition_type_root("s390x"), None);
+    }
+
+    // Verify partition attributes can be set and queried, and type-specific
+    // bits are only decoded for matching partition types.
+    #[test]
+    fn attributes() {
+        let mut a = PartitionAttributes::default();
+        a.set_required(true);
+        a.set_legacy_bios_bootable(true);
+        assert!(a.required());
+        assert!(!a.no_block_io_protocol());
+        assert!(a.legacy_bios_bootable());
+        assert_eq!(a.as_u64(), 0x5);
+
+        a.set_type_specific(0x8001);
+        assert_eq!(a.as_u64(), 0x8001_0000_0000_0005);
+        assert_eq!(a.type_specific(), 0x8001);
+        assert_eq!(
+            a.linux(),
+            LinuxAttributes {
+                no_auto: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            a.basic_data(),
+            BasicDataAttributes {
+                no_drive_letter: true,
+                ..Default::default()
+            }
+        );
+
+        a.set_linux(LinuxAttributes { no_auto: false, read_only: true, grow_fs: true });
+        assert_eq!(a.type_specific(), 0x1801);
+        a.set_basic_data(BasicDataAttributes { hidden: true, ..Default::default() });
+        assert_eq!(a.type_specific(), 0x4801);
+        a.set_required(false);
+        assert_eq!(u64::from(a), 0x4801_0000_0000_0004);
+
+        let mut e = PartitionEntry {
+            partition_type_guid: PART_TYPE_MICROSOFT_BASIC_DATA_GUID,
+            unique_partition_guid: PART_TYPE_UNUSED_GUID,
+            starting_lba: 0,
+            ending_lba: 0,
+            attributes: 0,
+            partition_name: [0; 36],
+        };
+        e.set_partition_attributes(a);
+        assert_eq!({ e.attributes }, a.as_u64());
+        assert!(!e.is_linux());
+        assert!(e.basic_data_attributes().unwrap().hidden);
+        assert_eq!(e.linux_attributes(), None);
+
+        e.partition_type_guid = PART_TYPE_LINUX_ROOT_X86_64_GUID;
+        assert!(e.is_linux());
+        assert_eq!(e.basic_data_attributes(), None);
+        assert!(e.linux_attributes().unwrap().grow_fs);
+
+        e.partition_type_guid = PART_TYPE_LINUX_SWAP_GUID;
+        assert!(!e.is_linux());
     }
 }
@@ -195,6 +195,11 @@
 // units, so they will be unaffected by this.
 #![cfg_attr(not(test), no_std)]
 
+// The `alloc` feature enables support for heap-allocated types. It is never required for the
+// protocol definitions themselves.
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 // Import the different core modules. We separate them into different modules to make it easier to
 // work on them and describe what each part implements. This is different to the reference
 // implementation, which uses a flat namespace due to its origins in the C language. For
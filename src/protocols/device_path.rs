@@ -2,6 +2,12 @@
 //!
 //! The device path protocol defines how to obtain generic path/location information
 //! concerning the phisycal or logical device.
+//!
+//! Device paths are a sequence of variable-sized nodes, each starting with a `Protocol` header.
+//! Nodes are byte-aligned, so all node structures are packed. Node structures that embed GUIDs
+//! or IP addresses use a private generic parameter to work around the restriction on embedding
+//! aligned types in packed structures (see `crate::gpt::PartitionEntry` for details). Nodes with
+//! trailing variable-sized data use a const-generic `N` for the length of that data.
 
 pub const PROTOCOL_GUID: crate::base::Guid = crate::base::Guid::from_fields(
     0x09576e91,
@@ -38,6 +44,10 @@ impl End {
     pub const SUBTYPE_ENTIRE: u8 = 0xff;
 }
 
+//
+// Hardware Device Paths
+//
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
 pub struct Hardware {
@@ -55,28 +65,3990 @@ impl Hardware {
 
 #[repr(C, packed)]
 #[derive(Clone, Copy, Debug)]
-pub struct HardDriveMedia {
+pub struct PciHardware {
     pub header: Protocol,
-    pub partition_number: u32,
-    pub partition_start: u64,
-    pub partition_size: u64,
-    pub partition_signature: [u8; 16],
-    pub partition_format: u8,
-    pub signature_type: u8,
+    pub function: u8,
+    pub device: u8,
 }
 
-pub struct Media {
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct PccardHardware {
     pub header: Protocol,
+    pub function_number: u8,
 }
 
-impl Media {
-    pub const SUBTYPE_HARDDRIVE: u8 = 0x01;
-    pub const SUBTYPE_CDROM: u8 = 0x02;
-    pub const SUBTYPE_VENDOR: u8 = 0x03;
-    pub const SUBTYPE_FILE_PATH: u8 = 0x04;
-    pub const SUBTYPE_MEDIA_PROTOCOL: u8 = 0x05;
-    pub const SUBTYPE_PIWG_FIRMWARE_FILE: u8 = 0x06;
-    pub const SUBTYPE_PIWG_FIRMWARE_VOLUME: u8 = 0x07;
-    pub const SUBTYPE_RELATIVE_OFFSET_RANGE: u8 = 0x08;
-    pub const SUBTYPE_RAM_DISK: u8 = 0x09;
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct MmapHardware {
+    pub header: Protocol,
+    pub memory_type: crate::system::MemoryType,
+    pub starting_address: crate::base::PhysicalAddress,
+    pub ending_address: crate::base::PhysicalAddress,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct VendorHardware<__PrivateGuid = crate::base::Guid, const N: usize = 0> {
+    pub header: Protocol,
+    pub vendor_guid: __PrivateGuid,
+    pub vendor_defined_data: [u8; N],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct ControllerHardware {
+    pub header: Protocol,
+    pub controller_number: u32,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct BmcHardware {
+    pub header: Protocol,
+    pub interface_type: u8,
+    pub base_address: u64,
+}
+
+impl BmcHardware {
+    pub const INTERFACE_TYPE_UNKNOWN: u8 = 0x00;
+    pub const INTERFACE_TYPE_KCS: u8 = 0x01;
+    pub const INTERFACE_TYPE_SMIC: u8 = 0x02;
+    pub const INTERFACE_TYPE_BT: u8 = 0x03;
+}
+
+//
+// ACPI Device Paths
+//
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Acpi {
+    pub header: Protocol,
+}
+
+impl Acpi {
+    pub const SUBTYPE_ACPI: u8 = 0x01;
+    pub const SUBTYPE_EXPANDED: u8 = 0x02;
+    pub const SUBTYPE_ADR: u8 = 0x03;
+    pub const SUBTYPE_NVDIMM: u8 = 0x04;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct HidAcpi {
+    pub header: Protocol,
+    pub hid: u32,
+    pub uid: u32,
+}
+
+// The HID, UID, and CID strings follow the fixed fields as consecutive
+// NUL-terminated ASCII strings.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct ExpandedAcpi<const N: usize = 0> {
+    pub header: Protocol,
+    pub hid: u32,
+    pub uid: u32,
+    pub cid: u32,
+    pub hid_uid_cid_str: [u8; N],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct AdrAcpi<const N: usize = 0> {
+    pub header: Protocol,
+    pub adr: u32,
+    pub additional_adr: [u32; N],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct NvdimmAcpi {
+    pub header: Protocol,
+    pub nfit_device_handle: u32,
+}
+
+//
+// Messaging Device Paths
+//
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Messaging {
+    pub header: Protocol,
+}
+
+impl Messaging {
+    pub const SUBTYPE_ATAPI: u8 = 0x01;
+    pub const SUBTYPE_SCSI: u8 = 0x02;
+    pub const SUBTYPE_FIBRE_CHANNEL: u8 = 0x03;
+    pub const SUBTYPE_1394: u8 = 0x04;
+    pub const SUBTYPE_USB: u8 = 0x05;
+    pub const SUBTYPE_I2O: u8 = 0x06;
+    pub const SUBTYPE_INFINIBAND: u8 = 0x09;
+    pub const SUBTYPE_VENDOR: u8 = 0x0a;
+    pub const SUBTYPE_MAC_ADDRESS: u8 = 0x0b;
+    pub const SUBTYPE_IPV4: u8 = 0x0c;
+    pub const SUBTYPE_IPV6: u8 = 0x0d;
+    pub const SUBTYPE_UART: u8 = 0x0e;
+    pub const SUBTYPE_USB_CLASS: u8 = 0x0f;
+    pub const SUBTYPE_USB_WWID: u8 = 0x10;
+    pub const SUBTYPE_DEVICE_LOGICAL_UNIT: u8 = 0x11;
+    pub const SUBTYPE_SATA: u8 = 0x12;
+    pub const SUBTYPE_ISCSI: u8 = 0x13;
+    pub const SUBTYPE_VLAN: u8 = 0x14;
+    pub const SUBTYPE_FIBRE_CHANNEL_EX: u8 = 0x15;
+    pub const SUBTYPE_SAS_EX: u8 = 0x16;
+    pub const SUBTYPE_NVME_NAMESPACE: u8 = 0x17;
+    pub const SUBTYPE_URI: u8 = 0x18;
+    pub const SUBTYPE_UFS: u8 = 0x19;
+    pub const SUBTYPE_SD: u8 = 0x1a;
+    pub const SUBTYPE_BLUETOOTH: u8 = 0x1b;
+    pub const SUBTYPE_WIFI: u8 = 0x1c;
+    pub const SUBTYPE_EMMC: u8 = 0x1d;
+    pub const SUBTYPE_BLUETOOTH_LE: u8 = 0x1e;
+    pub const SUBTYPE_DNS: u8 = 0x1f;
+    pub const SUBTYPE_NVDIMM_NAMESPACE: u8 = 0x20;
+    pub const SUBTYPE_REST_SERVICE: u8 = 0x21;
+    pub const SUBTYPE_NVME_OF_NAMESPACE: u8 = 0x22;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct AtapiMessaging {
+    pub header: Protocol,
+    pub primary_secondary: u8,
+    pub slave_master: u8,
+    pub lun: u16,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct ScsiMessaging {
+    pub header: Protocol,
+    pub target_id: u16,
+    pub lun: u16,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct FibreChannelMessaging {
+    pub header: Protocol,
+    pub reserved: u32,
+    pub world_wide_name: u64,
+    pub lun: u64,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct FibreChannelExMessaging {
+    pub header: Protocol,
+    pub reserved: u32,
+    pub world_wide_name: [u8; 8],
+    pub lun: [u8; 8],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct Ieee1394Messaging {
+    pub header: Protocol,
+    pub reserved: u32,
+    pub guid: u64,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct UsbMessaging {
+    pub header: Protocol,
+    pub parent_port_number: u8,
+    pub interface_number: u8,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct I2oMessaging {
+    pub header: Protocol,
+    pub tid: u32,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct InfinibandMessaging {
+    pub header: Protocol,
+    pub resource_flags: u32,
+    pub port_gid: [u8; 16],
+    pub service_id: u64,
+    pub target_port_id: u64,
+    pub device_id: u64,
+}
+
+impl InfinibandMessaging {
+    pub const RESOURCE_FLAG_IOC_SERVICE: u32 = 0x00000001u32;
+    pub const RESOURCE_FLAG_EXTENDED_BOOT_ENVIRONMENT: u32 = 0x00000002u32;
+    pub const RESOURCE_FLAG_CONSOLE_PROTOCOL: u32 = 0x00000004u32;
+    pub const RESOURCE_FLAG_STORAGE_PROTOCOL: u32 = 0x00000008u32;
+    pub const RESOURCE_FLAG_NETWORK_PROTOCOL: u32 = 0x00000010u32;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct VendorMessaging<__PrivateGuid = crate::base::Guid, const N: usize = 0> {
+    pub header: Protocol,
+    pub vendor_guid: __PrivateGuid,
+    pub vendor_defined_data: [u8; N],
+}
+
+pub const UART_FLOW_CONTROL_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x37499a9d,
+    0x542f,
+    0x4c89,
+    0xa0,
+    0x26,
+    &[0x35, 0xda, 0x14, 0x20, 0x94, 0xe4],
+);
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct UartFlowControlMessaging<__PrivateGuid = crate::base::Guid> {
+    pub header: Protocol,
+    pub guid: __PrivateGuid,
+    pub flow_control_map: u32,
+}
+
+impl UartFlowControlMessaging {
+    pub const FLOW_CONTROL_HARDWARE: u32 = 0x00000001u32;
+    pub const FLOW_CONTROL_XON_XOFF: u32 = 0x00000002u32;
+}
+
+pub const SAS_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xd487ddb4,
+    0x008b,
+    0x11d9,
+    0xaf,
+    0xdc,
+    &[0x00, 0x10, 0x83, 0xff, 0xca, 0x4d],
+);
+
+pub const PC_ANSI_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xe0c14753,
+    0xf9be,
+    0x11d2,
+    0x9a,
+    0x0c,
+    &[0x00, 0x90, 0x27, 0x3f, 0xc1, 0x4d],
+);
+
+pub const VT_100_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xdfa66065,
+    0xb419,
+    0x11d3,
+    0x9a,
+    0x2d,
+    &[0x00, 0x90, 0x27, 0x3f, 0xc1, 0x4d],
+);
+
+pub const VT_100_PLUS_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x7baec70b,
+    0x57e0,
+    0x4c76,
+    0x8e,
+    0x87,
+    &[0x2f, 0x9e, 0x28, 0x08, 0x83, 0x43],
+);
+
+pub const VT_UTF8_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xad15a0d6,
+    0x8bec,
+    0x4acf,
+    0xa0,
+    0x73,
+    &[0xd0, 0x1d, 0xe7, 0x7e, 0x2d, 0x88],
+);
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct SasMessaging<__PrivateGuid = crate::base::Guid> {
+    pub header: Protocol,
+    pub guid: __PrivateGuid,
+    pub reserved: u32,
+    pub sas_address: u64,
+    pub lun: u64,
+    pub device_topology: u16,
+    pub relative_target_port: u16,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct MacAddressMessaging {
+    pub header: Protocol,
+    pub mac_address: crate::base::MacAddress,
+    pub if_type: u8,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct Ipv4Messaging {
+    pub header: Protocol,
+    pub local_ip_address: crate::base::Ipv4Address,
+    pub remote_ip_address: crate::base::Ipv4Address,
+    pub local_port: u16,
+    pub remote_port: u16,
+    pub protocol: u16,
+    pub static_ip_address: crate::base::Boolean,
+    pub gateway_ip_address: crate::base::Ipv4Address,
+    pub subnet_mask: crate::base::Ipv4Address,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct Ipv6Messaging {
+    pub header: Protocol,
+    pub local_ip_address: crate::base::Ipv6Address,
+    pub remote_ip_address: crate::base::Ipv6Address,
+    pub local_port: u16,
+    pub remote_port: u16,
+    pub protocol: u16,
+    pub ip_address_origin: u8,
+    pub prefix_length: u8,
+    pub gateway_ip_address: crate::base::Ipv6Address,
+}
+
+impl Ipv6Messaging {
+    pub const IP_ADDRESS_ORIGIN_MANUAL: u8 = 0x00;
+    pub const IP_ADDRESS_ORIGIN_STATELESS_AUTO_CONFIGURATION: u8 = 0x01;
+    pub const IP_ADDRESS_ORIGIN_STATEFUL_AUTO_CONFIGURATION: u8 = 0x02;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct UartMessaging {
+    pub header: Protocol,
+    pub reserved: u32,
+    pub baud_rate: u64,
+    pub data_bits: u8,
+    pub parity: u8,
+    pub stop_bits: u8,
+}
+
+impl UartMessaging {
+    pub const PARITY_DEFAULT: u8 = 0x00;
+    pub const PARITY_NONE: u8 = 0x01;
+    pub const PARITY_EVEN: u8 = 0x02;
+    pub const PARITY_ODD: u8 = 0x03;
+    pub const PARITY_MARK: u8 = 0x04;
+    pub const PARITY_SPACE: u8 = 0x05;
+
+    pub const STOP_BITS_DEFAULT: u8 = 0x00;
+    pub const STOP_BITS_1: u8 = 0x01;
+    pub const STOP_BITS_1_5: u8 = 0x02;
+    pub const STOP_BITS_2: u8 = 0x03;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct UsbClassMessaging {
+    pub header: Protocol,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub device_class: u8,
+    pub device_subclass: u8,
+    pub device_protocol: u8,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct UsbWwidMessaging<const N: usize = 0> {
+    pub header: Protocol,
+    pub interface_number: u16,
+    pub device_vendor_id: u16,
+    pub device_product_id: u16,
+    pub serial_number: [crate::base::Char16; N],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct DeviceLogicalUnitMessaging {
+    pub header: Protocol,
+    pub lun: u8,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct SataMessaging {
+    pub header: Protocol,
+    pub hba_port_number: u16,
+    pub port_multiplier_port_number: u16,
+    pub lun: u16,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct IscsiMessaging<const N: usize = 0> {
+    pub header: Protocol,
+    pub protocol: u16,
+    pub options: u16,
+    pub lun: [u8; 8],
+    pub target_portal_group_tag: u16,
+    pub target_name: [u8; N],
+}
+
+impl IscsiMessaging {
+    pub const PROTOCOL_TCP: u16 = 0x0000;
+
+    pub const OPTION_HEADER_DIGEST_CRC32C: u16 = 0x0002;
+    pub const OPTION_DATA_DIGEST_CRC32C: u16 = 0x0008;
+    pub const OPTION_AUTH_NONE: u16 = 0x0800;
+    pub const OPTION_AUTH_CHAP_UNI: u16 = 0x1000;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct VlanMessaging {
+    pub header: Protocol,
+    pub vlan_id: u16,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct SasExMessaging {
+    pub header: Protocol,
+    pub sas_address: [u8; 8],
+    pub lun: [u8; 8],
+    pub device_topology: u16,
+    pub relative_target_port: u16,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct NvmeNamespaceMessaging {
+    pub header: Protocol,
+    pub namespace_identifier: u32,
+    pub ieee_extended_unique_identifier: u64,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct UriMessaging<const N: usize = 0> {
+    pub header: Protocol,
+    pub uri: [u8; N],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct UfsMessaging {
+    pub header: Protocol,
+    pub pun: u8,
+    pub lun: u8,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct SdMessaging {
+    pub header: Protocol,
+    pub slot_number: u8,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct BluetoothMessaging {
+    pub header: Protocol,
+    pub bd_addr: [u8; 6],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct WifiMessaging {
+    pub header: Protocol,
+    pub ssid: [u8; 32],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct EmmcMessaging {
+    pub header: Protocol,
+    pub slot_number: u8,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct BluetoothLeMessaging {
+    pub header: Protocol,
+    pub bd_addr: [u8; 6],
+    pub address_type: u8,
+}
+
+impl BluetoothLeMessaging {
+    pub const ADDRESS_TYPE_PUBLIC: u8 = 0x00;
+    pub const ADDRESS_TYPE_RANDOM: u8 = 0x01;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct DnsMessaging<__PrivateIpAddress = crate::base::IpAddress, const N: usize = 0> {
+    pub header: Protocol,
+    pub is_ipv6: u8,
+    pub dns_server_ip: [__PrivateIpAddress; N],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct NvdimmNamespaceMessaging<__PrivateGuid = crate::base::Guid> {
+    pub header: Protocol,
+    pub uuid: __PrivateGuid,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct RestServiceMessaging {
+    pub header: Protocol,
+    pub rest_service: u8,
+    pub access_mode: u8,
+}
+
+impl RestServiceMessaging {
+    pub const REST_SERVICE_REDFISH: u8 = 0x01;
+    pub const REST_SERVICE_ODATA: u8 = 0x02;
+    pub const REST_SERVICE_VENDOR: u8 = 0xff;
+
+    pub const ACCESS_MODE_IN_BAND: u8 = 0x01;
+    pub const ACCESS_MODE_OUT_OF_BAND: u8 = 0x02;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct NvmeOfNamespaceMessaging<const N: usize = 0> {
+    pub header: Protocol,
+    pub namespace_identifier_type: u8,
+    pub namespace_identifier: [u8; 16],
+    pub subsystem_nqn: [u8; N],
+}
+
+impl NvmeOfNamespaceMessaging {
+    pub const NAMESPACE_IDENTIFIER_TYPE_EUI64: u8 = 0x01;
+    pub const NAMESPACE_IDENTIFIER_TYPE_NGUID: u8 = 0x02;
+    pub const NAMESPACE_IDENTIFIER_TYPE_UUID: u8 = 0x03;
+}
+
+//
+// Media Device Paths
+//
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Media {
+    pub header: Protocol,
+}
+
+impl Media {
+    pub const SUBTYPE_HARDDRIVE: u8 = 0x01;
+    pub const SUBTYPE_CDROM: u8 = 0x02;
+    pub const SUBTYPE_VENDOR: u8 = 0x03;
+    pub const SUBTYPE_FILE_PATH: u8 = 0x04;
+    pub const SUBTYPE_MEDIA_PROTOCOL: u8 = 0x05;
+    pub const SUBTYPE_PIWG_FIRMWARE_FILE: u8 = 0x06;
+    pub const SUBTYPE_PIWG_FIRMWARE_VOLUME: u8 = 0x07;
+    pub const SUBTYPE_RELATIVE_OFFSET_RANGE: u8 = 0x08;
+    pub const SUBTYPE_RAM_DISK: u8 = 0x09;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct HardDriveMedia {
+    pub header: Protocol,
+    pub partition_number: u32,
+    pub partition_start: u64,
+    pub partition_size: u64,
+    pub partition_signature: [u8; 16],
+    pub partition_format: u8,
+    pub signature_type: u8,
+}
+
+impl HardDriveMedia {
+    pub const PARTITION_FORMAT_MBR: u8 = 0x01;
+    pub const PARTITION_FORMAT_GPT: u8 = 0x02;
+
+    pub const SIGNATURE_TYPE_NONE: u8 = 0x00;
+    pub const SIGNATURE_TYPE_MBR: u8 = 0x01;
+    pub const SIGNATURE_TYPE_GUID: u8 = 0x02;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct CdromMedia {
+    pub header: Protocol,
+    pub boot_entry: u32,
+    pub partition_start: u64,
+    pub partition_size: u64,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct VendorMedia<__PrivateGuid = crate::base::Guid, const N: usize = 0> {
+    pub header: Protocol,
+    pub vendor_guid: __PrivateGuid,
+    pub vendor_defined_data: [u8; N],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct FilePathMedia<const N: usize = 0> {
+    pub header: Protocol,
+    pub path_name: [crate::base::Char16; N],
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct MediaProtocolMedia<__PrivateGuid = crate::base::Guid> {
+    pub header: Protocol,
+    pub protocol: __PrivateGuid,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct PiwgFirmwareFileMedia<__PrivateGuid = crate::base::Guid> {
+    pub header: Protocol,
+    pub fv_file_name: __PrivateGuid,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct PiwgFirmwareVolumeMedia<__PrivateGuid = crate::base::Guid> {
+    pub header: Protocol,
+    pub fv_name: __PrivateGuid,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct RelativeOffsetRangeMedia {
+    pub header: Protocol,
+    pub reserved: u32,
+    pub starting_offset: u64,
+    pub ending_offset: u64,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct RamDiskMedia<__PrivateGuid = crate::base::Guid> {
+    pub header: Protocol,
+    pub starting_address: u64,
+    pub ending_address: u64,
+    pub disk_type: __PrivateGuid,
+    pub disk_instance: u16,
+}
+
+pub const VIRTUAL_DISK_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x77ab535a,
+    0x45fc,
+    0x624b,
+    0x55,
+    0x60,
+    &[0xf7, 0xb2, 0x81, 0xd1, 0xf9, 0x6e],
+);
+
+pub const VIRTUAL_CD_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x3d5abd30,
+    0x4175,
+    0x87ce,
+    0x6d,
+    0x64,
+    &[0xd2, 0xad, 0xe5, 0x23, 0xc4, 0xbb],
+);
+
+pub const PERSISTENT_VIRTUAL_DISK_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x5cea02c9,
+    0x4d07,
+    0x69d3,
+    0x26,
+    0x9f,
+    &[0x44, 0x96, 0xfb, 0xe0, 0x96, 0xf9],
+);
+
+pub const PERSISTENT_VIRTUAL_CD_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x08018188,
+    0x42cd,
+    0xbb48,
+    0x10,
+    0x0f,
+    &[0x53, 0x87, 0xd5, 0x3d, 0xed, 0x3d],
+);
+
+//
+// BIOS Boot Specification Device Paths
+//
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Bios {
+    pub header: Protocol,
+}
+
+impl Bios {
+    pub const SUBTYPE_BBS: u8 = 0x01;
+}
+
+// The description is a NUL-terminated ASCII string.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug)]
+pub struct BbsBios<const N: usize = 0> {
+    pub header: Protocol,
+    pub device_type: u16,
+    pub status_flag: u16,
+    pub description: [u8; N],
+}
+
+impl BbsBios {
+    pub const DEVICE_TYPE_FLOPPY: u16 = 0x0001;
+    pub const DEVICE_TYPE_HARDDRIVE: u16 = 0x0002;
+    pub const DEVICE_TYPE_CDROM: u16 = 0x0003;
+    pub const DEVICE_TYPE_PCMCIA: u16 = 0x0004;
+    pub const DEVICE_TYPE_USB: u16 = 0x0005;
+    pub const DEVICE_TYPE_EMBEDDED_NETWORK: u16 = 0x0006;
+    pub const DEVICE_TYPE_BEV: u16 = 0x0080;
+    pub const DEVICE_TYPE_UNKNOWN: u16 = 0x00ff;
+}
+
+//
+// Device Path Parsing
+//
+// Device paths are self-describing via the `length` field of each node header, but nothing
+// guarantees that these fields are sane. The following types validate a device path once, and
+// then provide safe, zero-copy iteration over its nodes and instances. They operate on plain
+// byte slices, so they can be used on device paths extracted from variable data just as well as
+// on device paths provided by the firmware.
+//
+
+/// Device Path Errors
+///
+/// This is returned when a device path fails validation.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum DevicePathError {
+    /// The data ends before a node header, before the end of a node, or
+    /// before the end-of-entire-path node.
+    Truncated,
+    /// A node is shorter than its header, or an end node has a length other
+    /// than that of its header.
+    NodeLength,
+}
+
+/// Device Path
+///
+/// This is a validated device path, borrowing the raw data of all its nodes
+/// including the terminating end-of-entire-path node. All node lengths have
+/// been verified to be in bounds, so iterating the nodes cannot fail.
+///
+/// Device paths compare equal if they consist of the same nodes.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct DevicePath<'a> {
+    bytes: &'a [u8],
+}
+
+/// Device Path Instance
+///
+/// This is a single instance of a (possibly multi-instance) device path,
+/// borrowing the raw data of its nodes without the terminating end node.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct DevicePathInstance<'a> {
+    bytes: &'a [u8],
+}
+
+/// Raw Device Path Node
+///
+/// This is a single node of a validated device path, borrowing its raw data
+/// including the node header.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct RawNode<'a> {
+    bytes: &'a [u8],
+}
+
+/// Device Path Node
+///
+/// This is a decoded device path node. Nodes with trailing variable-sized
+/// data carry the fixed part of the node and a slice of the remaining data.
+/// Nodes with unknown types, or with lengths that do not match their
+/// definition, are returned as `Unknown`.
+#[derive(Clone, Copy, Debug)]
+pub enum Node<'a> {
+    EndInstance,
+    EndEntire,
+
+    PciHardware(PciHardware),
+    PccardHardware(PccardHardware),
+    MmapHardware(MmapHardware),
+    VendorHardware(VendorHardware, &'a [u8]),
+    ControllerHardware(ControllerHardware),
+    BmcHardware(BmcHardware),
+
+    HidAcpi(HidAcpi),
+    ExpandedAcpi(ExpandedAcpi, &'a [u8]),
+    AdrAcpi(AdrAcpi, &'a [u8]),
+    NvdimmAcpi(NvdimmAcpi),
+
+    AtapiMessaging(AtapiMessaging),
+    ScsiMessaging(ScsiMessaging),
+    FibreChannelMessaging(FibreChannelMessaging),
+    FibreChannelExMessaging(FibreChannelExMessaging),
+    Ieee1394Messaging(Ieee1394Messaging),
+    UsbMessaging(UsbMessaging),
+    I2oMessaging(I2oMessaging),
+    InfinibandMessaging(InfinibandMessaging),
+    VendorMessaging(VendorMessaging, &'a [u8]),
+    UartFlowControlMessaging(UartFlowControlMessaging),
+    SasMessaging(SasMessaging),
+    MacAddressMessaging(MacAddressMessaging),
+    Ipv4Messaging(Ipv4Messaging),
+    Ipv6Messaging(Ipv6Messaging),
+    UartMessaging(UartMessaging),
+    UsbClassMessaging(UsbClassMessaging),
+    UsbWwidMessaging(UsbWwidMessaging, &'a [u8]),
+    DeviceLogicalUnitMessaging(DeviceLogicalUnitMessaging),
+    SataMessaging(SataMessaging),
+    IscsiMessaging(IscsiMessaging, &'a [u8]),
+    VlanMessaging(VlanMessaging),
+    SasExMessaging(SasExMessaging),
+    NvmeNamespaceMessaging(NvmeNamespaceMessaging),
+    UriMessaging(UriMessaging, &'a [u8]),
+    UfsMessaging(UfsMessaging),
+    SdMessaging(SdMessaging),
+    BluetoothMessaging(BluetoothMessaging),
+    WifiMessaging(WifiMessaging),
+    EmmcMessaging(EmmcMessaging),
+    BluetoothLeMessaging(BluetoothLeMessaging),
+    DnsMessaging(DnsMessaging, &'a [u8]),
+    NvdimmNamespaceMessaging(NvdimmNamespaceMessaging),
+    RestServiceMessaging(RestServiceMessaging, &'a [u8]),
+    NvmeOfNamespaceMessaging(NvmeOfNamespaceMessaging, &'a [u8]),
+
+    HardDriveMedia(HardDriveMedia),
+    CdromMedia(CdromMedia),
+    VendorMedia(VendorMedia, &'a [u8]),
+    FilePathMedia(FilePathMedia, &'a [u8]),
+    MediaProtocolMedia(MediaProtocolMedia),
+    PiwgFirmwareFileMedia(PiwgFirmwareFileMedia),
+    PiwgFirmwareVolumeMedia(PiwgFirmwareVolumeMedia),
+    RelativeOffsetRangeMedia(RelativeOffsetRangeMedia),
+    RamDiskMedia(RamDiskMedia),
+
+    BbsBios(BbsBios, &'a [u8]),
+
+    Unknown(RawNode<'a>),
+}
+
+/// Raw Device Path Node Iterator
+///
+/// This iterates the raw nodes of a device path or device path instance. It
+/// yields end-of-instance nodes, but stops at the end-of-entire-path node.
+#[derive(Clone, Debug)]
+pub struct RawNodes<'a> {
+    bytes: &'a [u8],
+}
+
+/// Device Path Node Iterator
+///
+/// This is like `RawNodes`, but decodes each node. See `RawNode::node()`.
+#[derive(Clone, Debug)]
+pub struct Nodes<'a> {
+    raw: RawNodes<'a>,
+}
+
+/// Device Path Instance Iterator
+///
+/// This splits a device path at its end nodes and yields each instance.
+/// Like `str::split()`, a device path consisting of only an
+/// end-of-entire-path node yields a single empty instance.
+#[derive(Clone, Debug)]
+pub struct Instances<'a> {
+    bytes: Option<&'a [u8]>,
+}
+
+// Return the length of the node at the start of `bytes`, as specified by its
+// header, or `None` if the header is truncated.
+fn node_length(bytes: &[u8]) -> Option<usize> {
+    Some(u16::from_le_bytes([*bytes.get(2)?, *bytes.get(3)?]) as usize)
+}
+
+// Read a node structure from the start of `bytes` and return it together with
+// the data following it.
+//
+// SAFETY: `T` must be one of the node structures of this module. They are
+//         packed and consist of plain integers, thus are valid for any
+//         bit-pattern and any alignment.
+unsafe fn read_node<T: Copy>(bytes: &[u8]) -> Option<(T, &[u8])> {
+    let size = core::mem::size_of::<T>();
+    if bytes.len() < size {
+        return None;
+    }
+    // SAFETY: The length was checked above, and the caller guarantees `T` is
+    //         valid for any bit-pattern.
+    let v = unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const T) };
+    Some((v, &bytes[size..]))
+}
+
+impl core::fmt::Display for DevicePathError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            DevicePathError::Truncated => write!(f, "device path truncated"),
+            DevicePathError::NodeLength => write!(f, "invalid device path node length"),
+        }
+    }
+}
+
+impl core::error::Error for DevicePathError {}
+
+impl<'a> DevicePath<'a> {
+    /// Parse Device Path
+    ///
+    /// Validate the device path at the start of `bytes`. Any data following
+    /// the end-of-entire-path node is ignored; use `as_bytes().len()` to
+    /// find where it starts.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, DevicePathError> {
+        let mut size = 0;
+
+        loop {
+            let rest = &bytes[size..];
+            let len = node_length(rest).ok_or(DevicePathError::Truncated)?;
+            let is_end = rest[0] == TYPE_END;
+
+            if len < core::mem::size_of::<Protocol>()
+                || (is_end && len != core::mem::size_of::<Protocol>())
+            {
+                return Err(DevicePathError::NodeLength);
+            }
+            if len > rest.len() {
+                return Err(DevicePathError::Truncated);
+            }
+
+            size += len;
+            if is_end && rest[1] == End::SUBTYPE_ENTIRE {
+                return Ok(Self { bytes: &bytes[..size] });
+            }
+        }
+    }
+
+    /// Validate Device Path in Memory
+    ///
+    /// Walk the device path at `ptr` until its end-of-entire-path node, and
+    /// return a validated view of it.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a device path whose nodes are readable and stay
+    /// unmodified for `'a`, up to and including the first node that either
+    /// has an invalid length or is an end-of-entire-path node.
+    pub unsafe fn from_ptr(ptr: *const Protocol) -> Result<Self, DevicePathError> {
+        let base = ptr as *const u8;
+        let mut size: usize = 0;
+
+        loop {
+            // SAFETY: The caller guarantees this node is readable. Nodes are
+            //         byte-aligned, so the header is read unaligned.
+            let header = unsafe { core::ptr::read_unaligned(base.add(size) as *const Protocol) };
+            let len = u16::from_le_bytes(header.length) as usize;
+            let is_end = header.r#type == TYPE_END;
+
+            if len < core::mem::size_of::<Protocol>()
+                || (is_end && len != core::mem::size_of::<Protocol>())
+            {
+                return Err(DevicePathError::NodeLength);
+            }
+
+            size = size
+                .checked_add(len)
+                .filter(|v| *v <= isize::MAX as usize)
+                .ok_or(DevicePathError::Truncated)?;
+            if is_end && header.sub_type == End::SUBTYPE_ENTIRE {
+                // SAFETY: The caller guarantees all nodes up to here are
+                //         readable for `'a`.
+                let bytes = unsafe { core::slice::from_raw_parts(base, size) };
+                return Ok(Self { bytes });
+            }
+        }
+    }
+
+    /// Return the raw data of the device path, including the terminating
+    /// end-of-entire-path node.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Return a pointer to the first node of the device path.
+    pub fn as_ptr(&self) -> *const Protocol {
+        self.bytes.as_ptr() as *const Protocol
+    }
+
+    /// Iterate the raw nodes of the device path.
+    pub fn raw_nodes(&self) -> RawNodes<'a> {
+        RawNodes { bytes: self.bytes }
+    }
+
+    /// Iterate the decoded nodes of the device path.
+    pub fn nodes(&self) -> Nodes<'a> {
+        Nodes { raw: self.raw_nodes() }
+    }
+
+    /// Iterate the instances of the device path.
+    pub fn instances(&self) -> Instances<'a> {
+        Instances { bytes: Some(self.bytes) }
+    }
+}
+
+impl<'a> DevicePathInstance<'a> {
+    /// Return the raw data of the instance, excluding the end node.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Check whether the instance has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Iterate the raw nodes of the instance.
+    pub fn raw_nodes(&self) -> RawNodes<'a> {
+        RawNodes { bytes: self.bytes }
+    }
+
+    /// Iterate the decoded nodes of the instance.
+    pub fn nodes(&self) -> Nodes<'a> {
+        Nodes { raw: self.raw_nodes() }
+    }
+}
+
+impl<'a> RawNode<'a> {
+    pub fn r#type(&self) -> u8 {
+        self.bytes[0]
+    }
+
+    pub fn sub_type(&self) -> u8 {
+        self.bytes[1]
+    }
+
+    /// Return the length of the node, including its header.
+    pub fn length(&self) -> u16 {
+        self.bytes.len() as u16
+    }
+
+    /// Return the raw data of the node, including its header.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Return the raw data of the node following its header.
+    pub fn data(&self) -> &'a [u8] {
+        &self.bytes[core::mem::size_of::<Protocol>()..]
+    }
+
+    pub fn is_end(&self) -> bool {
+        self.r#type() == TYPE_END
+    }
+
+    pub fn is_end_instance(&self) -> bool {
+        self.is_end() && self.sub_type() == End::SUBTYPE_INSTANCE
+    }
+
+    pub fn is_end_entire(&self) -> bool {
+        self.is_end() && self.sub_type() == End::SUBTYPE_ENTIRE
+    }
+
+    /// Decode Node
+    ///
+    /// Decode the node based on its type and sub-type. Nodes with a fixed
+    /// size must match it exactly, nodes with trailing data must at least
+    /// contain their fixed part. Otherwise, the node is returned as
+    /// `Node::Unknown`.
+    pub fn node(&self) -> Node<'a> {
+        let bytes = self.bytes;
+
+        macro_rules! fixed {
+            ($t:ident) => {
+                // SAFETY: `$t` is a node structure of this module.
+                match unsafe { read_node::<$t>(bytes) } {
+                    Some((v, [])) => Node::$t(v),
+                    _ => Node::Unknown(*self),
+                }
+            };
+        }
+        macro_rules! variable {
+            ($t:ident) => {
+                // SAFETY: `$t` is a node structure of this module.
+                match unsafe { read_node::<$t>(bytes) } {
+                    Some((v, data)) => Node::$t(v, data),
+                    None => Node::Unknown(*self),
+                }
+            };
+        }
+
+        match (self.r#type(), self.sub_type()) {
+            (TYPE_END, End::SUBTYPE_INSTANCE) => Node::EndInstance,
+            (TYPE_END, End::SUBTYPE_ENTIRE) => Node::EndEntire,
+
+            (TYPE_HARDWARE, Hardware::SUBTYPE_PCI) => fixed!(PciHardware),
+            (TYPE_HARDWARE, Hardware::SUBTYPE_PCCARD) => fixed!(PccardHardware),
+            (TYPE_HARDWARE, Hardware::SUBTYPE_MMAP) => fixed!(MmapHardware),
+            (TYPE_HARDWARE, Hardware::SUBTYPE_VENDOR) => variable!(VendorHardware),
+            (TYPE_HARDWARE, Hardware::SUBTYPE_CONTROLLER) => fixed!(ControllerHardware),
+            (TYPE_HARDWARE, Hardware::SUBTYPE_BMC) => fixed!(BmcHardware),
+
+            (TYPE_ACPI, Acpi::SUBTYPE_ACPI) => fixed!(HidAcpi),
+            (TYPE_ACPI, Acpi::SUBTYPE_EXPANDED) => variable!(ExpandedAcpi),
+            (TYPE_ACPI, Acpi::SUBTYPE_ADR) => variable!(AdrAcpi),
+            (TYPE_ACPI, Acpi::SUBTYPE_NVDIMM) => fixed!(NvdimmAcpi),
+
+            (TYPE_MESSAGING, Messaging::SUBTYPE_ATAPI) => fixed!(AtapiMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_SCSI) => fixed!(ScsiMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_FIBRE_CHANNEL) => fixed!(FibreChannelMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_FIBRE_CHANNEL_EX) => {
+                fixed!(FibreChannelExMessaging)
+            }
+            (TYPE_MESSAGING, Messaging::SUBTYPE_1394) => fixed!(Ieee1394Messaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_USB) => fixed!(UsbMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_I2O) => fixed!(I2oMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_INFINIBAND) => fixed!(InfinibandMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_VENDOR) => {
+                // UART flow control and SAS nodes are vendor nodes with a
+                // well-known GUID, so decode them as such if they match.
+                // SAFETY: These are node structures of this module.
+                let (uart, sas) = unsafe {
+                    (
+                        read_node::<UartFlowControlMessaging>(bytes),
+                        read_node::<SasMessaging>(bytes),
+                    )
+                };
+                match (uart, sas) {
+                    (Some((v, [])), _) if { v.guid } == UART_FLOW_CONTROL_GUID => {
+                        Node::UartFlowControlMessaging(v)
+                    }
+                    (_, Some((v, []))) if { v.guid } == SAS_GUID => Node::SasMessaging(v),
+                    _ => variable!(VendorMessaging),
+                }
+            }
+            (TYPE_MESSAGING, Messaging::SUBTYPE_MAC_ADDRESS) => fixed!(MacAddressMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_IPV4) => fixed!(Ipv4Messaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_IPV6) => fixed!(Ipv6Messaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_UART) => fixed!(UartMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_USB_CLASS) => fixed!(UsbClassMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_USB_WWID) => variable!(UsbWwidMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_DEVICE_LOGICAL_UNIT) => {
+                fixed!(DeviceLogicalUnitMessaging)
+            }
+            (TYPE_MESSAGING, Messaging::SUBTYPE_SATA) => fixed!(SataMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_ISCSI) => variable!(IscsiMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_VLAN) => fixed!(VlanMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_SAS_EX) => fixed!(SasExMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_NVME_NAMESPACE) => fixed!(NvmeNamespaceMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_URI) => variable!(UriMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_UFS) => fixed!(UfsMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_SD) => fixed!(SdMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_BLUETOOTH) => fixed!(BluetoothMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_WIFI) => fixed!(WifiMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_EMMC) => fixed!(EmmcMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_BLUETOOTH_LE) => fixed!(BluetoothLeMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_DNS) => variable!(DnsMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_NVDIMM_NAMESPACE) => {
+                fixed!(NvdimmNamespaceMessaging)
+            }
+            (TYPE_MESSAGING, Messaging::SUBTYPE_REST_SERVICE) => variable!(RestServiceMessaging),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_NVME_OF_NAMESPACE) => {
+                variable!(NvmeOfNamespaceMessaging)
+            }
+
+            (TYPE_MEDIA, Media::SUBTYPE_HARDDRIVE) => fixed!(HardDriveMedia),
+            (TYPE_MEDIA, Media::SUBTYPE_CDROM) => fixed!(CdromMedia),
+            (TYPE_MEDIA, Media::SUBTYPE_VENDOR) => variable!(VendorMedia),
+            (TYPE_MEDIA, Media::SUBTYPE_FILE_PATH) => variable!(FilePathMedia),
+            (TYPE_MEDIA, Media::SUBTYPE_MEDIA_PROTOCOL) => fixed!(MediaProtocolMedia),
+            (TYPE_MEDIA, Media::SUBTYPE_PIWG_FIRMWARE_FILE) => fixed!(PiwgFirmwareFileMedia),
+            (TYPE_MEDIA, Media::SUBTYPE_PIWG_FIRMWARE_VOLUME) => fixed!(PiwgFirmwareVolumeMedia),
+            (TYPE_MEDIA, Media::SUBTYPE_RELATIVE_OFFSET_RANGE) => {
+                fixed!(RelativeOffsetRangeMedia)
+            }
+            (TYPE_MEDIA, Media::SUBTYPE_RAM_DISK) => fixed!(RamDiskMedia),
+
+            (TYPE_BIOS, Bios::SUBTYPE_BBS) => variable!(BbsBios),
+
+            _ => Node::Unknown(*self),
+        }
+    }
+}
+
+impl<'a> Iterator for RawNodes<'a> {
+    type Item = RawNode<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The data was validated, but stay defensive and stop on anything
+        // that does not look like a node.
+        let len = node_length(self.bytes)?;
+        if len < core::mem::size_of::<Protocol>() || len > self.bytes.len() {
+            self.bytes = &[];
+            return None;
+        }
+
+        let (node, rest) = self.bytes.split_at(len);
+        let node = RawNode { bytes: node };
+        self.bytes = if node.is_end_entire() { &[] } else { rest };
+
+        if node.is_end_entire() {
+            None
+        } else {
+            Some(node)
+        }
+    }
+}
+
+impl<'a> Iterator for Nodes<'a> {
+    type Item = Node<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.raw.next().map(|v| v.node())
+    }
+}
+
+impl<'a> Iterator for Instances<'a> {
+    type Item = DevicePathInstance<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.bytes?;
+        let mut nodes = RawNodes { bytes };
+        let mut size = 0;
+
+        loop {
+            match nodes.next() {
+                Some(v) if v.is_end_instance() => {
+                    self.bytes = Some(&bytes[size + v.bytes.len()..]);
+                    break;
+                }
+                Some(v) => size += v.bytes.len(),
+                None => {
+                    self.bytes = None;
+                    break;
+                }
+            }
+        }
+
+        Some(DevicePathInstance { bytes: &bytes[..size] })
+    }
+}
+
+//
+// Device Path Construction
+//
+// Device paths are built node by node into a storage buffer. The builder computes the `length`
+// field of each node, terminates instances and the entire path with end nodes, and rejects nodes
+// that cannot be represented, rather than producing a corrupt device path.
+//
+
+/// Typed Device Path Node
+///
+/// This is implemented by all node structures of this module and associates
+/// them with their node type and sub-type. Nodes that are vendor nodes with a
+/// well-known GUID (e.g., `UartFlowControlMessaging`) use the vendor
+/// sub-type, and the caller must fill in the GUID.
+///
+/// # Safety
+///
+/// Implementors must be packed structures without padding, consisting of
+/// plain integers, and starting with a `Protocol` header. The node data is
+/// copied and read as raw bytes.
+pub unsafe trait TypedNode: Copy {
+    const TYPE: u8;
+    const SUB_TYPE: u8;
+
+    /// Return a node header for this node type, with the length set to the
+    /// size of the fixed part of the node.
+    fn header() -> Protocol {
+        Protocol {
+            r#type: Self::TYPE,
+            sub_type: Self::SUB_TYPE,
+            length: (core::mem::size_of::<Self>() as u16).to_le_bytes(),
+        }
+    }
+}
+
+macro_rules! typed_node {
+    ($({$($generics:tt)*})? $t:ty, $type:expr, $sub_type:expr $(,)?) => {
+        // SAFETY: All node structures of this module are packed structures
+        //         of plain integers, starting with a `Protocol` header.
+        unsafe impl<$($($generics)*)?> TypedNode for $t {
+            const TYPE: u8 = $type;
+            const SUB_TYPE: u8 = $sub_type;
+        }
+    };
+}
+
+typed_node!(PciHardware, TYPE_HARDWARE, Hardware::SUBTYPE_PCI);
+typed_node!(PccardHardware, TYPE_HARDWARE, Hardware::SUBTYPE_PCCARD);
+typed_node!(MmapHardware, TYPE_HARDWARE, Hardware::SUBTYPE_MMAP);
+typed_node!(
+    {const N: usize} VendorHardware<crate::base::Guid, N>,
+    TYPE_HARDWARE,
+    Hardware::SUBTYPE_VENDOR,
+);
+typed_node!(ControllerHardware, TYPE_HARDWARE, Hardware::SUBTYPE_CONTROLLER);
+typed_node!(BmcHardware, TYPE_HARDWARE, Hardware::SUBTYPE_BMC);
+
+typed_node!(HidAcpi, TYPE_ACPI, Acpi::SUBTYPE_ACPI);
+typed_node!({const N: usize} ExpandedAcpi<N>, TYPE_ACPI, Acpi::SUBTYPE_EXPANDED);
+typed_node!({const N: usize} AdrAcpi<N>, TYPE_ACPI, Acpi::SUBTYPE_ADR);
+typed_node!(NvdimmAcpi, TYPE_ACPI, Acpi::SUBTYPE_NVDIMM);
+
+typed_node!(AtapiMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_ATAPI);
+typed_node!(ScsiMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_SCSI);
+typed_node!(FibreChannelMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_FIBRE_CHANNEL);
+typed_node!(FibreChannelExMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_FIBRE_CHANNEL_EX);
+typed_node!(Ieee1394Messaging, TYPE_MESSAGING, Messaging::SUBTYPE_1394);
+typed_node!(UsbMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_USB);
+typed_node!(I2oMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_I2O);
+typed_node!(InfinibandMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_INFINIBAND);
+typed_node!(
+    {const N: usize} VendorMessaging<crate::base::Guid, N>,
+    TYPE_MESSAGING,
+    Messaging::SUBTYPE_VENDOR,
+);
+typed_node!(UartFlowControlMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_VENDOR);
+typed_node!(SasMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_VENDOR);
+typed_node!(MacAddressMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_MAC_ADDRESS);
+typed_node!(Ipv4Messaging, TYPE_MESSAGING, Messaging::SUBTYPE_IPV4);
+typed_node!(Ipv6Messaging, TYPE_MESSAGING, Messaging::SUBTYPE_IPV6);
+typed_node!(UartMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_UART);
+typed_node!(UsbClassMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_USB_CLASS);
+typed_node!({const N: usize} UsbWwidMessaging<N>, TYPE_MESSAGING, Messaging::SUBTYPE_USB_WWID);
+typed_node!(
+    DeviceLogicalUnitMessaging,
+    TYPE_MESSAGING,
+    Messaging::SUBTYPE_DEVICE_LOGICAL_UNIT,
+);
+typed_node!(SataMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_SATA);
+typed_node!({const N: usize} IscsiMessaging<N>, TYPE_MESSAGING, Messaging::SUBTYPE_ISCSI);
+typed_node!(VlanMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_VLAN);
+typed_node!(SasExMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_SAS_EX);
+typed_node!(NvmeNamespaceMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_NVME_NAMESPACE);
+typed_node!({const N: usize} UriMessaging<N>, TYPE_MESSAGING, Messaging::SUBTYPE_URI);
+typed_node!(UfsMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_UFS);
+typed_node!(SdMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_SD);
+typed_node!(BluetoothMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_BLUETOOTH);
+typed_node!(WifiMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_WIFI);
+typed_node!(EmmcMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_EMMC);
+typed_node!(BluetoothLeMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_BLUETOOTH_LE);
+typed_node!(
+    {const N: usize} DnsMessaging<crate::base::IpAddress, N>,
+    TYPE_MESSAGING,
+    Messaging::SUBTYPE_DNS,
+);
+typed_node!(NvdimmNamespaceMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_NVDIMM_NAMESPACE);
+typed_node!(RestServiceMessaging, TYPE_MESSAGING, Messaging::SUBTYPE_REST_SERVICE);
+typed_node!(
+    {const N: usize} NvmeOfNamespaceMessaging<N>,
+    TYPE_MESSAGING,
+    Messaging::SUBTYPE_NVME_OF_NAMESPACE,
+);
+
+typed_node!(HardDriveMedia, TYPE_MEDIA, Media::SUBTYPE_HARDDRIVE);
+typed_node!(CdromMedia, TYPE_MEDIA, Media::SUBTYPE_CDROM);
+typed_node!(
+    {const N: usize} VendorMedia<crate::base::Guid, N>,
+    TYPE_MEDIA,
+    Media::SUBTYPE_VENDOR,
+);
+typed_node!({const N: usize} FilePathMedia<N>, TYPE_MEDIA, Media::SUBTYPE_FILE_PATH);
+typed_node!(MediaProtocolMedia, TYPE_MEDIA, Media::SUBTYPE_MEDIA_PROTOCOL);
+typed_node!(PiwgFirmwareFileMedia, TYPE_MEDIA, Media::SUBTYPE_PIWG_FIRMWARE_FILE);
+typed_node!(PiwgFirmwareVolumeMedia, TYPE_MEDIA, Media::SUBTYPE_PIWG_FIRMWARE_VOLUME);
+typed_node!(RelativeOffsetRangeMedia, TYPE_MEDIA, Media::SUBTYPE_RELATIVE_OFFSET_RANGE);
+typed_node!(RamDiskMedia, TYPE_MEDIA, Media::SUBTYPE_RAM_DISK);
+
+typed_node!({const N: usize} BbsBios<N>, TYPE_BIOS, Bios::SUBTYPE_BBS);
+
+/// Device Path Builder Errors
+///
+/// This is returned when appending to a device path builder fails. The
+/// builder is left unmodified in that case.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum DevicePathBuilderError {
+    /// The node is shorter than its header or longer than the maximum
+    /// node length, or is an end node.
+    NodeSize,
+    /// The file path is empty, contains NUL characters, or characters that
+    /// cannot be encoded as UCS-2.
+    FilePath,
+    /// An instance was terminated without any nodes.
+    EmptyInstance,
+    /// The total size of the device path overflows.
+    Overflow,
+    /// The buffer is too small, or could not be grown. The required size is
+    /// included.
+    BufferTooSmall(usize),
+}
+
+/// Device Path Storage
+///
+/// This is the storage a `DevicePathBuilder` writes to. It is implemented
+/// for caller-provided byte slices, and for `Vec<u8>` with the `alloc`
+/// feature.
+pub trait DevicePathStorage {
+    /// The type returned by `DevicePathBuilder::finish()`.
+    type Output;
+
+    /// Return the storage, with room for at least `len` bytes, or `None` if
+    /// that is not possible.
+    fn reserve(&mut self, len: usize) -> Option<&mut [u8]>;
+
+    /// Return the storage. This includes at least all bytes written so far.
+    fn as_bytes(&self) -> &[u8];
+
+    /// Return the final device path, given its length.
+    fn finish(self, len: usize) -> Self::Output;
+}
+
+impl<'a> DevicePathStorage for &'a mut [u8] {
+    type Output = &'a [u8];
+
+    fn reserve(&mut self, len: usize) -> Option<&mut [u8]> {
+        if self.len() < len {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn finish(self, len: usize) -> Self::Output {
+        &self[..len]
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DevicePathStorage for alloc::vec::Vec<u8> {
+    type Output = alloc::vec::Vec<u8>;
+
+    fn reserve(&mut self, len: usize) -> Option<&mut [u8]> {
+        if self.len() < len {
+            self.try_reserve(len - self.len()).ok()?;
+            self.resize(len, 0);
+        }
+        Some(self)
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn finish(mut self, len: usize) -> Self::Output {
+        self.truncate(len);
+        self
+    }
+}
+
+/// Device Path Builder
+///
+/// This builds a device path in a `DevicePathStorage`. Nodes are appended
+/// to the current instance, `end_instance()` starts a new instance, and
+/// `finish()` terminates the device path with an end-of-entire-path node.
+#[derive(Debug)]
+pub struct DevicePathBuilder<B> {
+    storage: B,
+    len: usize,
+    instance: usize,
+}
+
+impl<B: DevicePathStorage> DevicePathBuilder<B> {
+    /// Create an Empty Device Path Builder
+    ///
+    /// Any existing contents of `storage` are overwritten.
+    pub fn new(storage: B) -> Self {
+        Self { storage, len: 0, instance: 0 }
+    }
+
+    /// Return the Device Path Size
+    ///
+    /// This returns the size of all nodes appended so far, excluding the
+    /// final end node.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether no Nodes were Appended
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Reserve space for a node of `length` bytes and return it, after
+    // writing its header.
+    fn node(
+        &mut self,
+        r#type: u8,
+        sub_type: u8,
+        length: usize,
+    ) -> Result<&mut [u8], DevicePathBuilderError> {
+        if length < core::mem::size_of::<Protocol>() || length > u16::MAX as usize {
+            return Err(DevicePathBuilderError::NodeSize);
+        }
+
+        // Unless this is the final end node, account for it already, so
+        // `finish()` cannot fail due to lack of space.
+        let end = self.len.checked_add(length).ok_or(DevicePathBuilderError::Overflow)?;
+        let trailer = if r#type == TYPE_END && sub_type == End::SUBTYPE_ENTIRE {
+            0
+        } else {
+            core::mem::size_of::<Protocol>()
+        };
+        let required = end
+            .checked_add(trailer)
+            .filter(|v| *v <= isize::MAX as usize)
+            .ok_or(DevicePathBuilderError::Overflow)?;
+
+        let start = self.len;
+        let buffer = self
+            .storage
+            .reserve(required)
+            .ok_or(DevicePathBuilderError::BufferTooSmall(required))?;
+        let node = &mut buffer[start..end];
+        node[0] = r#type;
+        node[1] = sub_type;
+        node[2..4].copy_from_slice(&(length as u16).to_le_bytes());
+        self.len = end;
+        Ok(&mut node[core::mem::size_of::<Protocol>()..])
+    }
+
+    /// Append a Raw Node
+    ///
+    /// Append a node of the given type and sub-type, with `data` following
+    /// the node header. End nodes cannot be appended this way.
+    pub fn push_raw(
+        &mut self,
+        r#type: u8,
+        sub_type: u8,
+        data: &[u8],
+    ) -> Result<(), DevicePathBuilderError> {
+        if r#type == TYPE_END {
+            return Err(DevicePathBuilderError::NodeSize);
+        }
+
+        let length = data
+            .len()
+            .checked_add(core::mem::size_of::<Protocol>())
+            .ok_or(DevicePathBuilderError::NodeSize)?;
+        self.node(r#type, sub_type, length)?.copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Append a Typed Node
+    ///
+    /// Append `node` followed by `data`. This is meant for nodes with
+    /// trailing variable-sized data, but `data` can also be empty. The node
+    /// header of `node` is ignored, and instead derived from the node type
+    /// and the total size.
+    pub fn push_with_data<T: TypedNode>(
+        &mut self,
+        node: &T,
+        data: &[u8],
+    ) -> Result<(), DevicePathBuilderError> {
+        let size = core::mem::size_of::<T>();
+        let length = size.checked_add(data.len()).ok_or(DevicePathBuilderError::NodeSize)?;
+
+        // SAFETY: `TypedNode` guarantees `T` consists of plain integers
+        //         without padding, so it can be viewed as bytes.
+        let bytes = unsafe { core::slice::from_raw_parts(node as *const T as *const u8, size) };
+        let header = core::mem::size_of::<Protocol>();
+
+        let out = self.node(T::TYPE, T::SUB_TYPE, length)?;
+        out[..size - header].copy_from_slice(&bytes[header..]);
+        out[size - header..].copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Append a Typed Node
+    ///
+    /// Append `node`. The node header of `node` is ignored, and instead
+    /// derived from the node type and size.
+    pub fn push<T: TypedNode>(&mut self, node: &T) -> Result<(), DevicePathBuilderError> {
+        self.push_with_data(node, &[])
+    }
+
+    /// Append a File Path Node
+    ///
+    /// Append a file path media node for `path`, encoded as NUL-terminated
+    /// UCS-2.
+    pub fn push_file_path(&mut self, path: &str) -> Result<(), DevicePathBuilderError> {
+        if path.is_empty() || path.chars().any(|c| c == '\0' || c.len_utf16() != 1) {
+            return Err(DevicePathBuilderError::FilePath);
+        }
+
+        let length = path
+            .chars()
+            .count()
+            .checked_add(1)
+            .and_then(|v| v.checked_mul(2))
+            .and_then(|v| v.checked_add(core::mem::size_of::<Protocol>()))
+            .ok_or(DevicePathBuilderError::NodeSize)?;
+        let data = self.node(TYPE_MEDIA, Media::SUBTYPE_FILE_PATH, length)?;
+        for (c, v) in path.chars().chain(Some('\0')).zip(data.chunks_exact_mut(2)) {
+            v.copy_from_slice(&(c as u16).to_le_bytes());
+        }
+        Ok(())
+    }
+
+    /// Terminate the Current Instance
+    ///
+    /// Append an end-of-instance node, so following nodes form a new
+    /// instance of a multi-instance device path.
+    pub fn end_instance(&mut self) -> Result<(), DevicePathBuilderError> {
+        if self.len == self.instance {
+            return Err(DevicePathBuilderError::EmptyInstance);
+        }
+
+        self.node(TYPE_END, End::SUBTYPE_INSTANCE, core::mem::size_of::<Protocol>())?;
+        self.instance = self.len;
+        Ok(())
+    }
+
+    /// Finish the Device Path
+    ///
+    /// Append the end-of-entire-path node and return the device path. Space
+    /// for the end node was reserved when appending nodes, so this only fails
+    /// if an empty storage is too small for the end node itself, or if the
+    /// last instance was terminated via `end_instance()` without any nodes
+    /// following it.
+    pub fn finish(mut self) -> Result<B::Output, DevicePathBuilderError> {
+        if self.len > 0 && self.len == self.instance {
+            return Err(DevicePathBuilderError::EmptyInstance);
+        }
+
+        self.node(TYPE_END, End::SUBTYPE_ENTIRE, core::mem::size_of::<Protocol>())?;
+        Ok(self.storage.finish(self.len))
+    }
+}
+
+impl core::fmt::Display for DevicePathBuilderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            DevicePathBuilderError::NodeSize => write!(f, "invalid device path node size"),
+            DevicePathBuilderError::FilePath => write!(f, "invalid device path file path"),
+            DevicePathBuilderError::EmptyInstance => write!(f, "empty device path instance"),
+            DevicePathBuilderError::Overflow => write!(f, "device path size overflow"),
+            DevicePathBuilderError::BufferTooSmall(v) => {
+                write!(f, "buffer too small, {} bytes required", v)
+            }
+        }
+    }
+}
+
+impl core::error::Error for DevicePathBuilderError {}
+
+//
+// Device Path Text Representation
+//
+// The specification defines a text representation of device paths (see the chapter on the
+// device path to text protocol). Nodes are separated by `/`, instances by `,`, and each node is
+// written as `Keyword(arg,...)`, except for file path nodes which are written as the path itself.
+// Nodes without a dedicated keyword, or with contents the keyword form cannot express, use the
+// generic forms (e.g., `Msg(subtype,data)` or `Path(type,subtype,data)`), which carry the raw
+// node data in hexadecimal. Hence, the full text form converts back to the identical binary
+// device path. The display-only form omits some details and is meant for humans only.
+//
+
+/// Device Path Text Errors
+///
+/// This is returned when parsing the text representation of a device path
+/// fails. The builder is left unmodified in that case.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum DevicePathTextError {
+    /// A node is empty, or parentheses or quotes are unbalanced.
+    Syntax,
+    /// A node keyword is unknown.
+    Keyword,
+    /// An argument is missing, superfluous, malformed, or out of range.
+    Argument,
+    /// The node could not be appended to the device path.
+    Build(DevicePathBuilderError),
+}
+
+/// Device Path Text Formatter
+///
+/// This formats a device path in its text representation. See
+/// `DevicePath::to_text()`.
+#[derive(Clone, Copy, Debug)]
+pub struct DevicePathText<'a> {
+    path: DevicePath<'a>,
+    display_only: bool,
+}
+
+/// Device Path Node Text Formatter
+///
+/// This formats a single device path node in its text representation. See
+/// `RawNode::to_text()`.
+#[derive(Clone, Copy, Debug)]
+pub struct NodeText<'a> {
+    node: RawNode<'a>,
+    display_only: bool,
+}
+
+const TEXT_ATA_CHANNEL: &[(&str, u64)] = &[("Primary", 0), ("Secondary", 1)];
+const TEXT_ATA_DEVICE: &[(&str, u64)] = &[("Master", 0), ("Slave", 1)];
+const TEXT_IP_PROTOCOL: &[(&str, u64)] = &[("TCP", 6), ("UDP", 17)];
+const TEXT_IPV4_TYPE: &[(&str, u64)] = &[("DHCP", 0), ("Static", 1)];
+const TEXT_IPV6_ORIGIN: &[(&str, u64)] = &[
+    ("Static", 0),
+    ("StatelessAutoConfigure", 1),
+    ("StatefulAutoConfigure", 2),
+];
+const TEXT_UART_PARITY: &[(&str, u64)] =
+    &[("D", 0), ("N", 1), ("E", 2), ("O", 3), ("M", 4), ("S", 5)];
+const TEXT_UART_STOP_BITS: &[(&str, u64)] = &[("D", 0), ("1", 1), ("1.5", 2), ("2", 3)];
+const TEXT_UART_FLOW_CONTROL: &[(&str, u64)] = &[("None", 0), ("Hardware", 1), ("XonXoff", 2)];
+const TEXT_ISCSI_DIGEST: &[(&str, u64)] = &[("None", 0), ("CRC32C", 1)];
+const TEXT_ISCSI_AUTH: &[(&str, u64)] = &[("CHAP_BI", 0), ("None", 1), ("CHAP_UNI", 2)];
+const TEXT_ISCSI_PROTOCOL: &[(&str, u64)] = &[("TCP", 0)];
+const TEXT_REST_SERVICE: &[(&str, u64)] = &[("RedFish", 1), ("OData", 2)];
+const TEXT_REST_ACCESS_MODE: &[(&str, u64)] = &[("In-Band", 1), ("Out-of-Band", 2)];
+const TEXT_PARTITION_FORMAT: &[(&str, u64)] = &[("MBR", 1), ("GPT", 2)];
+const TEXT_BBS_DEVICE_TYPE: &[(&str, u64)] = &[
+    ("Floppy", 1),
+    ("HD", 2),
+    ("CDROM", 3),
+    ("PCMCIA", 4),
+    ("USB", 5),
+    ("Network", 6),
+];
+
+// ACPI nodes with PNP IDs that have their own keyword.
+const TEXT_ACPI_PNP: &[(&str, u64)] = &[
+    ("PciRoot", 0x0a03),
+    ("PcieRoot", 0x0a08),
+    ("Floppy", 0x0604),
+    ("Keyboard", 0x0301),
+    ("Serial", 0x0501),
+    ("ParallelPort", 0x0401),
+];
+
+const TEXT_RAM_DISK: &[(&str, crate::base::Guid)] = &[
+    ("VirtualDisk", VIRTUAL_DISK_GUID),
+    ("VirtualCD", VIRTUAL_CD_GUID),
+    ("PersistentVirtualDisk", PERSISTENT_VIRTUAL_DISK_GUID),
+    ("PersistentVirtualCD", PERSISTENT_VIRTUAL_CD_GUID),
+];
+
+// Vendor messaging nodes without vendor data that have their own keyword.
+const TEXT_VENDOR_MESSAGING: &[(&str, crate::base::Guid)] = &[
+    ("VenPcAnsi", PC_ANSI_GUID),
+    ("VenVt100", VT_100_GUID),
+    ("VenVt100Plus", VT_100_PLUS_GUID),
+    ("VenUtf8", VT_UTF8_GUID),
+    ("DebugPort", crate::protocols::debugport::PROTOCOL_GUID),
+];
+
+// Compressed EISA vendor ID of "PNP".
+const EISA_PNP: u32 = 0x41d0u32;
+
+// Check whether `c` can be part of a string argument. Quoted strings can
+// contain any printable ASCII character except for quotes, unquoted strings
+// must not contain any syntax characters either.
+fn text_safe(c: char, quoted: bool) -> bool {
+    (' '..='~').contains(&c) && c != '"' && (quoted || !"(),/".contains(c))
+}
+
+fn fmt_hex(f: &mut core::fmt::Formatter, data: &[u8]) -> core::fmt::Result {
+    for v in data {
+        write!(f, "{:02x}", v)?;
+    }
+    Ok(())
+}
+
+fn fmt_keyword(f: &mut core::fmt::Formatter, table: &[(&str, u64)], v: u64) -> core::fmt::Result {
+    match table.iter().find(|t| t.1 == v) {
+        Some(t) => f.write_str(t.0),
+        None => write!(f, "0x{:x}", v),
+    }
+}
+
+// Print an EISA ID as its vendor letters followed by the product ID, or as a
+// plain number if the vendor ID is not a valid compressed EISA vendor ID.
+fn fmt_eisa(f: &mut core::fmt::Formatter, id: u32) -> core::fmt::Result {
+    let letters = [(id >> 10) & 0x1f, (id >> 5) & 0x1f, id & 0x1f];
+    if id & 0x8000 != 0 || letters.iter().any(|v| !(1..=26).contains(v)) {
+        return write!(f, "0x{:x}", id);
+    }
+    for v in letters {
+        write!(f, "{}", (b'A' - 1 + v as u8) as char)?;
+    }
+    write!(f, "{:04X}", id >> 16)
+}
+
+// Print an IEEE EUI-64 as dash-separated bytes.
+fn fmt_eui64(f: &mut core::fmt::Formatter, eui: &[u8]) -> core::fmt::Result {
+    for (i, v) in eui.iter().enumerate() {
+        write!(f, "{}{:02x}", if i > 0 { "-" } else { "" }, v)?;
+    }
+    Ok(())
+}
+
+// Print the device topology of a SAS node as its SAS/SATA, location,
+// connection, and drive bay arguments. Topologies these cannot express are
+// printed as a number in place of SAS/SATA, followed by zeros.
+fn fmt_sas_topology(f: &mut core::fmt::Formatter, topology: u16) -> core::fmt::Result {
+    let kind = topology & 0x0f;
+    if topology == 0 {
+        f.write_str("NoTopology,0,0,0")
+    } else if (kind == 1 && topology & 0xff80 == 0) || (kind == 2 && topology & 0x80 == 0) {
+        write!(
+            f,
+            "{},{},{},",
+            if topology & 0x10 != 0 { "SATA" } else { "SAS" },
+            if topology & 0x20 != 0 { "External" } else { "Internal" },
+            if topology & 0x40 != 0 { "Expanded" } else { "Direct" },
+        )?;
+        match kind {
+            1 => f.write_str("0"),
+            _ => write!(f, "0x{:x}", (topology >> 8) + 1),
+        }
+    } else {
+        write!(f, "0x{:x},0,0,0", topology)
+    }
+}
+
+// Print an IP address, optionally followed by a port. IPv6 addresses are
+// enclosed in brackets if a port follows.
+fn fmt_ip_port(
+    f: &mut core::fmt::Formatter,
+    addr: core::net::IpAddr,
+    port: u16,
+) -> core::fmt::Result {
+    match (addr, port) {
+        (_, 0) => write!(f, "{}", addr),
+        (core::net::IpAddr::V4(v), _) => write!(f, "{}:{}", v, port),
+        (core::net::IpAddr::V6(v), _) => write!(f, "[{}]:{}", v, port),
+    }
+}
+
+// Return the string in `data` if it is printable ASCII without NULs and can
+// be used as string argument.
+fn text_ascii(data: &[u8], quoted: bool) -> Option<&str> {
+    let s = core::str::from_utf8(data).ok()?;
+    s.chars().all(|c| text_safe(c, quoted)).then_some(s)
+}
+
+// Return the text of a file path node, if it can be represented as such. The
+// path must be a non-empty, NUL-terminated UCS-2 string without characters
+// that are part of the text syntax. Surrogate pairs are not UCS-2, and would
+// be rejected when parsed back.
+fn text_file_path(data: &[u8]) -> Option<impl Iterator<Item = char> + '_> {
+    let (path, rest) = split_nul_ucs2(data)?;
+    let valid = rest.is_empty()
+        && !path.is_empty()
+        && core::char::decode_utf16(ucs2_units(path)).all(|c| {
+            c.is_ok_and(|c| c.len_utf16() == 1 && !c.is_control() && !"(),/\"".contains(c))
+        });
+    valid.then(|| core::char::decode_utf16(ucs2_units(path)).map(|c| c.unwrap_or('?')))
+}
+
+fn ucs2_units(data: &[u8]) -> impl Iterator<Item = u16> + Clone + '_ {
+    data.chunks_exact(2).map(|v| u16::from_le_bytes([v[0], v[1]]))
+}
+
+// Split little-endian UCS-2 data at the first NUL character, returning the
+// string without the NUL and the data following it.
+fn split_nul_ucs2(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let nul = data.chunks_exact(2).position(|v| v == [0, 0])?;
+    Some((&data[..nul * 2], &data[(nul + 1) * 2..]))
+}
+
+// Split ASCII data at the first NUL character, returning the string without
+// the NUL and the data following it.
+fn split_nul(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let nul = data.iter().position(|v| *v == 0)?;
+    Some((&data[..nul], &data[nul + 1..]))
+}
+
+// Print a node in its generic form, carrying the raw node data.
+fn fmt_node_generic(f: &mut core::fmt::Formatter, node: RawNode<'_>) -> core::fmt::Result {
+    match node.r#type() {
+        TYPE_HARDWARE => write!(f, "HardwarePath({}", node.sub_type())?,
+        TYPE_ACPI => write!(f, "AcpiPath({}", node.sub_type())?,
+        TYPE_MESSAGING => write!(f, "Msg({}", node.sub_type())?,
+        TYPE_MEDIA => write!(f, "MediaPath({}", node.sub_type())?,
+        TYPE_BIOS => write!(f, "BbsPath({}", node.sub_type())?,
+        v => write!(f, "Path({},{}", v, node.sub_type())?,
+    }
+    if !node.data().is_empty() {
+        f.write_str(",")?;
+        fmt_hex(f, node.data())?;
+    }
+    f.write_str(")")
+}
+
+// Print a vendor node, with the vendor data in hexadecimal.
+fn fmt_node_vendor(
+    f: &mut core::fmt::Formatter,
+    keyword: &str,
+    guid: crate::base::Guid,
+    data: &[u8],
+) -> core::fmt::Result {
+    write!(f, "{}({}", keyword, guid)?;
+    if !data.is_empty() {
+        f.write_str(",")?;
+        fmt_hex(f, data)?;
+    }
+    f.write_str(")")
+}
+
+// Print a node in its text representation. Any node that cannot be
+// represented losslessly with its keyword falls back to the generic form.
+fn fmt_node(
+    f: &mut core::fmt::Formatter,
+    node: RawNode<'_>,
+    display_only: bool,
+) -> core::fmt::Result {
+    let guid = crate::base::Guid::from_bytes;
+
+    match node.node() {
+        Node::PciHardware(v) => write!(f, "Pci(0x{:x},0x{:x})", v.device, v.function),
+        Node::PccardHardware(v) => write!(f, "PcCard(0x{:x})", v.function_number),
+        Node::MmapHardware(v) => write!(
+            f,
+            "MemoryMapped(0x{:x},0x{:x},0x{:x})",
+            { v.memory_type },
+            { v.starting_address },
+            { v.ending_address },
+        ),
+        Node::VendorHardware(v, data) => fmt_node_vendor(f, "VenHw", v.vendor_guid, data),
+        Node::ControllerHardware(v) => write!(f, "Ctrl(0x{:x})", { v.controller_number }),
+        Node::BmcHardware(v) => {
+            write!(f, "BMC(0x{:x},0x{:x})", v.interface_type, { v.base_address })
+        }
+
+        Node::HidAcpi(v) => {
+            let (hid, uid) = ({ v.hid }, { v.uid });
+            let pnp = TEXT_ACPI_PNP
+                .iter()
+                .find(|t| hid & 0xffff == EISA_PNP && t.1 == (hid >> 16) as u64);
+            match pnp {
+                Some(t) => write!(f, "{}(0x{:x})", t.0, uid),
+                None => {
+                    f.write_str("Acpi(")?;
+                    fmt_eisa(f, hid)?;
+                    write!(f, ",0x{:x})", uid)
+                }
+            }
+        }
+        Node::ExpandedAcpi(v, data) => {
+            let strings = split_nul(data).and_then(|(hid, rest)| {
+                let (uid, rest) = split_nul(rest)?;
+                let (cid, rest) = split_nul(rest)?;
+                let ascii = |v| text_ascii(v, false);
+                rest.is_empty().then_some((ascii(hid)?, ascii(uid)?, ascii(cid)?))
+            });
+            match strings {
+                Some((hid, uid, cid)) => {
+                    f.write_str("AcpiEx(")?;
+                    fmt_eisa(f, v.hid)?;
+                    f.write_str(",")?;
+                    fmt_eisa(f, v.cid)?;
+                    write!(f, ",0x{:x},{},{},{})", { v.uid }, hid, cid, uid)
+                }
+                None => fmt_node_generic(f, node),
+            }
+        }
+        Node::AdrAcpi(v, data) if data.len() % 4 == 0 => {
+            write!(f, "AcpiAdr(0x{:x}", { v.adr })?;
+            for v in data.chunks_exact(4) {
+                write!(f, ",0x{:x}", u32::from_le_bytes([v[0], v[1], v[2], v[3]]))?;
+            }
+            f.write_str(")")
+        }
+
+        Node::AtapiMessaging(v) => {
+            f.write_str("Ata(")?;
+            fmt_keyword(f, TEXT_ATA_CHANNEL, v.primary_secondary as u64)?;
+            f.write_str(",")?;
+            fmt_keyword(f, TEXT_ATA_DEVICE, v.slave_master as u64)?;
+            write!(f, ",0x{:x})", { v.lun })
+        }
+        Node::ScsiMessaging(v) => write!(f, "Scsi(0x{:x},0x{:x})", { v.target_id }, { v.lun }),
+        Node::FibreChannelMessaging(v) if { v.reserved } == 0 => {
+            write!(f, "Fibre(0x{:x},0x{:x})", { v.world_wide_name }, { v.lun })
+        }
+        Node::FibreChannelExMessaging(v) if { v.reserved } == 0 => {
+            f.write_str("FibreEx(0x")?;
+            fmt_hex(f, &v.world_wide_name)?;
+            f.write_str(",0x")?;
+            fmt_hex(f, &v.lun)?;
+            f.write_str(")")
+        }
+        Node::Ieee1394Messaging(v) if { v.reserved } == 0 => {
+            write!(f, "I1394(0x{:016x})", { v.guid })
+        }
+        Node::UsbMessaging(v) => {
+            write!(f, "USB(0x{:x},0x{:x})", v.parent_port_number, v.interface_number)
+        }
+        Node::I2oMessaging(v) => write!(f, "I2O(0x{:x})", { v.tid }),
+        Node::InfinibandMessaging(v) => write!(
+            f,
+            "Infiniband(0x{:x},{},0x{:x},0x{:x},0x{:x})",
+            { v.resource_flags },
+            guid(&v.port_gid),
+            { v.service_id },
+            { v.target_port_id },
+            { v.device_id },
+        ),
+        Node::VendorMessaging(v, data) => {
+            let vendor = v.vendor_guid;
+            match TEXT_VENDOR_MESSAGING.iter().find(|t| t.1 == vendor) {
+                Some(t) if data.is_empty() => write!(f, "{}()", t.0),
+                _ => fmt_node_vendor(f, "VenMsg", vendor, data),
+            }
+        }
+        Node::UartFlowControlMessaging(v) => {
+            f.write_str("UartFlowCtrl(")?;
+            fmt_keyword(f, TEXT_UART_FLOW_CONTROL, { v.flow_control_map } as u64)?;
+            f.write_str(")")
+        }
+        Node::SasMessaging(v) => {
+            write!(
+                f,
+                "SAS(0x{:x},0x{:x},0x{:x},",
+                { v.sas_address },
+                { v.lun },
+                { v.relative_target_port },
+            )?;
+            fmt_sas_topology(f, v.device_topology)?;
+            write!(f, ",0x{:x})", { v.reserved })
+        }
+        Node::MacAddressMessaging(v) => {
+            let len = if v.if_type <= 1 { 6 } else { 32 };
+            if v.mac_address.addr[len..].iter().any(|v| *v != 0) {
+                return fmt_node_generic(f, node);
+            }
+            f.write_str("MAC(")?;
+            fmt_hex(f, &v.mac_address.addr[..len])?;
+            write!(f, ",0x{:x})", v.if_type)
+        }
+        Node::Ipv4Messaging(v) => {
+            let remote = core::net::IpAddr::V4(v.remote_ip_address.into());
+            f.write_str("IPv4(")?;
+            fmt_ip_port(f, remote, v.remote_port)?;
+            if !display_only {
+                f.write_str(",")?;
+                fmt_keyword(f, TEXT_IP_PROTOCOL, { v.protocol } as u64)?;
+                f.write_str(",")?;
+                // `Boolean` folds any non-zero value to `true`, so print the
+                // raw byte to keep the text lossless.
+                let offset = core::mem::offset_of!(Ipv4Messaging, static_ip_address);
+                let r#static = node.as_bytes()[offset];
+                fmt_keyword(f, TEXT_IPV4_TYPE, r#static as u64)?;
+                f.write_str(",")?;
+                let local = core::net::IpAddr::V4(v.local_ip_address.into());
+                fmt_ip_port(f, local, v.local_port)?;
+                write!(f, ",{},{}", v.gateway_ip_address, v.subnet_mask)?;
+            }
+            f.write_str(")")
+        }
+        Node::Ipv6Messaging(v) => {
+            let remote = core::net::IpAddr::V6(v.remote_ip_address.into());
+            f.write_str("IPv6(")?;
+            fmt_ip_port(f, remote, v.remote_port)?;
+            if !display_only {
+                f.write_str(",")?;
+                fmt_keyword(f, TEXT_IP_PROTOCOL, { v.protocol } as u64)?;
+                f.write_str(",")?;
+                fmt_keyword(f, TEXT_IPV6_ORIGIN, v.ip_address_origin as u64)?;
+                f.write_str(",")?;
+                let local = core::net::IpAddr::V6(v.local_ip_address.into());
+                fmt_ip_port(f, local, v.local_port)?;
+                write!(f, ",0x{:x},{}", v.prefix_length, v.gateway_ip_address)?;
+            }
+            f.write_str(")")
+        }
+        Node::UartMessaging(v) if { v.reserved } == 0 => {
+            write!(f, "Uart({},{},", { v.baud_rate }, v.data_bits)?;
+            fmt_keyword(f, TEXT_UART_PARITY, v.parity as u64)?;
+            f.write_str(",")?;
+            fmt_keyword(f, TEXT_UART_STOP_BITS, v.stop_bits as u64)?;
+            f.write_str(")")
+        }
+        Node::UsbClassMessaging(v) => write!(
+            f,
+            "UsbClass(0x{:x},0x{:x},0x{:x},0x{:x},0x{:x})",
+            { v.vendor_id },
+            { v.product_id },
+            v.device_class,
+            v.device_subclass,
+            v.device_protocol,
+        ),
+        Node::UsbWwidMessaging(v, data) => {
+            let serial = core::char::decode_utf16(ucs2_units(data));
+            let valid = serial.clone().all(|c| c.is_ok_and(|c| text_safe(c, true)));
+            if data.len() % 2 != 0 || !valid {
+                return fmt_node_generic(f, node);
+            }
+            write!(
+                f,
+                "UsbWwid(0x{:x},0x{:x},0x{:x},\"",
+                { v.device_vendor_id },
+                { v.device_product_id },
+                { v.interface_number },
+            )?;
+            for c in serial {
+                write!(f, "{}", c.unwrap_or('?'))?;
+            }
+            f.write_str("\")")
+        }
+        Node::DeviceLogicalUnitMessaging(v) => write!(f, "Unit(0x{:x})", v.lun),
+        Node::SataMessaging(v) => write!(
+            f,
+            "Sata(0x{:x},0x{:x},0x{:x})",
+            { v.hba_port_number },
+            { v.port_multiplier_port_number },
+            { v.lun },
+        ),
+        Node::VlanMessaging(v) => write!(f, "Vlan({})", { v.vlan_id }),
+        Node::IscsiMessaging(v, data) => {
+            let options = { v.options };
+            let known = IscsiMessaging::OPTION_HEADER_DIGEST_CRC32C
+                | IscsiMessaging::OPTION_DATA_DIGEST_CRC32C
+                | IscsiMessaging::OPTION_AUTH_NONE
+                | IscsiMessaging::OPTION_AUTH_CHAP_UNI;
+            let auth = (options >> 11) & 0x3;
+            match text_ascii(data, false) {
+                Some(name) if options & !known == 0 && auth != 0x3 => {
+                    write!(f, "iSCSI({},0x{:x},0x", name, { v.target_portal_group_tag })?;
+                    fmt_hex(f, &v.lun)?;
+                    f.write_str(",")?;
+                    fmt_keyword(f, TEXT_ISCSI_DIGEST, ((options >> 1) & 0x1) as u64)?;
+                    f.write_str(",")?;
+                    fmt_keyword(f, TEXT_ISCSI_DIGEST, ((options >> 3) & 0x1) as u64)?;
+                    f.write_str(",")?;
+                    fmt_keyword(f, TEXT_ISCSI_AUTH, auth as u64)?;
+                    f.write_str(",")?;
+                    fmt_keyword(f, TEXT_ISCSI_PROTOCOL, { v.protocol } as u64)?;
+                    f.write_str(")")
+                }
+                _ => fmt_node_generic(f, node),
+            }
+        }
+        Node::SasExMessaging(v) => {
+            f.write_str("SasEx(0x")?;
+            fmt_hex(f, &v.sas_address)?;
+            f.write_str(",0x")?;
+            fmt_hex(f, &v.lun)?;
+            write!(f, ",0x{:x},", { v.relative_target_port })?;
+            fmt_sas_topology(f, v.device_topology)?;
+            f.write_str(")")
+        }
+        Node::NvmeNamespaceMessaging(v) => {
+            write!(f, "NVMe(0x{:x},", { v.namespace_identifier })?;
+            fmt_eui64(f, &{ v.ieee_extended_unique_identifier }.to_be_bytes())?;
+            f.write_str(")")
+        }
+        Node::NvmeOfNamespaceMessaging(v, data) => {
+            let nqn = split_nul(data)
+                .filter(|v| v.1.is_empty())
+                .and_then(|v| text_ascii(v.0, false));
+            let (nidt, nid) = (v.namespace_identifier_type, v.namespace_identifier);
+            let valid = match nidt {
+                NvmeOfNamespaceMessaging::NAMESPACE_IDENTIFIER_TYPE_EUI64 => nid[8..] == [0; 8],
+                NvmeOfNamespaceMessaging::NAMESPACE_IDENTIFIER_TYPE_NGUID
+                | NvmeOfNamespaceMessaging::NAMESPACE_IDENTIFIER_TYPE_UUID => true,
+                _ => false,
+            };
+            match nqn {
+                Some(nqn) if valid => {
+                    write!(f, "NVMEoF({},", nqn)?;
+                    match nidt {
+                        NvmeOfNamespaceMessaging::NAMESPACE_IDENTIFIER_TYPE_EUI64 => {
+                            fmt_eui64(f, &nid[..8])?
+                        }
+                        NvmeOfNamespaceMessaging::NAMESPACE_IDENTIFIER_TYPE_NGUID => {
+                            fmt_hex(f, &nid)?
+                        }
+                        _ => {
+                            for (i, v) in nid.iter().enumerate() {
+                                let dash = matches!(i, 4 | 6 | 8 | 10);
+                                write!(f, "{}{:02x}", if dash { "-" } else { "" }, v)?;
+                            }
+                        }
+                    }
+                    f.write_str(")")
+                }
+                _ => fmt_node_generic(f, node),
+            }
+        }
+        Node::UriMessaging(_, data) => match core::str::from_utf8(data) {
+            Ok(uri) if uri.chars().all(|c| (' '..='~').contains(&c) && !"()\"".contains(c)) => {
+                write!(f, "Uri({})", uri)
+            }
+            _ => fmt_node_generic(f, node),
+        },
+        Node::UfsMessaging(v) => write!(f, "UFS(0x{:x},0x{:x})", v.pun, v.lun),
+        Node::SdMessaging(v) => write!(f, "SD(0x{:x})", v.slot_number),
+        Node::EmmcMessaging(v) => write!(f, "eMMC(0x{:x})", v.slot_number),
+        Node::BluetoothMessaging(v) => {
+            let mut addr = v.bd_addr;
+            addr.reverse();
+            f.write_str("Bluetooth(")?;
+            fmt_hex(f, &addr)?;
+            f.write_str(")")
+        }
+        Node::BluetoothLeMessaging(v) => {
+            let mut addr = v.bd_addr;
+            addr.reverse();
+            f.write_str("BluetoothLE(")?;
+            fmt_hex(f, &addr)?;
+            write!(f, ",0x{:x})", v.address_type)
+        }
+        Node::WifiMessaging(v) => {
+            let (ssid, rest) = split_nul(&v.ssid).unwrap_or((&v.ssid, &[]));
+            match text_ascii(ssid, false) {
+                Some(ssid) if rest.iter().all(|v| *v == 0) => write!(f, "Wi-Fi({})", ssid),
+                _ => fmt_node_generic(f, node),
+            }
+        }
+        Node::DnsMessaging(v, data)
+            if v.is_ipv6 <= 1
+                && !data.is_empty()
+                && data.len() % 16 == 0
+                && (v.is_ipv6 == 1 || data.chunks_exact(16).all(|v| v[4..] == [0; 12])) =>
+        {
+            f.write_str("Dns(")?;
+            for (i, a) in data.chunks_exact(16).enumerate() {
+                f.write_str(if i > 0 { "," } else { "" })?;
+                let mut addr = [0u8; 16];
+                addr.copy_from_slice(a);
+                match v.is_ipv6 {
+                    0 => write!(f, "{}", core::net::Ipv4Addr::new(a[0], a[1], a[2], a[3]))?,
+                    _ => write!(f, "{}", core::net::Ipv6Addr::from(addr))?,
+                }
+            }
+            f.write_str(")")
+        }
+        Node::NvdimmNamespaceMessaging(v) => write!(f, "NVDIMM({})", { v.uuid }),
+        Node::RestServiceMessaging(v, []) => {
+            f.write_str("RestService(")?;
+            fmt_keyword(f, TEXT_REST_SERVICE, v.rest_service as u64)?;
+            f.write_str(",")?;
+            fmt_keyword(f, TEXT_REST_ACCESS_MODE, v.access_mode as u64)?;
+            f.write_str(")")
+        }
+
+        Node::HardDriveMedia(v) => {
+            let signature = v.partition_signature;
+            let format = (v.partition_format, v.signature_type);
+            let mbr = format
+                == (HardDriveMedia::PARTITION_FORMAT_MBR, HardDriveMedia::SIGNATURE_TYPE_MBR)
+                && signature[4..] == [0; 12];
+            let gpt = format
+                == (HardDriveMedia::PARTITION_FORMAT_GPT, HardDriveMedia::SIGNATURE_TYPE_GUID);
+            if !mbr && !gpt {
+                return fmt_node_generic(f, node);
+            }
+
+            write!(f, "HD({},", { v.partition_number })?;
+            if mbr {
+                let s = &signature;
+                write!(f, "MBR,0x{:08x}", u32::from_le_bytes([s[0], s[1], s[2], s[3]]))?;
+            } else {
+                write!(f, "GPT,{}", guid(&signature))?;
+            }
+            if !display_only {
+                write!(f, ",0x{:x},0x{:x}", { v.partition_start }, { v.partition_size })?;
+            }
+            f.write_str(")")
+        }
+        Node::CdromMedia(v) => {
+            write!(f, "CDROM(0x{:x}", { v.boot_entry })?;
+            if !display_only {
+                write!(f, ",0x{:x},0x{:x}", { v.partition_start }, { v.partition_size })?;
+            }
+            f.write_str(")")
+        }
+        Node::VendorMedia(v, data) => fmt_node_vendor(f, "VenMedia", v.vendor_guid, data),
+        Node::FilePathMedia(_, data) => match text_file_path(data) {
+            Some(path) => {
+                for c in path {
+                    write!(f, "{}", c)?;
+                }
+                Ok(())
+            }
+            None => fmt_node_generic(f, node),
+        },
+        Node::MediaProtocolMedia(v) => write!(f, "Media({})", { v.protocol }),
+        Node::PiwgFirmwareFileMedia(v) => write!(f, "FvFile({})", { v.fv_file_name }),
+        Node::PiwgFirmwareVolumeMedia(v) => write!(f, "Fv({})", { v.fv_name }),
+        Node::RelativeOffsetRangeMedia(v) if { v.reserved } == 0 => {
+            write!(f, "Offset(0x{:x},0x{:x})", { v.starting_offset }, { v.ending_offset })
+        }
+        Node::RamDiskMedia(v) => {
+            let disk_type = { v.disk_type };
+            let range = ({ v.starting_address }, { v.ending_address }, { v.disk_instance });
+            match TEXT_RAM_DISK.iter().find(|t| t.1 == disk_type) {
+                Some(t) => write!(f, "{}(0x{:x},0x{:x},{})", t.0, range.0, range.1, range.2),
+                None => write!(
+                    f,
+                    "RamDisk(0x{:x},0x{:x},{},{})",
+                    range.0, range.1, range.2, disk_type,
+                ),
+            }
+        }
+
+        Node::BbsBios(v, data) => {
+            let description = split_nul(data)
+                .filter(|v| v.1.is_empty())
+                .and_then(|v| text_ascii(v.0, false));
+            match description {
+                Some(description) => {
+                    f.write_str("BBS(")?;
+                    fmt_keyword(f, TEXT_BBS_DEVICE_TYPE, { v.device_type } as u64)?;
+                    write!(f, ",{},0x{:x})", description, { v.status_flag })
+                }
+                None => fmt_node_generic(f, node),
+            }
+        }
+
+        _ => fmt_node_generic(f, node),
+    }
+}
+
+// Find the first of `separators` in `s` that is neither enclosed in
+// parentheses nor in quotes, and split `s` at it.
+fn text_split<'t>(
+    s: &'t str,
+    separators: &str,
+) -> Result<Option<(&'t str, char, &'t str)>, DevicePathTextError> {
+    let mut depth = 0usize;
+    let mut quoted = false;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            _ if quoted => {}
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1).ok_or(DevicePathTextError::Syntax)?,
+            _ if depth == 0 && separators.contains(c) => {
+                return Ok(Some((&s[..i], c, &s[i + c.len_utf8()..])));
+            }
+            _ => {}
+        }
+    }
+
+    if depth > 0 || quoted {
+        Err(DevicePathTextError::Syntax)
+    } else {
+        Ok(None)
+    }
+}
+
+// Parse a number in the given radix, rejecting signs and empty strings.
+fn text_radix(digits: &str, radix: u32, max: u64) -> Result<u64, DevicePathTextError> {
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(DevicePathTextError::Argument);
+    }
+    u64::from_str_radix(digits, radix)
+        .ok()
+        .filter(|v| *v <= max)
+        .ok_or(DevicePathTextError::Argument)
+}
+
+// Parse a decimal or hexadecimal (`0x` prefixed) number no larger than `max`.
+fn text_num(s: &str, max: u64) -> Result<u64, DevicePathTextError> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(v) => text_radix(v, 16, max),
+        None => text_radix(s, 10, max),
+    }
+}
+
+fn text_keyword(s: &str, table: &[(&str, u64)], max: u64) -> Result<u64, DevicePathTextError> {
+    match table.iter().find(|t| t.0 == s) {
+        Some(t) => Ok(t.1),
+        None => text_num(s, max),
+    }
+}
+
+// Parse an EISA ID as printed by `fmt_eisa()`.
+fn text_eisa(s: &str) -> Result<u32, DevicePathTextError> {
+    let b = s.as_bytes();
+    if b.len() == 7 && b[..3].iter().all(|v| v.is_ascii_uppercase()) {
+        let vendor = b[..3].iter().fold(0, |acc, v| (acc << 5) | (v - b'A' + 1) as u32);
+        let product = text_radix(&s[3..], 16, 0xffff)? as u32;
+        Ok(vendor | (product << 16))
+    } else {
+        Ok(text_num(s, u32::MAX as u64)? as u32)
+    }
+}
+
+// Parse hexadecimal data, optionally `0x` prefixed, into `out` and return
+// the number of bytes.
+fn text_bytes(s: &str, out: &mut [u8]) -> Result<usize, DevicePathTextError> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if s.len() % 2 != 0 || s.len() / 2 > out.len() || !s.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(DevicePathTextError::Argument);
+    }
+    text_write_hex(s, out);
+    Ok(s.len() / 2)
+}
+
+// Write the validated hexadecimal data `s` into `out`.
+fn text_write_hex(s: &str, out: &mut [u8]) {
+    for (v, o) in s.as_bytes().chunks_exact(2).zip(out.iter_mut()) {
+        let digit = |c: u8| (c as char).to_digit(16).unwrap_or(0) as u8;
+        *o = (digit(v[0]) << 4) | digit(v[1]);
+    }
+}
+
+fn text_string(s: &str, quoted: bool) -> Result<&str, DevicePathTextError> {
+    let s = match quoted {
+        true => s
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .ok_or(DevicePathTextError::Argument)?,
+        false => s,
+    };
+    match s.chars().all(|c| text_safe(c, quoted)) {
+        true => Ok(s),
+        false => Err(DevicePathTextError::Argument),
+    }
+}
+
+// Parse an IEEE EUI-64 as printed by `fmt_eui64()`.
+fn text_eui64(s: &str) -> Result<[u8; 8], DevicePathTextError> {
+    let mut eui = [0; 8];
+    let mut parts = s.split('-');
+    for v in eui.iter_mut() {
+        let part = parts.next().filter(|v| v.len() == 2);
+        *v = text_radix(part.ok_or(DevicePathTextError::Argument)?, 16, u8::MAX as u64)? as u8;
+    }
+    match parts.next() {
+        Some(_) => Err(DevicePathTextError::Argument),
+        None => Ok(eui),
+    }
+}
+
+// Parse the namespace identifier of an `NVMEoF()` node, returning its type
+// and value. It is either an IEEE EUI-64, an NGUID as 32 hexadecimal digits,
+// or a UUID.
+fn text_nid(s: &str) -> Result<(u8, [u8; 16]), DevicePathTextError> {
+    let mut nid = [0; 16];
+    let parts: [usize; 5] = [8, 4, 4, 4, 12];
+    if s.len() == 32 {
+        match text_bytes(s, &mut nid)? {
+            16 => Ok((NvmeOfNamespaceMessaging::NAMESPACE_IDENTIFIER_TYPE_NGUID, nid)),
+            _ => Err(DevicePathTextError::Argument),
+        }
+    } else if s.len() == 36 && s.split('-').map(str::len).eq(parts) {
+        let mut at = 0;
+        for part in s.split('-') {
+            if part.starts_with("0x") {
+                return Err(DevicePathTextError::Argument);
+            }
+            at += text_bytes(part, &mut nid[at..])?;
+        }
+        Ok((NvmeOfNamespaceMessaging::NAMESPACE_IDENTIFIER_TYPE_UUID, nid))
+    } else {
+        nid[..8].copy_from_slice(&text_eui64(s)?);
+        Ok((NvmeOfNamespaceMessaging::NAMESPACE_IDENTIFIER_TYPE_EUI64, nid))
+    }
+}
+
+fn text_ipv4_port(s: &str) -> Result<([u8; 4], u16), DevicePathTextError> {
+    let (addr, port) = match s.split_once(':') {
+        Some((addr, port)) => (addr, text_num(port, u16::MAX as u64)? as u16),
+        None => (s, 0),
+    };
+    let addr: core::net::Ipv4Addr = addr.parse().map_err(|_| DevicePathTextError::Argument)?;
+    Ok((addr.octets(), port))
+}
+
+fn text_ipv6_port(s: &str) -> Result<([u8; 16], u16), DevicePathTextError> {
+    let (addr, port) = match s.strip_prefix('[').and_then(|v| v.split_once("]:")) {
+        Some((addr, port)) => (addr, text_num(port, u16::MAX as u64)? as u16),
+        None => (s, 0),
+    };
+    let addr: core::net::Ipv6Addr = addr.parse().map_err(|_| DevicePathTextError::Argument)?;
+    Ok((addr.octets(), port))
+}
+
+// Arguments of a node in text form, split at top-level commas. An empty
+// argument list is a single empty argument.
+#[derive(Clone)]
+struct TextArgs<'t> {
+    rest: Option<&'t str>,
+}
+
+impl<'t> TextArgs<'t> {
+    fn is_empty(&self) -> bool {
+        self.rest.is_none()
+    }
+
+    fn next(&mut self) -> Result<&'t str, DevicePathTextError> {
+        let s = self.rest.ok_or(DevicePathTextError::Argument)?;
+        match text_split(s, ",")? {
+            Some((v, _, rest)) => {
+                self.rest = Some(rest);
+                Ok(v)
+            }
+            None => {
+                self.rest = None;
+                Ok(s)
+            }
+        }
+    }
+
+    fn finish(&self) -> Result<(), DevicePathTextError> {
+        match self.rest {
+            Some(_) => Err(DevicePathTextError::Argument),
+            None => Ok(()),
+        }
+    }
+
+    fn num(&mut self, max: u64) -> Result<u64, DevicePathTextError> {
+        text_num(self.next()?, max)
+    }
+
+    fn keyword(&mut self, table: &[(&str, u64)], max: u64) -> Result<u64, DevicePathTextError> {
+        text_keyword(self.next()?, table, max)
+    }
+
+    fn guid(&mut self) -> Result<[u8; 16], DevicePathTextError> {
+        crate::base::Guid::parse(self.next()?)
+            .map(|v| *v.as_bytes())
+            .map_err(|_| DevicePathTextError::Argument)
+    }
+
+    fn hex(&mut self) -> Result<&'t str, DevicePathTextError> {
+        let s = self.next()?;
+        match s.len() % 2 == 0 && s.chars().all(|c| c.is_ascii_hexdigit()) {
+            true => Ok(s),
+            false => Err(DevicePathTextError::Argument),
+        }
+    }
+
+    // Parse the topology of a SAS node as printed by `fmt_sas_topology()`.
+    fn sas_topology(&mut self) -> Result<u64, DevicePathTextError> {
+        let (kind, location) = (self.next()?, self.next()?);
+        let (connect, bay) = (self.next()?, self.next()?);
+        let topology = match kind {
+            "SAS" | "SATA" => {
+                let location = match location {
+                    "Internal" => 0,
+                    "External" => 0x20,
+                    _ => return Err(DevicePathTextError::Argument),
+                };
+                let connect = match connect {
+                    "Direct" => 0,
+                    "Expanded" => 0x40,
+                    _ => return Err(DevicePathTextError::Argument),
+                };
+                let bay = match text_num(bay, 0x100)? {
+                    0 => 0x1,
+                    v => 0x2 | ((v - 1) << 8),
+                };
+                return Ok(if kind == "SATA" { 0x10 } else { 0 } | location | connect | bay);
+            }
+            "NoTopology" => 0,
+            _ => text_num(kind, u16::MAX as u64)?,
+        };
+        match (location, connect, bay) {
+            ("0", "0", "0") => Ok(topology),
+            _ => Err(DevicePathTextError::Argument),
+        }
+    }
+
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], DevicePathTextError> {
+        let mut v = [0; N];
+        match text_bytes(self.next()?, &mut v)? == N {
+            true => Ok(v),
+            false => Err(DevicePathTextError::Argument),
+        }
+    }
+}
+
+// Fixed-size part of a node parsed from text, in its binary little-endian
+// encoding.
+struct TextFields {
+    data: [u8; 64],
+    len: usize,
+}
+
+impl TextFields {
+    fn new() -> Self {
+        Self { data: [0; 64], len: 0 }
+    }
+
+    fn put(&mut self, v: &[u8]) {
+        self.data[self.len..self.len + v.len()].copy_from_slice(v);
+        self.len += v.len();
+    }
+
+    fn u8(&mut self, v: u64) {
+        self.put(&[v as u8]);
+    }
+
+    fn u16(&mut self, v: u64) {
+        self.put(&(v as u16).to_le_bytes());
+    }
+
+    fn u32(&mut self, v: u64) {
+        self.put(&(v as u32).to_le_bytes());
+    }
+
+    fn u64(&mut self, v: u64) {
+        self.put(&v.to_le_bytes());
+    }
+}
+
+// Variable-sized part of a node parsed from text.
+enum TextTail<'t> {
+    None,
+    Hex(&'t str),
+    Ascii(&'t str),
+    AsciiNul([&'t str; 3], usize),
+    Ucs2(&'t str),
+    Adr(TextArgs<'t>),
+    Dns(TextArgs<'t>, bool),
+}
+
+impl TextTail<'_> {
+    fn len(&self) -> usize {
+        match self {
+            TextTail::None => 0,
+            TextTail::Hex(v) => v.len() / 2,
+            TextTail::Ascii(v) => v.len(),
+            TextTail::AsciiNul(v, n) => v[..*n].iter().map(|v| v.len() + 1).sum(),
+            TextTail::Ucs2(v) => v.chars().count() * 2,
+            TextTail::Adr(v) => {
+                let mut args = v.clone();
+                let mut n = 0;
+                while args.next().is_ok() {
+                    n += 4;
+                }
+                n
+            }
+            TextTail::Dns(v, _) => {
+                let mut args = v.clone();
+                let mut n = 0;
+                while args.next().is_ok() {
+                    n += 16;
+                }
+                n
+            }
+        }
+    }
+
+    // Write the tail into `out`. The arguments were validated before.
+    fn write(&self, out: &mut [u8]) {
+        match self {
+            TextTail::None => {}
+            TextTail::Hex(v) => text_write_hex(v, out),
+            TextTail::Ascii(v) => out.copy_from_slice(v.as_bytes()),
+            TextTail::AsciiNul(v, n) => {
+                let mut at = 0;
+                for v in &v[..*n] {
+                    out[at..at + v.len()].copy_from_slice(v.as_bytes());
+                    out[at + v.len()] = 0;
+                    at += v.len() + 1;
+                }
+            }
+            TextTail::Ucs2(v) => {
+                for (c, o) in v.chars().zip(out.chunks_exact_mut(2)) {
+                    o.copy_from_slice(&(c as u16).to_le_bytes());
+                }
+            }
+            TextTail::Adr(v) => {
+                let mut args = v.clone();
+                for o in out.chunks_exact_mut(4) {
+                    let adr = args.num(u32::MAX as u64).unwrap_or(0) as u32;
+                    o.copy_from_slice(&adr.to_le_bytes());
+                }
+            }
+            TextTail::Dns(v, is_ipv6) => {
+                let mut args = v.clone();
+                for o in out.chunks_exact_mut(16) {
+                    match is_ipv6 {
+                        false => {
+                            let addr = text_ipv4_port(args.next().unwrap_or_default());
+                            o[..4].copy_from_slice(&addr.map(|v| v.0).unwrap_or_default());
+                        }
+                        true => {
+                            let addr = text_ipv6_port(args.next().unwrap_or_default());
+                            o.copy_from_slice(&addr.map(|v| v.0).unwrap_or_default());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for DevicePathTextError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            DevicePathTextError::Syntax => write!(f, "invalid device path text syntax"),
+            DevicePathTextError::Keyword => write!(f, "unknown device path node keyword"),
+            DevicePathTextError::Argument => write!(f, "invalid device path node argument"),
+            DevicePathTextError::Build(v) => v.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for DevicePathTextError {}
+
+impl From<DevicePathBuilderError> for DevicePathTextError {
+    fn from(v: DevicePathBuilderError) -> Self {
+        DevicePathTextError::Build(v)
+    }
+}
+
+impl core::fmt::Display for DevicePathText<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut separator = "";
+        for node in self.path.raw_nodes() {
+            if node.is_end_instance() {
+                f.write_str(",")?;
+                separator = "";
+            } else {
+                f.write_str(separator)?;
+                fmt_node(f, node, self.display_only)?;
+                separator = "/";
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for NodeText<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt_node(f, self.node, self.display_only)
+    }
+}
+
+impl<'a> DevicePath<'a> {
+    /// Format as Text
+    ///
+    /// Return a formatter that prints the device path in its text
+    /// representation. Unless `display_only` is set, the full form is
+    /// printed, which `DevicePathBuilder::push_text()` converts back to the
+    /// identical device path. The display-only form omits details of some
+    /// nodes and cannot necessarily be parsed.
+    ///
+    /// Two kinds of paths accepted by `parse()` cannot be built by
+    /// `DevicePathBuilder`, and thus their text does not parse back: paths
+    /// with empty instances, printed as an empty text between separators,
+    /// and paths with end nodes of non-standard sub-types, printed in the
+    /// generic form `Path(127,...)`.
+    pub fn to_text(&self, display_only: bool) -> DevicePathText<'a> {
+        DevicePathText { path: *self, display_only }
+    }
+}
+
+impl<'a> RawNode<'a> {
+    /// Format as Text
+    ///
+    /// Return a formatter that prints the node in its text representation.
+    /// See `DevicePath::to_text()`.
+    pub fn to_text(&self, display_only: bool) -> NodeText<'a> {
+        NodeText { node: *self, display_only }
+    }
+}
+
+impl<B: DevicePathStorage> DevicePathBuilder<B> {
+    // Append a node consisting of `fixed` followed by `tail`.
+    fn push_text_parts(
+        &mut self,
+        r#type: u8,
+        sub_type: u8,
+        fixed: &[u8],
+        tail: &TextTail<'_>,
+    ) -> Result<(), DevicePathBuilderError> {
+        let length = core::mem::size_of::<Protocol>()
+            .checked_add(fixed.len())
+            .and_then(|v| v.checked_add(tail.len()))
+            .ok_or(DevicePathBuilderError::NodeSize)?;
+        let data = self.node(r#type, sub_type, length)?;
+        data[..fixed.len()].copy_from_slice(fixed);
+        tail.write(&mut data[fixed.len()..]);
+        Ok(())
+    }
+
+    // Append a node given in its generic text form.
+    fn push_text_generic(
+        &mut self,
+        r#type: u8,
+        sub_type: u64,
+        mut args: TextArgs<'_>,
+    ) -> Result<(), DevicePathTextError> {
+        let data = if args.is_empty() { "" } else { args.hex()? };
+        args.finish()?;
+        if r#type == TYPE_END {
+            return Err(DevicePathTextError::Argument);
+        }
+        Ok(self.push_text_parts(r#type, sub_type as u8, &[], &TextTail::Hex(data))?)
+    }
+
+    // Append a single node given in text form.
+    fn push_text_node(&mut self, text: &str) -> Result<(), DevicePathTextError> {
+        let Some((name, inner)) = text.split_once('(') else {
+            if text.is_empty() || text.chars().any(|c| c.is_control() || ")\"".contains(c)) {
+                return Err(DevicePathTextError::Syntax);
+            }
+            return self.push_file_path(text).map_err(|e| match e {
+                DevicePathBuilderError::FilePath => DevicePathTextError::Argument,
+                e => DevicePathTextError::Build(e),
+            });
+        };
+        let inner = inner.strip_suffix(')').ok_or(DevicePathTextError::Syntax)?;
+
+        if let Some(t) = TEXT_VENDOR_MESSAGING.iter().find(|t| t.0 == name) {
+            if !inner.is_empty() {
+                return Err(DevicePathTextError::Argument);
+            }
+            let (r#type, sub_type) = (TYPE_MESSAGING, Messaging::SUBTYPE_VENDOR);
+            return Ok(self.push_text_parts(r#type, sub_type, t.1.as_bytes(), &TextTail::None)?);
+        }
+
+        let mut args = TextArgs { rest: Some(inner) };
+        let mut fields = TextFields::new();
+        let mut tail = TextTail::None;
+        let f = &mut fields;
+        let a = &mut args;
+        let (u8max, u16max, u32max) = (u8::MAX as u64, u16::MAX as u64, u32::MAX as u64);
+
+        let (r#type, sub_type) = match name {
+            "Path" => {
+                let r#type = a.num(u8max)? as u8;
+                let sub_type = a.num(u8max)?;
+                return self.push_text_generic(r#type, sub_type, args);
+            }
+            "HardwarePath" => return self.push_text_generic(TYPE_HARDWARE, a.num(u8max)?, args),
+            "AcpiPath" => return self.push_text_generic(TYPE_ACPI, a.num(u8max)?, args),
+            "Msg" => return self.push_text_generic(TYPE_MESSAGING, a.num(u8max)?, args),
+            "MediaPath" => return self.push_text_generic(TYPE_MEDIA, a.num(u8max)?, args),
+            "BbsPath" => return self.push_text_generic(TYPE_BIOS, a.num(u8max)?, args),
+
+            "Pci" => {
+                let device = a.num(u8max)?;
+                f.u8(a.num(u8max)?);
+                f.u8(device);
+                (TYPE_HARDWARE, Hardware::SUBTYPE_PCI)
+            }
+            "PcCard" => {
+                f.u8(a.num(u8max)?);
+                (TYPE_HARDWARE, Hardware::SUBTYPE_PCCARD)
+            }
+            "MemoryMapped" => {
+                f.u32(a.num(u32max)?);
+                f.u64(a.num(u64::MAX)?);
+                f.u64(a.num(u64::MAX)?);
+                (TYPE_HARDWARE, Hardware::SUBTYPE_MMAP)
+            }
+            "VenHw" | "VenMsg" | "VenMedia" => {
+                f.put(&a.guid()?);
+                if !a.is_empty() {
+                    tail = TextTail::Hex(a.hex()?);
+                }
+                match name {
+                    "VenHw" => (TYPE_HARDWARE, Hardware::SUBTYPE_VENDOR),
+                    "VenMsg" => (TYPE_MESSAGING, Messaging::SUBTYPE_VENDOR),
+                    _ => (TYPE_MEDIA, Media::SUBTYPE_VENDOR),
+                }
+            }
+            "Ctrl" => {
+                f.u32(a.num(u32max)?);
+                (TYPE_HARDWARE, Hardware::SUBTYPE_CONTROLLER)
+            }
+            "BMC" => {
+                f.u8(a.num(u8max)?);
+                f.u64(a.num(u64::MAX)?);
+                (TYPE_HARDWARE, Hardware::SUBTYPE_BMC)
+            }
+
+            _ if TEXT_ACPI_PNP.iter().any(|t| t.0 == name) => {
+                let pnp = text_keyword(name, TEXT_ACPI_PNP, 0)?;
+                f.u32((pnp << 16) | EISA_PNP as u64);
+                f.u32(a.num(u32max)?);
+                (TYPE_ACPI, Acpi::SUBTYPE_ACPI)
+            }
+            "Acpi" => {
+                f.u32(text_eisa(a.next()?)? as u64);
+                f.u32(a.num(u32max)?);
+                (TYPE_ACPI, Acpi::SUBTYPE_ACPI)
+            }
+            "AcpiEx" => {
+                let hid = text_eisa(a.next()?)?;
+                let cid = text_eisa(a.next()?)?;
+                f.u32(hid as u64);
+                f.u32(a.num(u32max)?);
+                f.u32(cid as u64);
+                let hid = text_string(a.next()?, false)?;
+                let cid = text_string(a.next()?, false)?;
+                let uid = text_string(a.next()?, false)?;
+                tail = TextTail::AsciiNul([hid, uid, cid], 3);
+                (TYPE_ACPI, Acpi::SUBTYPE_EXPANDED)
+            }
+            "AcpiAdr" => {
+                f.u32(a.num(u32max)?);
+                let rest = a.clone();
+                while !a.is_empty() {
+                    a.num(u32max)?;
+                }
+                tail = TextTail::Adr(rest);
+                (TYPE_ACPI, Acpi::SUBTYPE_ADR)
+            }
+
+            "Ata" => {
+                f.u8(a.keyword(TEXT_ATA_CHANNEL, u8max)?);
+                f.u8(a.keyword(TEXT_ATA_DEVICE, u8max)?);
+                f.u16(a.num(u16max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_ATAPI)
+            }
+            "Scsi" => {
+                f.u16(a.num(u16max)?);
+                f.u16(a.num(u16max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_SCSI)
+            }
+            "Fibre" => {
+                f.u32(0);
+                f.u64(a.num(u64::MAX)?);
+                f.u64(a.num(u64::MAX)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_FIBRE_CHANNEL)
+            }
+            "FibreEx" => {
+                f.u32(0);
+                f.put(&a.bytes::<8>()?);
+                f.put(&a.bytes::<8>()?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_FIBRE_CHANNEL_EX)
+            }
+            "I1394" => {
+                f.u32(0);
+                f.u64(a.num(u64::MAX)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_1394)
+            }
+            "USB" => {
+                f.u8(a.num(u8max)?);
+                f.u8(a.num(u8max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_USB)
+            }
+            "I2O" => {
+                f.u32(a.num(u32max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_I2O)
+            }
+            "Infiniband" => {
+                f.u32(a.num(u32max)?);
+                f.put(&a.guid()?);
+                f.u64(a.num(u64::MAX)?);
+                f.u64(a.num(u64::MAX)?);
+                f.u64(a.num(u64::MAX)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_INFINIBAND)
+            }
+            "UartFlowCtrl" => {
+                f.put(UART_FLOW_CONTROL_GUID.as_bytes());
+                f.u32(a.keyword(TEXT_UART_FLOW_CONTROL, u32max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_VENDOR)
+            }
+            "SAS" => {
+                let address = a.num(u64::MAX)?;
+                let lun = a.num(u64::MAX)?;
+                let port = a.num(u16max)?;
+                let topology = a.sas_topology()?;
+                f.put(SAS_GUID.as_bytes());
+                f.u32(a.num(u32max)?);
+                f.u64(address);
+                f.u64(lun);
+                f.u16(topology);
+                f.u16(port);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_VENDOR)
+            }
+            "MAC" => {
+                let mut addr = [0; 32];
+                text_bytes(a.next()?, &mut addr)?;
+                f.put(&addr);
+                f.u8(a.num(u8max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_MAC_ADDRESS)
+            }
+            "IPv4" => {
+                let (remote, remote_port) = text_ipv4_port(a.next()?)?;
+                let protocol = a.keyword(TEXT_IP_PROTOCOL, u16max)?;
+                let r#static = a.keyword(TEXT_IPV4_TYPE, u8max)?;
+                let (local, local_port) = text_ipv4_port(a.next()?)?;
+                f.put(&local);
+                f.put(&remote);
+                f.u16(local_port as u64);
+                f.u16(remote_port as u64);
+                f.u16(protocol);
+                f.u8(r#static);
+                f.put(&text_ipv4_port(a.next()?).map(|v| v.0)?);
+                f.put(&text_ipv4_port(a.next()?).map(|v| v.0)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_IPV4)
+            }
+            "IPv6" => {
+                let (remote, remote_port) = text_ipv6_port(a.next()?)?;
+                let protocol = a.keyword(TEXT_IP_PROTOCOL, u16max)?;
+                let origin = a.keyword(TEXT_IPV6_ORIGIN, u8max)?;
+                let (local, local_port) = text_ipv6_port(a.next()?)?;
+                f.put(&local);
+                f.put(&remote);
+                f.u16(local_port as u64);
+                f.u16(remote_port as u64);
+                f.u16(protocol);
+                f.u8(origin);
+                f.u8(a.num(u8max)?);
+                f.put(&text_ipv6_port(a.next()?).map(|v| v.0)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_IPV6)
+            }
+            "Uart" => {
+                f.u32(0);
+                f.u64(a.num(u64::MAX)?);
+                f.u8(a.num(u8max)?);
+                f.u8(a.keyword(TEXT_UART_PARITY, u8max)?);
+                f.u8(a.keyword(TEXT_UART_STOP_BITS, u8max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_UART)
+            }
+            "UsbClass" => {
+                f.u16(a.num(u16max)?);
+                f.u16(a.num(u16max)?);
+                f.u8(a.num(u8max)?);
+                f.u8(a.num(u8max)?);
+                f.u8(a.num(u8max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_USB_CLASS)
+            }
+            "UsbWwid" => {
+                let vendor_id = a.num(u16max)?;
+                let product_id = a.num(u16max)?;
+                f.u16(a.num(u16max)?);
+                f.u16(vendor_id);
+                f.u16(product_id);
+                tail = TextTail::Ucs2(text_string(a.next()?, true)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_USB_WWID)
+            }
+            "Unit" => {
+                f.u8(a.num(u8max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_DEVICE_LOGICAL_UNIT)
+            }
+            "Sata" => {
+                f.u16(a.num(u16max)?);
+                f.u16(a.num(u16max)?);
+                f.u16(a.num(u16max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_SATA)
+            }
+            "iSCSI" => {
+                let name = text_string(a.next()?, false)?;
+                let tag = a.num(u16max)?;
+                let lun = a.bytes::<8>()?;
+                let header_digest = a.keyword(TEXT_ISCSI_DIGEST, 1)?;
+                let data_digest = a.keyword(TEXT_ISCSI_DIGEST, 1)?;
+                let auth = a.keyword(TEXT_ISCSI_AUTH, 2)?;
+                f.u16(a.keyword(TEXT_ISCSI_PROTOCOL, u16max)?);
+                f.u16((header_digest << 1) | (data_digest << 3) | (auth << 11));
+                f.put(&lun);
+                f.u16(tag);
+                tail = TextTail::Ascii(name);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_ISCSI)
+            }
+            "Vlan" => {
+                f.u16(a.num(u16max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_VLAN)
+            }
+            "SasEx" => {
+                f.put(&a.bytes::<8>()?);
+                f.put(&a.bytes::<8>()?);
+                let port = a.num(u16max)?;
+                f.u16(a.sas_topology()?);
+                f.u16(port);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_SAS_EX)
+            }
+            "NVMe" => {
+                f.u32(a.num(u32max)?);
+                f.put(&u64::from_be_bytes(text_eui64(a.next()?)?).to_le_bytes());
+                (TYPE_MESSAGING, Messaging::SUBTYPE_NVME_NAMESPACE)
+            }
+            "Uri" => {
+                a.rest = None;
+                if !inner.chars().all(|c| (' '..='~').contains(&c)) {
+                    return Err(DevicePathTextError::Argument);
+                }
+                tail = TextTail::Ascii(inner);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_URI)
+            }
+            "UFS" => {
+                f.u8(a.num(u8max)?);
+                f.u8(a.num(u8max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_UFS)
+            }
+            "SD" => {
+                f.u8(a.num(u8max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_SD)
+            }
+            "eMMC" => {
+                f.u8(a.num(u8max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_EMMC)
+            }
+            "Bluetooth" | "BluetoothLE" => {
+                let mut addr = a.bytes::<6>()?;
+                addr.reverse();
+                f.put(&addr);
+                if name == "Bluetooth" {
+                    (TYPE_MESSAGING, Messaging::SUBTYPE_BLUETOOTH)
+                } else {
+                    f.u8(a.num(u8max)?);
+                    (TYPE_MESSAGING, Messaging::SUBTYPE_BLUETOOTH_LE)
+                }
+            }
+            "Wi-Fi" => {
+                a.rest = None;
+                let ssid = text_string(inner, false)?;
+                if ssid.len() > 32 {
+                    return Err(DevicePathTextError::Argument);
+                }
+                let mut v = [0; 32];
+                v[..ssid.len()].copy_from_slice(ssid.as_bytes());
+                f.put(&v);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_WIFI)
+            }
+            "Dns" => {
+                let rest = a.clone();
+                let is_ipv6 = text_ipv4_port(rest.clone().next()?).is_err();
+                while !a.is_empty() {
+                    let v = a.next()?;
+                    let valid = match is_ipv6 {
+                        false => v.parse::<core::net::Ipv4Addr>().is_ok(),
+                        true => v.parse::<core::net::Ipv6Addr>().is_ok(),
+                    };
+                    if !valid {
+                        return Err(DevicePathTextError::Argument);
+                    }
+                }
+                f.u8(is_ipv6 as u64);
+                tail = TextTail::Dns(rest, is_ipv6);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_DNS)
+            }
+            "NVDIMM" => {
+                f.put(&a.guid()?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_NVDIMM_NAMESPACE)
+            }
+            "RestService" => {
+                f.u8(a.keyword(TEXT_REST_SERVICE, u8max)?);
+                f.u8(a.keyword(TEXT_REST_ACCESS_MODE, u8max)?);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_REST_SERVICE)
+            }
+            "NVMEoF" => {
+                let nqn = text_string(a.next()?, false)?;
+                let (nidt, nid) = text_nid(a.next()?)?;
+                f.u8(nidt as u64);
+                f.put(&nid);
+                tail = TextTail::AsciiNul([nqn, "", ""], 1);
+                (TYPE_MESSAGING, Messaging::SUBTYPE_NVME_OF_NAMESPACE)
+            }
+
+            "HD" => {
+                let number = a.num(u32max)?;
+                let format = a.keyword(TEXT_PARTITION_FORMAT, u8max)?;
+                let mut signature = [0; 16];
+                let signature_type = match format as u8 {
+                    HardDriveMedia::PARTITION_FORMAT_MBR => {
+                        signature[..4].copy_from_slice(&(a.num(u32max)? as u32).to_le_bytes());
+                        HardDriveMedia::SIGNATURE_TYPE_MBR
+                    }
+                    HardDriveMedia::PARTITION_FORMAT_GPT => {
+                        signature = a.guid()?;
+                        HardDriveMedia::SIGNATURE_TYPE_GUID
+                    }
+                    _ => return Err(DevicePathTextError::Argument),
+                };
+                f.u32(number);
+                f.u64(a.num(u64::MAX)?);
+                f.u64(a.num(u64::MAX)?);
+                f.put(&signature);
+                f.u8(format);
+                f.u8(signature_type as u64);
+                (TYPE_MEDIA, Media::SUBTYPE_HARDDRIVE)
+            }
+            "CDROM" => {
+                f.u32(a.num(u32max)?);
+                f.u64(a.num(u64::MAX)?);
+                f.u64(a.num(u64::MAX)?);
+                (TYPE_MEDIA, Media::SUBTYPE_CDROM)
+            }
+            "Media" => {
+                f.put(&a.guid()?);
+                (TYPE_MEDIA, Media::SUBTYPE_MEDIA_PROTOCOL)
+            }
+            "FvFile" => {
+                f.put(&a.guid()?);
+                (TYPE_MEDIA, Media::SUBTYPE_PIWG_FIRMWARE_FILE)
+            }
+            "Fv" => {
+                f.put(&a.guid()?);
+                (TYPE_MEDIA, Media::SUBTYPE_PIWG_FIRMWARE_VOLUME)
+            }
+            "Offset" => {
+                f.u32(0);
+                f.u64(a.num(u64::MAX)?);
+                f.u64(a.num(u64::MAX)?);
+                (TYPE_MEDIA, Media::SUBTYPE_RELATIVE_OFFSET_RANGE)
+            }
+            "RamDisk" | "VirtualDisk" | "VirtualCD" | "PersistentVirtualDisk"
+            | "PersistentVirtualCD" => {
+                f.u64(a.num(u64::MAX)?);
+                f.u64(a.num(u64::MAX)?);
+                let instance = a.num(u16max)?;
+                let disk_type = match TEXT_RAM_DISK.iter().find(|t| t.0 == name) {
+                    Some(t) => *t.1.as_bytes(),
+                    None => a.guid()?,
+                };
+                f.put(&disk_type);
+                f.u16(instance);
+                (TYPE_MEDIA, Media::SUBTYPE_RAM_DISK)
+            }
+
+            "BBS" => {
+                f.u16(a.keyword(TEXT_BBS_DEVICE_TYPE, u16max)?);
+                let description = text_string(a.next()?, false)?;
+                f.u16(a.num(u16max)?);
+                tail = TextTail::AsciiNul([description, "", ""], 1);
+                (TYPE_BIOS, Bios::SUBTYPE_BBS)
+            }
+
+            _ => return Err(DevicePathTextError::Keyword),
+        };
+
+        args.finish()?;
+        Ok(self.push_text_parts(r#type, sub_type, &fields.data[..fields.len], &tail)?)
+    }
+
+    // Append all nodes of a device path given in text form.
+    fn push_text_nodes(&mut self, mut text: &str) -> Result<(), DevicePathTextError> {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        loop {
+            let (node, separator, rest) = text_split(text, "/,")?.unwrap_or((text, '\0', ""));
+            self.push_text_node(node)?;
+            match separator {
+                '/' => {}
+                ',' => self.end_instance()?,
+                _ => return Ok(()),
+            }
+            text = rest;
+        }
+    }
+
+    /// Append Nodes from Text
+    ///
+    /// Parse `text` as the text representation of a device path and append
+    /// its nodes. Instances separated by `,` are terminated with
+    /// end-of-instance nodes. This accepts the full text form as printed by
+    /// `DevicePath::to_text()`, including the generic node forms. An empty
+    /// text appends no nodes.
+    pub fn push_text(&mut self, text: &str) -> Result<(), DevicePathTextError> {
+        self.transaction(|b| b.push_text_nodes(text))
+    }
+}
+
+//
+// Device Path Utilities
+//
+// These provide the operations of the device path utilities protocol without relying on the
+// firmware. Operations that produce new device paths are provided by `DevicePathBuilder`, the
+// others work in-place on the validated views.
+//
+
+impl<'a> DevicePath<'a> {
+    /// Return the size of the device path in bytes, including the
+    /// terminating end-of-entire-path node.
+    pub fn size(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Check whether the device path has more than one instance.
+    pub fn is_multi_instance(&self) -> bool {
+        self.raw_nodes().any(|v| v.is_end_instance())
+    }
+
+    /// Split off the First Instance
+    ///
+    /// Return the first instance of the device path, and the device path
+    /// formed by the remaining instances, if any.
+    pub fn split_first_instance(&self) -> (DevicePathInstance<'a>, Option<DevicePath<'a>>) {
+        let mut size = 0;
+        for node in self.raw_nodes() {
+            if node.is_end_instance() {
+                let instance = DevicePathInstance { bytes: &self.bytes[..size] };
+                let rest = DevicePath { bytes: &self.bytes[size + node.bytes.len()..] };
+                return (instance, Some(rest));
+            }
+            size += node.bytes.len();
+        }
+        (DevicePathInstance { bytes: &self.bytes[..size] }, None)
+    }
+
+    /// Strip a Device Path Prefix
+    ///
+    /// If the nodes of `prefix` (excluding its end-of-entire-path node) are
+    /// the leading nodes of this device path, return the device path formed
+    /// by the remaining nodes. This is the matching performed by
+    /// `LocateDevicePath()`.
+    pub fn strip_prefix(&self, prefix: &DevicePath<'_>) -> Option<DevicePath<'a>> {
+        // Both paths are validated, so a byte-wise prefix always ends on a
+        // node boundary.
+        let prefix = &prefix.bytes[..prefix.bytes.len() - core::mem::size_of::<Protocol>()];
+        match self.bytes.starts_with(prefix) {
+            true => Some(DevicePath { bytes: &self.bytes[prefix.len()..] }),
+            false => None,
+        }
+    }
+
+    /// Check whether the leading nodes of this device path are the nodes of
+    /// `prefix`. See `strip_prefix()`.
+    pub fn starts_with(&self, prefix: &DevicePath<'_>) -> bool {
+        self.strip_prefix(prefix).is_some()
+    }
+}
+
+impl<'a> DevicePathInstance<'a> {
+    /// Return the size of the instance in bytes, excluding the end node.
+    pub fn size(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Split off the Last Node
+    ///
+    /// Return the instance without its last node, and the last node, or
+    /// `None` if the instance is empty.
+    pub fn split_last(&self) -> Option<(DevicePathInstance<'a>, RawNode<'a>)> {
+        let last = self.raw_nodes().last()?;
+        let size = self.bytes.len() - last.bytes.len();
+        Some((DevicePathInstance { bytes: &self.bytes[..size] }, last))
+    }
+
+    /// Check whether the leading nodes of this instance are the nodes of
+    /// `prefix`.
+    pub fn starts_with(&self, prefix: &DevicePathInstance<'_>) -> bool {
+        self.bytes.starts_with(prefix.bytes)
+    }
+}
+
+impl<B: DevicePathStorage> DevicePathBuilder<B> {
+    // Run `f`, and restore the builder if it fails.
+    fn transaction<E>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let (len, instance) = (self.len, self.instance);
+        let r = f(self);
+        if r.is_err() {
+            self.len = len;
+            self.instance = instance;
+        }
+        r
+    }
+
+    /// Append a Node
+    ///
+    /// Append a copy of `node`, as taken from another device path. An
+    /// end-of-instance node terminates the current instance, as by
+    /// `end_instance()`. End-of-entire-path nodes cannot be appended.
+    pub fn push_node(&mut self, node: RawNode<'_>) -> Result<(), DevicePathBuilderError> {
+        if node.is_end_instance() {
+            return self.end_instance();
+        }
+        self.push_raw(node.r#type(), node.sub_type(), node.data())
+    }
+
+    /// Append a Device Path
+    ///
+    /// Append all nodes of `path`, excluding its end-of-entire-path node. The
+    /// first instance of `path` is thus joined with the current instance.
+    pub fn push_path(&mut self, path: &DevicePath<'_>) -> Result<(), DevicePathBuilderError> {
+        self.transaction(|b| path.raw_nodes().try_for_each(|v| b.push_node(v)))
+    }
+
+    /// Append an Instance
+    ///
+    /// Append `instance` as a new instance. If the current instance is not
+    /// empty, it is terminated first.
+    pub fn push_instance(
+        &mut self,
+        instance: &DevicePathInstance<'_>,
+    ) -> Result<(), DevicePathBuilderError> {
+        self.transaction(|b| {
+            if b.len != b.instance {
+                b.end_instance()?;
+            }
+            instance.raw_nodes().try_for_each(|v| b.push_node(v))
+        })
+    }
+
+    /// Remove the Last Node
+    ///
+    /// Remove the last node appended, which might be an end-of-instance
+    /// node. Returns `false` if the builder is empty.
+    pub fn pop(&mut self) -> bool {
+        let len = self.len;
+        let mut offset = 0;
+        let mut instance = 0;
+        for node in (RawNodes { bytes: &self.storage.as_bytes()[..len] }) {
+            let next = offset + node.bytes.len();
+            if next == len {
+                self.len = offset;
+                self.instance = instance;
+                return true;
+            }
+            if node.is_end_instance() {
+                instance = next;
+            }
+            offset = next;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::mem;
+    use super::*;
+
+    // Device path nodes are byte-aligned, and the spec defines the size of
+    // each node. Verify our layouts match.
+    #[test]
+    fn layout() {
+        macro_rules! node {
+            ($t:ty, $size:expr) => {
+                assert_eq!(mem::align_of::<$t>(), 1, stringify!($t));
+                assert_eq!(mem::size_of::<$t>(), $size, stringify!($t));
+            };
+        }
+
+        node!(Protocol, 4);
+        node!(End, 4);
+
+        node!(PciHardware, 6);
+        node!(PccardHardware, 5);
+        node!(MmapHardware, 24);
+        node!(VendorHardware, 20);
+        node!(VendorHardware<crate::base::Guid, 3>, 23);
+        node!(ControllerHardware, 8);
+        node!(BmcHardware, 13);
+
+        node!(HidAcpi, 12);
+        node!(ExpandedAcpi, 16);
+        node!(ExpandedAcpi<3>, 19);
+        node!(AdrAcpi, 8);
+        node!(AdrAcpi<2>, 16);
+        node!(NvdimmAcpi, 8);
+
+        node!(AtapiMessaging, 8);
+        node!(ScsiMessaging, 8);
+        node!(FibreChannelMessaging, 24);
+        node!(FibreChannelExMessaging, 24);
+        node!(Ieee1394Messaging, 16);
+        node!(UsbMessaging, 6);
+        node!(I2oMessaging, 8);
+        node!(InfinibandMessaging, 48);
+        node!(VendorMessaging, 20);
+        node!(UartFlowControlMessaging, 24);
+        node!(SasMessaging, 44);
+        node!(MacAddressMessaging, 37);
+        node!(Ipv4Messaging, 27);
+        node!(Ipv6Messaging, 60);
+        node!(UartMessaging, 19);
+        node!(UsbClassMessaging, 11);
+        node!(UsbWwidMessaging, 10);
+        node!(UsbWwidMessaging<4>, 18);
+        node!(DeviceLogicalUnitMessaging, 5);
+        node!(SataMessaging, 10);
+        node!(IscsiMessaging, 18);
+        node!(VlanMessaging, 6);
+        node!(SasExMessaging, 24);
+        node!(NvmeNamespaceMessaging, 16);
+        node!(UriMessaging, 4);
+        node!(UfsMessaging, 6);
+        node!(SdMessaging, 5);
+        node!(BluetoothMessaging, 10);
+        node!(WifiMessaging, 36);
+        node!(EmmcMessaging, 5);
+        node!(BluetoothLeMessaging, 11);
+        node!(DnsMessaging, 5);
+        node!(DnsMessaging<crate::base::IpAddress, 2>, 37);
+        node!(NvdimmNamespaceMessaging, 20);
+        node!(RestServiceMessaging, 6);
+        node!(NvmeOfNamespaceMessaging, 21);
+
+        node!(HardDriveMedia, 42);
+        node!(CdromMedia, 24);
+        node!(VendorMedia, 20);
+        node!(FilePathMedia, 4);
+        node!(FilePathMedia<8>, 20);
+        node!(MediaProtocolMedia, 20);
+        node!(PiwgFirmwareFileMedia, 20);
+        node!(PiwgFirmwareVolumeMedia, 20);
+        node!(RelativeOffsetRangeMedia, 24);
+        node!(RamDiskMedia, 38);
+
+        node!(BbsBios, 8);
+    }
+
+    fn raw(r#type: u8, sub_type: u8, data: &[u8]) -> std::vec::Vec<u8> {
+        let len = (data.len() + 4) as u16;
+        let mut v = std::vec![r#type, sub_type];
+        v.extend_from_slice(&len.to_le_bytes());
+        v.extend_from_slice(data);
+        v
+    }
+
+    // Verify parsing and iteration of a multi-instance device path, as found
+    // in load options.
+    #[test]
+    fn parse() {
+        let mut path = std::vec::Vec::new();
+        path.extend(raw(TYPE_ACPI, Acpi::SUBTYPE_ACPI, &[0xd0, 0x41, 0x03, 0x0a, 0, 0, 0, 0]));
+        path.extend(raw(TYPE_HARDWARE, Hardware::SUBTYPE_PCI, &[0x00, 0x01]));
+        path.extend(raw(TYPE_MEDIA, Media::SUBTYPE_FILE_PATH, &[b'\\', 0, b'A', 0, 0, 0]));
+        path.extend(raw(TYPE_END, End::SUBTYPE_INSTANCE, &[]));
+        path.extend(raw(TYPE_HARDWARE, Hardware::SUBTYPE_PCI, &[0x00, 0x02, 0xff]));
+        path.extend(raw(0x42, 0x01, &[0x01]));
+        path.extend(raw(TYPE_END, End::SUBTYPE_ENTIRE, &[]));
+        let size = path.len();
+        path.extend_from_slice(&[0xde, 0xad]);
+
+        let dp = DevicePath::parse(&path).unwrap();
+        assert_eq!(dp.as_bytes(), &path[..size]);
+
+        let nodes: std::vec::Vec<_> = dp.nodes().collect();
+        assert_eq!(nodes.len(), 6);
+        match nodes[0] {
+            Node::HidAcpi(v) => assert_eq!(({ v.hid }, { v.uid }), (0x0a0341d0, 0)),
+            _ => panic!(),
+        }
+        match nodes[1] {
+            Node::PciHardware(v) => assert_eq!((v.function, v.device), (0, 1)),
+            _ => panic!(),
+        }
+        match nodes[2] {
+            Node::FilePathMedia(_, v) => assert_eq!(v, &[b'\\', 0, b'A', 0, 0, 0]),
+            _ => panic!(),
+        }
+        assert!(matches!(nodes[3], Node::EndInstance));
+        // Fixed-size nodes with a mismatching length are not decoded.
+        match nodes[4] {
+            Node::Unknown(v) => assert_eq!((v.length(), v.data()), (7, &[0x00, 0x02, 0xff][..])),
+            _ => panic!(),
+        }
+        match nodes[5] {
+            Node::Unknown(v) => assert_eq!((v.r#type(), v.sub_type()), (0x42, 0x01)),
+            _ => panic!(),
+        }
+
+        let instances: std::vec::Vec<_> = dp.instances().collect();
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0].as_bytes(), &path[..28]);
+        assert_eq!(instances[0].raw_nodes().count(), 3);
+        assert_eq!(instances[1].as_bytes(), &path[32..size - 4]);
+        assert_eq!(instances[1].raw_nodes().count(), 2);
+
+        // SAFETY: `path` is a valid device path.
+        let dp2 = unsafe { DevicePath::from_ptr(path.as_ptr() as *const Protocol).unwrap() };
+        assert_eq!(dp, dp2);
+
+        // An empty device path has a single empty instance.
+        let end = raw(TYPE_END, End::SUBTYPE_ENTIRE, &[]);
+        let dp = DevicePath::parse(&end).unwrap();
+        assert_eq!(dp.nodes().count(), 0);
+        assert_eq!(dp.instances().count(), 1);
+        assert!(dp.instances().next().unwrap().is_empty());
+
+        // Vendor nodes with well-known GUIDs are decoded accordingly.
+        let mut data = std::vec::Vec::from(&UART_FLOW_CONTROL_GUID.as_bytes()[..]);
+        data.extend_from_slice(&1u32.to_le_bytes());
+        let node = raw(TYPE_MESSAGING, Messaging::SUBTYPE_VENDOR, &data);
+        let node = RawNode { bytes: &node };
+        match node.node() {
+            Node::UartFlowControlMessaging(v) => assert_eq!({ v.flow_control_map }, 1),
+            _ => panic!(),
+        }
+    }
+
+    // Verify malformed device paths are rejected.
+    #[test]
+    fn parse_invalid() {
+        let pci = raw(TYPE_HARDWARE, Hardware::SUBTYPE_PCI, &[0, 0]);
+        let end = raw(TYPE_END, End::SUBTYPE_ENTIRE, &[]);
+
+        assert_eq!(DevicePath::parse(&[]), Err(DevicePathError::Truncated));
+        assert_eq!(DevicePath::parse(&end[..3]), Err(DevicePathError::Truncated));
+        assert_eq!(DevicePath::parse(&pci), Err(DevicePathError::Truncated));
+        assert_eq!(DevicePath::parse(&pci[..5]), Err(DevicePathError::Truncated));
+        assert_eq!(
+            DevicePath::parse(&[TYPE_HARDWARE, Hardware::SUBTYPE_PCI, 3, 0]),
+            Err(DevicePathError::NodeLength),
+        );
+        assert_eq!(
+            DevicePath::parse(&[TYPE_END, End::SUBTYPE_ENTIRE, 5, 0, 0]),
+            Err(DevicePathError::NodeLength),
+        );
+        // SAFETY: The invalid node is readable.
+        assert_eq!(
+            unsafe { DevicePath::from_ptr([1u8, 1, 0, 0].as_ptr() as *const Protocol) },
+            Err(DevicePathError::NodeLength),
+        );
+    }
+
+    // Verify device paths are built correctly and can be parsed again.
+    #[test]
+    fn build() {
+        let mut buffer = [0xffu8; 64];
+        let mut b = DevicePathBuilder::new(&mut buffer[..]);
+
+        b.push(&HidAcpi { header: HidAcpi::header(), hid: 0x0a0341d0, uid: 0 }).unwrap();
+        b.push(&PciHardware { header: PciHardware::header(), function: 0, device: 1 }).unwrap();
+        b.end_instance().unwrap();
+        assert_eq!(b.end_instance(), Err(DevicePathBuilderError::EmptyInstance));
+        let file = FilePathMedia { header: <FilePathMedia>::header(), path_name: [] };
+        b.push_with_data(&file, &[b'A', 0, 0, 0]).unwrap();
+        b.push_file_path("\\EFI").unwrap();
+        assert_eq!(b.len(), 12 + 6 + 4 + 8 + 14);
+        let path = b.finish().unwrap();
+        assert_eq!(path.len(), 48);
+
+        let dp = DevicePath::parse(path).unwrap();
+        assert_eq!(dp.as_bytes(), path);
+        assert_eq!(dp.instances().count(), 2);
+        let nodes: std::vec::Vec<_> = dp.raw_nodes().collect();
+        assert_eq!(nodes.len(), 5);
+        assert_eq!(nodes[0].as_bytes(), &[2, 1, 12, 0, 0xd0, 0x41, 0x03, 0x0a, 0, 0, 0, 0]);
+        assert_eq!(nodes[1].as_bytes(), &[1, 1, 6, 0, 0, 1]);
+        assert!(nodes[2].is_end_instance());
+        assert_eq!(nodes[3].as_bytes(), &[4, 4, 8, 0, b'A', 0, 0, 0]);
+        assert_eq!(nodes[4].data(), &[b'\\', 0, b'E', 0, b'F', 0, b'I', 0, 0, 0]);
+
+        // An empty builder yields an empty device path.
+        let mut buffer = [0u8; 4];
+        let path = DevicePathBuilder::new(&mut buffer[..]).finish().unwrap();
+        assert_eq!(path, &[TYPE_END, End::SUBTYPE_ENTIRE, 4, 0]);
+    }
+
+    // Verify invalid nodes and short buffers are rejected without modifying
+    // the device path.
+    #[test]
+    fn build_invalid() {
+        let mut buffer = [0u8; 15];
+        let mut b = DevicePathBuilder::new(&mut buffer[..]);
+
+        b.push_raw(TYPE_HARDWARE, Hardware::SUBTYPE_PCI, &[0, 0]).unwrap();
+        assert_eq!(
+            b.push_raw(TYPE_HARDWARE, Hardware::SUBTYPE_PCI, &[0, 0]),
+            Err(DevicePathBuilderError::BufferTooSmall(16)),
+        );
+        assert_eq!(
+            b.push_raw(TYPE_END, End::SUBTYPE_INSTANCE, &[]),
+            Err(DevicePathBuilderError::NodeSize),
+        );
+        assert_eq!(
+            b.push_raw(TYPE_HARDWARE, Hardware::SUBTYPE_VENDOR, &[0; 0xfffc]),
+            Err(DevicePathBuilderError::NodeSize),
+        );
+        assert_eq!(b.push_file_path(""), Err(DevicePathBuilderError::FilePath));
+        assert_eq!(b.push_file_path("a\0"), Err(DevicePathBuilderError::FilePath));
+        assert_eq!(b.push_file_path("\u{1f600}"), Err(DevicePathBuilderError::FilePath));
+        assert_eq!(b.len(), 6);
+
+        b.end_instance().unwrap();
+        assert_eq!(b.finish(), Err(DevicePathBuilderError::EmptyInstance));
+
+        let mut buffer = [0u8; 3];
+        assert_eq!(
+            DevicePathBuilder::new(&mut buffer[..]).finish(),
+            Err(DevicePathBuilderError::BufferTooSmall(4)),
+        );
+    }
+
+    fn text_to_path<'a>(buffer: &'a mut [u8], text: &str) -> DevicePath<'a> {
+        let mut b = DevicePathBuilder::new(buffer);
+        b.push_text(text).expect(text);
+        DevicePath::parse(b.finish().unwrap()).unwrap()
+    }
+
+    // Verify text representations convert to binary and back unchanged.
+    #[test]
+    fn text_round_trip() {
+        let texts = [
+            "",
+            concat!(
+                "PciRoot(0x0)/Pci(0x1f,0x2)/Sata(0x0,0xffff,0x0)/",
+                "HD(1,GPT,e0e6b9b3-4a30-4cf2-9d6d-2a2c2ef2d9b1,0x800,0x100000)/",
+                "\\EFI\\BOOT\\BOOTX64.EFI",
+            ),
+            concat!(
+                "PcieRoot(0x1)/Pci(0x0,0x0)/NVMe(0x1,00-11-22-33-44-55-66-77)/",
+                "HD(2,MBR,0x1234abcd,0x3f,0x1000)",
+            ),
+            concat!(
+                "Acpi(PNP0A06,0x2)/Acpi(0x12340000,0x0)/AcpiEx(PNP0A08,0x0,0x1,,ABC,)/",
+                "AcpiAdr(0x80010100,0x80010200)",
+            ),
+            concat!(
+                "MemoryMapped(0xb,0x1000,0x1fff)/VenHw(8be4df61-93ca-11d2-aa0d-00e098032b8c,0102)/",
+                "Ctrl(0x1)/BMC(0x1,0xca2)/PcCard(0x3)",
+            ),
+            concat!(
+                "Pci(0x2,0x0)/Ata(Primary,Slave,0x0)/Scsi(0x1,0x2)/Fibre(0x1122334455667788,0x1)/",
+                "FibreEx(0x0011223344556677,0x8899aabbccddeeff)/I1394(0x0000000000000abc)",
+            ),
+            concat!(
+                "USB(0x1,0x0)/UsbClass(0x46d,0xc52b,0x3,0x1,0x2)/",
+                "UsbWwid(0x781,0x5581,0x0,\"4C53/0001\")/Unit(0x2)",
+            ),
+            concat!(
+                "MAC(525400123456,0x1)/",
+                "IPv4(192.168.0.1:80,TCP,Static,192.168.0.2,192.168.0.254,255.255.255.0)/",
+                "Uri(http://example.com/boot.efi?a=1,b=2)",
+            ),
+            concat!(
+                "MAC(525400123456,0x1)/",
+                "IPv6([2001:db8::1]:443,UDP,StatefulAutoConfigure,fe80::1,0x40,::)/",
+                "Dns(2001:db8::53,2001:db8::54)",
+            ),
+            concat!(
+                "Uart(115200,8,N,1)/UartFlowCtrl(Hardware)/Vlan(100)/I2O(0x5)/",
+                "Infiniband(0x1,8be4df61-93ca-11d2-aa0d-00e098032b8c,0x1,0x2,0x3)",
+            ),
+            concat!(
+                "SD(0x0)/eMMC(0x1)/UFS(0x0,0x1)/Bluetooth(001122334455)/",
+                "BluetoothLE(001122334455,0x1)/Wi-Fi(My Network)/Dns(10.0.0.1)",
+            ),
+            "NVDIMM(8be4df61-93ca-11d2-aa0d-00e098032b8c)/RestService(RedFish,Out-of-Band)",
+            concat!(
+                "CDROM(0x1,0x10,0x20)/VenMedia(8be4df61-93ca-11d2-aa0d-00e098032b8c)/",
+                "Media(8be4df61-93ca-11d2-aa0d-00e098032b8c)",
+            ),
+            concat!(
+                "FvFile(8be4df61-93ca-11d2-aa0d-00e098032b8c)/",
+                "Fv(8be4df61-93ca-11d2-aa0d-00e098032b8c)/Offset(0x0,0x1ff)",
+            ),
+            concat!(
+                "VirtualDisk(0x1000,0x1fff,0)/",
+                "RamDisk(0x1000,0x1fff,1,8be4df61-93ca-11d2-aa0d-00e098032b8c)",
+            ),
+            "BBS(HD,Disk 1,0x0)/BBS(0x80,Net,0x1)",
+            "PciRoot(0x0)/Pci(0x1,0x0),PciRoot(0x1)/Pci(0x2,0x0),\\a b",
+            "Msg(22,0011)/Path(6,1,ff)/HardwarePath(9)/Uart(9600,8,0x9,2)/Ata(0x2,Master,0x1)",
+            "iSCSI(iqn.2001-04.com.example:disk,0x1,0x0001000000000000,CRC32C,None,CHAP_UNI,TCP)",
+            "iSCSI(,0x0,0x0000000000000000,None,CRC32C,None,0x1)",
+            "iSCSI(a,0x2,0x00ff000000000000,None,None,CHAP_BI,TCP)",
+            "SAS(0x5000c500a1b2c3d4,0x0,0x0,SATA,External,Expanded,0x3,0x0)",
+            "SAS(0x1,0x2,0x3,NoTopology,0,0,0,0x4)",
+            "SAS(0x1,0x2,0x3,0x1234,0,0,0,0x0)/SAS(0x1,0x0,0x0,SAS,Internal,Direct,0,0x0)",
+            "SasEx(0x5000c500a1b2c3d4,0x0000000000000001,0x1,NoTopology,0,0,0)",
+            "SasEx(0x0000000000000001,0x0000000000000000,0x0,SAS,Internal,Direct,0)",
+            "SasEx(0x0000000000000001,0x0000000000000000,0x0,SATA,Internal,Expanded,0x100)",
+            "SasEx(0x0000000000000001,0x0000000000000000,0x0,0x181,0,0,0)",
+            "VenPcAnsi()",
+            "VenVt100()",
+            "VenVt100Plus()",
+            "VenUtf8()",
+            "DebugPort()",
+            "VenMsg(e0c14753-f9be-11d2-9a0c-0090273fc14d,00)",
+            "NVMEoF(nqn.2014-08.org.nvmexpress:uuid:1,00-11-22-33-44-55-66-77)",
+            "NVMEoF(nqn.a,00112233445566778899aabbccddeeff)",
+            "NVMEoF(nqn.b,00112233-4455-6677-8899-aabbccddeeff)",
+        ];
+
+        for text in texts {
+            let mut buffer = [0u8; 1024];
+            let path = text_to_path(&mut buffer, text);
+            assert_eq!(std::format!("{}", path.to_text(false)), text);
+        }
+
+        let mut buffer = [0u8; 1024];
+        let path = text_to_path(&mut buffer, texts[1]);
+        assert_eq!(
+            std::format!("{}", path.to_text(true)),
+            concat!(
+                "PciRoot(0x0)/Pci(0x1f,0x2)/Sata(0x0,0xffff,0x0)/",
+                "HD(1,GPT,e0e6b9b3-4a30-4cf2-9d6d-2a2c2ef2d9b1)/\\EFI\\BOOT\\BOOTX64.EFI",
+            ),
+        );
+        let node = path.raw_nodes().nth(1).unwrap();
+        assert_eq!(std::format!("{}", node.to_text(false)), "Pci(0x1f,0x2)");
+    }
+
+    // Verify nodes that cannot be expressed with their keyword use the
+    // generic form and convert back to identical binary nodes.
+    #[test]
+    fn text_generic() {
+        let nodes: [(u8, u8, &[u8]); 6] = [
+            (TYPE_MESSAGING, Messaging::SUBTYPE_FIBRE_CHANNEL, &[1; 20]),
+            (TYPE_MEDIA, Media::SUBTYPE_FILE_PATH, &[b'a', 0, b'/', 0, 0, 0]),
+            (TYPE_MEDIA, Media::SUBTYPE_FILE_PATH, &[0, 0xd8, 0, 0]),
+            (TYPE_MEDIA, Media::SUBTYPE_FILE_PATH, &[b'a', 0, 0x3d, 0xd8, 0x00, 0xde, 0, 0]),
+            (TYPE_MESSAGING, Messaging::SUBTYPE_MAC_ADDRESS, &[1; 33]),
+            (TYPE_BIOS, Bios::SUBTYPE_BBS, &[1, 0, 0, 0, b'(', 0]),
+        ];
+
+        for (r#type, sub_type, data) in nodes {
+            let mut buffer = [0u8; 64];
+            let mut b = DevicePathBuilder::new(&mut buffer[..]);
+            b.push_raw(r#type, sub_type, data).unwrap();
+            let path = DevicePath::parse(b.finish().unwrap()).unwrap();
+
+            let text = std::format!("{}", path.to_text(false));
+            let mut buffer = [0u8; 64];
+            assert_eq!(text_to_path(&mut buffer, &text), path, "{}", text);
+        }
+    }
+
+    // Verify malformed text is rejected without modifying the builder.
+    #[test]
+    fn text_invalid() {
+        let mut buffer = [0u8; 32];
+        let mut b = DevicePathBuilder::new(&mut buffer[..]);
+        b.push_text("Pci(0x1,0x0)").unwrap();
+
+        let texts = [
+            ("Pci(0x1,0x0", DevicePathTextError::Syntax),
+            ("Pci(0x1,0x0))", DevicePathTextError::Syntax),
+            ("Pci(0x1,0x0)/", DevicePathTextError::Syntax),
+            ("\\a\"b", DevicePathTextError::Syntax),
+            ("Foo(1)", DevicePathTextError::Keyword),
+            ("Pci(0x100,0x0)", DevicePathTextError::Argument),
+            ("Pci(0x1)", DevicePathTextError::Argument),
+            ("Pci(0x1,0x0,0x0)", DevicePathTextError::Argument),
+            ("Pci(+1,0x0)", DevicePathTextError::Argument),
+            ("HD(1,0x3,0x0,0x0,0x0)", DevicePathTextError::Argument),
+            ("Path(127,1)", DevicePathTextError::Argument),
+            ("\\\u{1f600}", DevicePathTextError::Argument),
+            (
+                concat!(
+                    "Pci(0x1,0x0)/",
+                    "VenHw(8be4df61-93ca-11d2-aa0d-00e098032b8c,00000000000000000000000000000000)",
+                ),
+                DevicePathTextError::Build(DevicePathBuilderError::BufferTooSmall(52)),
+            ),
+        ];
+        for (text, error) in texts {
+            assert_eq!(b.push_text(text), Err(error), "{}", text);
+            assert_eq!(b.len(), 6);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn build_vec() {
+        let mut b = DevicePathBuilder::new(std::vec::Vec::new());
+        b.push(&SdMessaging { header: SdMessaging::header(), slot_number: 3 }).unwrap();
+        let uri = UriMessaging { header: <UriMessaging>::header(), uri: [] };
+        b.push_with_data(&uri, b"http://a").unwrap();
+        let path = b.finish().unwrap();
+        assert_eq!(path.len(), 5 + 12 + 4);
+        assert_eq!(DevicePath::parse(&path).unwrap().nodes().count(), 2);
+    }
+
+    // Verify binary paths the parser does not produce itself convert to text
+    // and back unchanged, or are documented not to.
+    #[test]
+    fn text_binary() {
+        let round_trip = |bytes: &[u8], text: &str| {
+            let path = DevicePath::parse(bytes).unwrap();
+            assert_eq!(std::format!("{}", path.to_text(false)), text);
+            let mut buffer = [0u8; 128];
+            assert_eq!(text_to_path(&mut buffer, text).as_bytes(), bytes);
+        };
+
+        // `Boolean` values other than 0 and 1
+        let mut buffer = [0u8; 128];
+        let text = "IPv4(10.0.0.1,TCP,Static,10.0.0.2,0.0.0.0,0.0.0.0)";
+        let mut bytes = [0u8; 31];
+        bytes.copy_from_slice(text_to_path(&mut buffer, text).as_bytes());
+        bytes[18] = 2;
+        round_trip(&bytes, "IPv4(10.0.0.1,TCP,0x2,10.0.0.2,0.0.0.0,0.0.0.0)");
+
+        // URIs with quotes
+        let mut b = DevicePathBuilder::new(&mut buffer[..]);
+        let uri = UriMessaging { header: <UriMessaging>::header(), uri: [] };
+        b.push_with_data(&uri, b"a\"b").unwrap();
+        round_trip(b.finish().unwrap(), "Msg(24,612262)");
+
+        // Empty instances and non-standard end nodes
+        let texts: [(&[u8], &str, DevicePathTextError); 3] = [
+            (&[0x7f, 0x01, 4, 0, 0x7f, 0xff, 4, 0], ",", DevicePathTextError::Syntax),
+            (
+                &[0x01, 0x01, 6, 0, 0, 0, 0x7f, 0x01, 4, 0, 0x7f, 0xff, 4, 0],
+                "Pci(0x0,0x0),",
+                DevicePathTextError::Syntax,
+            ),
+            (&[0x7f, 0x02, 4, 0, 0x7f, 0xff, 4, 0], "Path(127,2)", DevicePathTextError::Argument),
+        ];
+        for (bytes, text, error) in texts {
+            let path = DevicePath::parse(bytes).unwrap();
+            assert_eq!(std::format!("{}", path.to_text(false)), text);
+            let mut b = DevicePathBuilder::new(&mut buffer[..]);
+            assert_eq!(b.push_text(text), Err(error));
+        }
+    }
+
+    // Verify the device path utilities on multi-instance paths.
+    #[test]
+    fn algebra() {
+        let mut b0 = [0u8; 128];
+        let mut b1 = [0u8; 128];
+        let mut b2 = [0u8; 128];
+        let path = text_to_path(
+            &mut b0,
+            "PciRoot(0x0)/Pci(0x1,0x0)/Ctrl(0x1),PciRoot(0x0)/Pci(0x2,0x0)",
+        );
+        let prefix = text_to_path(&mut b1, "PciRoot(0x0)/Pci(0x1,0x0)");
+        let other = text_to_path(&mut b2, "PciRoot(0x0)/Pci(0x2,0x0)");
+
+        assert_eq!(path.size(), 12 + 6 + 8 + 4 + 12 + 6 + 4);
+        assert!(path.is_multi_instance());
+        assert!(!prefix.is_multi_instance());
+        assert!(path.starts_with(&prefix));
+        assert!(!path.starts_with(&other));
+        assert!(path.starts_with(&DevicePath::parse(&[0x7f, 0xff, 4, 0]).unwrap()));
+        let rest = path.strip_prefix(&prefix).unwrap();
+        assert_eq!(std::format!("{}", rest.to_text(false)), "Ctrl(0x1),PciRoot(0x0)/Pci(0x2,0x0)");
+
+        let (first, rest) = path.split_first_instance();
+        assert_eq!(first.size(), 12 + 6 + 8);
+        assert_eq!(rest, Some(other));
+        assert_eq!(other.split_first_instance(), (other.instances().next().unwrap(), None));
+
+        let (init, last) = first.split_last().unwrap();
+        assert!(first.starts_with(&init));
+        assert_eq!(last.r#type(), 1);
+        assert_eq!(init.split_last().unwrap().0.split_last().unwrap().0.split_last(), None);
+
+        // Rebuild the path from its parts.
+        let mut buffer = [0u8; 128];
+        let mut b = DevicePathBuilder::new(&mut buffer[..]);
+        b.push_path(&prefix).unwrap();
+        b.push_node(last).unwrap();
+        b.push_instance(&other.instances().next().unwrap()).unwrap();
+        assert!(b.pop());
+        b.push_text("Pci(0x2,0x0)").unwrap();
+        assert_eq!(b.finish().unwrap(), path.as_bytes());
+
+        // Removing nodes walks back across instances.
+        let mut buffer = [0u8; 128];
+        let mut b = DevicePathBuilder::new(&mut buffer[..]);
+        b.push_path(&path).unwrap();
+        assert!(b.pop() && b.pop() && b.pop());
+        let mut b3 = [0u8; 128];
+        let first = text_to_path(&mut b3, "PciRoot(0x0)/Pci(0x1,0x0)/Ctrl(0x1)");
+        assert_eq!(b.finish().unwrap(), first.as_bytes());
+
+        // Appending is atomic.
+        let mut buffer = [0u8; 40];
+        let mut b = DevicePathBuilder::new(&mut buffer[..]);
+        b.push_path(&prefix).unwrap();
+        assert_eq!(b.push_path(&path), Err(DevicePathBuilderError::BufferTooSmall(48)));
+        assert_eq!(b.len(), 18);
+        assert!(b.pop() && b.pop() && !b.pop());
+        assert!(b.is_empty());
+    }
 }
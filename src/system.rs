@@ -46,6 +46,279 @@ pub struct TimeCapabilities {
     pub sets_to_zero: crate::base::Boolean,
 }
 
+/// Time Validation Errors
+///
+/// This is returned when a `Time` object is outside of the ranges defined by
+/// the specification, or when a conversion would produce such a time. Each
+/// variant names the offending field.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum TimeError {
+    /// The year is not within 1900 to 9999.
+    Year,
+    /// The month is not within 1 to 12.
+    Month,
+    /// The day is 0, or past the end of the month.
+    Day,
+    /// The hour is bigger than 23.
+    Hour,
+    /// The minute is bigger than 59.
+    Minute,
+    /// The second is bigger than 59.
+    Second,
+    /// The nanosecond is not below one second.
+    Nanosecond,
+    /// The time zone is neither within -1440 to 1440 minutes, nor
+    /// `UNSPECIFIED_TIMEZONE`.
+    TimeZone,
+    /// Daylight bits other than `TIME_ADJUST_DAYLIGHT` and `TIME_IN_DAYLIGHT`
+    /// are set.
+    Daylight,
+}
+
+/// RFC 3339 Time Formatter
+///
+/// This formats a `Time` object as RFC 3339 timestamp. See `Time::rfc3339()`.
+#[derive(Clone, Copy, Debug)]
+pub struct TimeRfc3339<'a> {
+    time: &'a Time,
+}
+
+impl Time {
+    const SECONDS_PER_DAY: i64 = 86400;
+
+    /// Check whether a Year is a Leap Year
+    pub const fn is_leap_year(year: u16) -> bool {
+        (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+    }
+
+    /// Return the Number of Days in a Month
+    ///
+    /// Return the number of days of `month` (1 to 12) in `year`, or 0 if the
+    /// month is invalid.
+    pub const fn days_in_month(year: u16, month: u8) -> u8 {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if Self::is_leap_year(year) => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+
+    // Days since 1970-01-01 of a proleptic Gregorian date. This is valid for
+    // any month and day in range, but does not verify them.
+    const fn days_from_civil(year: u16, month: u8, day: u8) -> i64 {
+        let y = year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = month as i64;
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+
+    // Inverse of `days_from_civil()`, returning year, month, and day.
+    const fn civil_from_days(days: i64) -> (i64, u8, u8) {
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let d = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let m = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+        (y, m, d)
+    }
+
+    /// Validate a Time Object
+    ///
+    /// Verify that all fields are within the ranges defined by the
+    /// specification: year 1900 to 9999, a valid month and day (including
+    /// leap days), a valid time of day, nanoseconds below one second, a time
+    /// zone between -1440 and 1440 minutes or `UNSPECIFIED_TIMEZONE`, and no
+    /// daylight bits other than `TIME_ADJUST_DAYLIGHT` and
+    /// `TIME_IN_DAYLIGHT`. The padding fields are ignored.
+    pub fn validate(&self) -> Result<(), TimeError> {
+        if self.year < 1900 || self.year > 9999 {
+            Err(TimeError::Year)
+        } else if self.month < 1 || self.month > 12 {
+            Err(TimeError::Month)
+        } else if self.day < 1 || self.day > Self::days_in_month(self.year, self.month) {
+            Err(TimeError::Day)
+        } else if self.hour > 23 {
+            Err(TimeError::Hour)
+        } else if self.minute > 59 {
+            Err(TimeError::Minute)
+        } else if self.second > 59 {
+            Err(TimeError::Second)
+        } else if self.nanosecond > 999_999_999 {
+            Err(TimeError::Nanosecond)
+        } else if self.timezone != UNSPECIFIED_TIMEZONE
+            && (self.timezone < -1440 || self.timezone > 1440)
+        {
+            Err(TimeError::TimeZone)
+        } else if self.daylight & !(TIME_ADJUST_DAYLIGHT | TIME_IN_DAYLIGHT) != 0 {
+            Err(TimeError::Daylight)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check whether a Time Object is Valid
+    ///
+    /// See `validate()` for details.
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Return the Time Zone Offset in Minutes
+    ///
+    /// Return the offset of the local time relative to UTC (i.e., local time
+    /// is UTC plus the offset). `UNSPECIFIED_TIMEZONE` yields `None`.
+    pub fn timezone_offset(&self) -> Option<i16> {
+        if self.timezone == UNSPECIFIED_TIMEZONE {
+            None
+        } else {
+            Some(self.timezone)
+        }
+    }
+
+    // Seconds since the Unix epoch, treating unspecified time zones as UTC.
+    // This does not validate the object, but never overflows.
+    fn unix_unchecked(&self) -> i64 {
+        Self::days_from_civil(self.year, self.month, self.day) * Self::SECONDS_PER_DAY
+            + self.hour as i64 * 3600
+            + self.minute as i64 * 60
+            + self.second as i64
+            - self.timezone_offset().unwrap_or(0) as i64 * 60
+    }
+
+    /// Convert to Unix Time
+    ///
+    /// Return the number of seconds since 1970-01-01T00:00:00Z. The time zone
+    /// offset is applied. Times with an unspecified time zone are treated as
+    /// UTC. The daylight bits are informational and not applied. The
+    /// sub-second part is available in the `nanosecond` field.
+    pub fn to_unix(&self) -> Result<i64, TimeError> {
+        self.validate()?;
+        Ok(self.unix_unchecked())
+    }
+
+    /// Create a Time Object from Unix Time
+    ///
+    /// Create a time object in UTC (time zone 0, no daylight bits) from the
+    /// number of seconds since 1970-01-01T00:00:00Z and a sub-second part in
+    /// nanoseconds. An error is returned if the result is out of the range
+    /// defined by the specification.
+    pub fn from_unix(seconds: i64, nanosecond: u32) -> Result<Self, TimeError> {
+        if nanosecond > 999_999_999 {
+            return Err(TimeError::Nanosecond);
+        }
+
+        let days = seconds.div_euclid(Self::SECONDS_PER_DAY);
+        let secs = seconds.rem_euclid(Self::SECONDS_PER_DAY);
+        let (year, month, day) = Self::civil_from_days(days);
+        if !(1900..=9999).contains(&year) {
+            return Err(TimeError::Year);
+        }
+
+        Ok(Self {
+            year: year as u16,
+            month,
+            day,
+            hour: (secs / 3600) as u8,
+            minute: (secs / 60 % 60) as u8,
+            second: (secs % 60) as u8,
+            pad1: 0,
+            nanosecond,
+            timezone: 0,
+            daylight: 0,
+            pad2: 0,
+        })
+    }
+
+    /// Normalize to UTC
+    ///
+    /// Return the same point in time in UTC (time zone 0, no daylight bits).
+    /// Times with an unspecified time zone are treated as UTC. An error is
+    /// returned if the object is invalid, or the result is out of range.
+    pub fn to_utc(&self) -> Result<Self, TimeError> {
+        Self::from_unix(self.to_unix()?, self.nanosecond)
+    }
+
+    /// Compare Points in Time
+    ///
+    /// Compare two time objects by the point in time they represent, taking
+    /// the time zones into account. Times with an unspecified time zone are
+    /// treated as UTC. The objects are not validated, and the padding fields
+    /// are ignored.
+    pub fn cmp_instant(&self, other: &Self) -> core::cmp::Ordering {
+        (self.unix_unchecked(), self.nanosecond).cmp(&(other.unix_unchecked(), other.nanosecond))
+    }
+
+    /// Format as RFC 3339
+    ///
+    /// Return a formatter that prints the time as RFC 3339 timestamp (e.g.,
+    /// `2024-02-29T13:37:00.5+01:00`). The sub-second part is only printed if
+    /// non-zero. A time zone of 0 is printed as `Z`, an unspecified time zone
+    /// as `-00:00` (i.e., unknown local offset). The object is not validated.
+    pub fn rfc3339(&self) -> TimeRfc3339<'_> {
+        TimeRfc3339 { time: self }
+    }
+}
+
+impl core::fmt::Display for TimeRfc3339<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let t = self.time;
+
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            t.year, t.month, t.day, t.hour, t.minute, t.second,
+        )?;
+
+        if t.nanosecond > 0 {
+            let mut v = t.nanosecond;
+            let mut width = 9;
+            while v % 10 == 0 {
+                v /= 10;
+                width -= 1;
+            }
+            write!(f, ".{:0width$}", v, width = width)?;
+        }
+
+        match t.timezone_offset() {
+            None => write!(f, "-00:00"),
+            Some(0) => write!(f, "Z"),
+            Some(v) => {
+                let sign = if v < 0 { '-' } else { '+' };
+                write!(f, "{}{:02}:{:02}", sign, v.unsigned_abs() / 60, v.unsigned_abs() % 60)
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for TimeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let field = match self {
+            TimeError::Year => "year",
+            TimeError::Month => "month",
+            TimeError::Day => "day",
+            TimeError::Hour => "hour",
+            TimeError::Minute => "minute",
+            TimeError::Second => "second",
+            TimeError::Nanosecond => "nanosecond",
+            TimeError::TimeZone => "time zone",
+            TimeError::Daylight => "daylight",
+        };
+        write!(f, "{} out of range", field)
+    }
+}
+
+impl core::error::Error for TimeError {}
+
 //
 // UEFI Variables
 //
@@ -83,9 +356,811 @@ pub struct VariableAuthentication<const N: usize = 0> {
 #[derive(Clone, Copy, Debug)]
 pub struct VariableAuthentication2<const N: usize = 0> {
     pub timestamp: Time,
-    pub auth_info: [u8; N], // WIN_CERTIFICATE_UEFI_ID from PE/COFF
+    pub auth_info: [u8; N], // WinCertificateUefiGuid
+}
+
+pub const WIN_CERT_REVISION_1_0: u16 = 0x0100u16;
+pub const WIN_CERT_REVISION_2_0: u16 = 0x0200u16;
+
+pub const WIN_CERT_TYPE_PKCS_SIGNED_DATA: u16 = 0x0002u16;
+pub const WIN_CERT_TYPE_EFI_PKCS115: u16 = 0x0ef0u16;
+pub const WIN_CERT_TYPE_EFI_GUID: u16 = 0x0ef1u16;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct WinCertificate {
+    pub length: u32,
+    pub revision: u16,
+    pub certificate_type: u16,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct WinCertificateUefiGuid<const N: usize = 0> {
+    pub hdr: WinCertificate,
+    pub cert_type: crate::base::Guid,
+    pub cert_data: [u8; N],
+}
+
+pub const CERT_TYPE_RSA2048_SHA256_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xa7717414,
+    0xc616,
+    0x4977,
+    0x94,
+    0x20,
+    &[0x84, 0x47, 0x12, 0xa7, 0x35, 0xbf],
+);
+
+pub const CERT_TYPE_PKCS7_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x4aafd29d,
+    0x68df,
+    0x49ee,
+    0x8a,
+    0xa9,
+    &[0x34, 0x7d, 0x37, 0x56, 0x65, 0xa7],
+);
+
+/// Authenticated Variable Errors
+///
+/// This is returned when building time-based authenticated variable
+/// payloads fails.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum AuthenticatedVariableError {
+    /// The timestamp is invalid, or has any of `pad1`, `nanosecond`,
+    /// `timezone`, `daylight`, or `pad2` set, which must be 0.
+    Timestamp,
+    /// The signature is empty or too big.
+    Signature,
+    /// The variable name is empty, or contains characters that cannot be
+    /// encoded as UCS-2 (including NULs).
+    Name,
+    /// `VARIABLE_TIME_BASED_AUTHENTICATED_WRITE_ACCESS` is not set, or
+    /// `VARIABLE_AUTHENTICATED_WRITE_ACCESS` is set.
+    Attributes,
+    /// The output buffer is too small. The required size is included.
+    BufferTooSmall(usize),
+}
+
+impl Time {
+    /// Serialize to Bytes
+    ///
+    /// Return the little-endian representation of the time object as used
+    /// in variable payloads and signed data. The padding fields are included
+    /// verbatim.
+    pub fn to_bytes(&self) -> [u8; 16] {
+        let mut v = [0u8; 16];
+        v[0..2].copy_from_slice(&self.year.to_le_bytes());
+        v[2] = self.month;
+        v[3] = self.day;
+        v[4] = self.hour;
+        v[5] = self.minute;
+        v[6] = self.second;
+        v[7] = self.pad1;
+        v[8..12].copy_from_slice(&self.nanosecond.to_le_bytes());
+        v[12..14].copy_from_slice(&self.timezone.to_le_bytes());
+        v[14] = self.daylight;
+        v[15] = self.pad2;
+        v
+    }
+}
+
+fn verify_authentication_timestamp(timestamp: &Time) -> Result<(), AuthenticatedVariableError> {
+    if timestamp.validate().is_err()
+        || timestamp.pad1 != 0
+        || timestamp.nanosecond != 0
+        || timestamp.timezone != 0
+        || timestamp.daylight != 0
+        || timestamp.pad2 != 0
+    {
+        Err(AuthenticatedVariableError::Timestamp)
+    } else {
+        Ok(())
+    }
+}
+
+// Copy `parts` into `buffer` back to back, after checking the total size.
+fn write_parts(buffer: &mut [u8], parts: &[&[u8]]) -> Result<usize, AuthenticatedVariableError> {
+    let size = parts.iter().map(|v| v.len()).sum();
+    let buffer = buffer
+        .get_mut(..size)
+        .ok_or(AuthenticatedVariableError::BufferTooSmall(size))?;
+
+    let mut offset = 0;
+    for v in parts {
+        buffer[offset..offset + v.len()].copy_from_slice(v);
+        offset += v.len();
+    }
+
+    Ok(size)
 }
 
+/// Serialize a Time-Based Authenticated Variable
+///
+/// Write the payload to pass to `set_variable()` together with
+/// `VARIABLE_TIME_BASED_AUTHENTICATED_WRITE_ACCESS` into `buffer`, and return
+/// its size. The payload consists of a `VariableAuthentication2` descriptor
+/// with `timestamp` and a `WinCertificateUefiGuid` of type
+/// `CERT_TYPE_PKCS7_GUID` carrying the detached PKCS#7 `signature`, followed
+/// by the variable `data`. The signature must cover the data produced by
+/// `write_variable_authentication_2_signed_data()` for the same timestamp and
+/// data.
+///
+/// The timestamp must be a valid time with `nanosecond`, `timezone`,
+/// `daylight`, and the padding fields set to 0, as required by the
+/// specification.
+pub fn write_variable_authentication_2(
+    buffer: &mut [u8],
+    timestamp: &Time,
+    signature: &[u8],
+    data: &[u8],
+) -> Result<usize, AuthenticatedVariableError> {
+    verify_authentication_timestamp(timestamp)?;
+
+    let cert_size = core::mem::size_of::<WinCertificateUefiGuid>() + signature.len();
+    if signature.is_empty() || cert_size > u32::MAX as usize {
+        return Err(AuthenticatedVariableError::Signature);
+    }
+
+    let mut hdr = [0u8; 8];
+    hdr[0..4].copy_from_slice(&(cert_size as u32).to_le_bytes());
+    hdr[4..6].copy_from_slice(&WIN_CERT_REVISION_2_0.to_le_bytes());
+    hdr[6..8].copy_from_slice(&WIN_CERT_TYPE_EFI_GUID.to_le_bytes());
+
+    write_parts(
+        buffer,
+        &[
+            &timestamp.to_bytes(),
+            &hdr,
+            CERT_TYPE_PKCS7_GUID.as_bytes(),
+            signature,
+            data,
+        ],
+    )
+}
+
+/// Serialize the Signed Data of a Time-Based Authenticated Variable
+///
+/// Write the data to be hashed and signed for a time-based authenticated
+/// variable into `buffer`, and return its size. This is the concatenation of
+/// the variable name (UCS-2, without terminating NUL), the vendor GUID, the
+/// attributes, the timestamp, and the variable data. The attributes are
+/// included verbatim, so `VARIABLE_APPEND_WRITE` must be set if the payload
+/// is written with it.
+pub fn write_variable_authentication_2_signed_data(
+    buffer: &mut [u8],
+    name: &str,
+    vendor_guid: &crate::base::Guid,
+    attributes: u32,
+    timestamp: &Time,
+    data: &[u8],
+) -> Result<usize, AuthenticatedVariableError> {
+    verify_authentication_timestamp(timestamp)?;
+
+    if name.is_empty() || name.chars().any(|c| c == '\0' || c.len_utf16() != 1) {
+        return Err(AuthenticatedVariableError::Name);
+    }
+    if attributes & VARIABLE_TIME_BASED_AUTHENTICATED_WRITE_ACCESS == 0
+        || attributes & VARIABLE_AUTHENTICATED_WRITE_ACCESS != 0
+    {
+        return Err(AuthenticatedVariableError::Attributes);
+    }
+
+    let name_size = name.chars().count() * 2;
+    let size = name_size + 16 + 4 + 16 + data.len();
+    if buffer.len() < size {
+        return Err(AuthenticatedVariableError::BufferTooSmall(size));
+    }
+
+    for (i, c) in name.chars().enumerate() {
+        buffer[i * 2..i * 2 + 2].copy_from_slice(&(c as u16).to_le_bytes());
+    }
+
+    write_parts(
+        &mut buffer[name_size..],
+        &[
+            vendor_guid.as_bytes(),
+            &attributes.to_le_bytes(),
+            &timestamp.to_bytes(),
+            data,
+        ],
+    )
+    .map(|v| v + name_size)
+}
+
+impl core::fmt::Display for AuthenticatedVariableError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            AuthenticatedVariableError::Timestamp => write!(f, "invalid authentication timestamp"),
+            AuthenticatedVariableError::Signature => write!(f, "invalid signature size"),
+            AuthenticatedVariableError::Name => write!(f, "invalid variable name"),
+            AuthenticatedVariableError::Attributes => {
+                write!(f, "invalid authenticated variable attributes")
+            }
+            AuthenticatedVariableError::BufferTooSmall(v) => {
+                write!(f, "buffer too small, {} bytes required", v)
+            }
+        }
+    }
+}
+
+impl core::error::Error for AuthenticatedVariableError {}
+
+pub const IMAGE_SECURITY_DATABASE_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xd719b2cb,
+    0x3d3a,
+    0x4596,
+    0xa3,
+    0xbc,
+    &[0xda, 0xd0, 0x0e, 0x67, 0x65, 0x6f],
+);
+
+/// Name of the authorized signature database variable.
+pub const IMAGE_SECURITY_DATABASE: &str = "db";
+/// Name of the forbidden signature database variable.
+pub const IMAGE_SECURITY_DATABASE1: &str = "dbx";
+/// Name of the timestamp signature database variable.
+pub const IMAGE_SECURITY_DATABASE2: &str = "dbt";
+/// Name of the OS recovery signature database variable.
+pub const IMAGE_SECURITY_DATABASE3: &str = "dbr";
+
+pub const CERT_SHA1_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x826ca512,
+    0xcf10,
+    0x4ac9,
+    0xb1,
+    0x87,
+    &[0xbe, 0x01, 0x49, 0x66, 0x31, 0xbd],
+);
+
+pub const CERT_SHA224_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x0b6e5233,
+    0xa65c,
+    0x44c9,
+    0x94,
+    0x07,
+    &[0xd9, 0xab, 0x83, 0xbf, 0xc8, 0xbd],
+);
+
+pub const CERT_SHA256_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xc1c41626,
+    0x504c,
+    0x4092,
+    0xac,
+    0xa9,
+    &[0x41, 0xf9, 0x36, 0x93, 0x43, 0x28],
+);
+
+pub const CERT_SHA384_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xff3e5307,
+    0x9fd0,
+    0x48c9,
+    0x85,
+    0xf1,
+    &[0x8a, 0xd5, 0x6c, 0x70, 0x1e, 0x01],
+);
+
+pub const CERT_SHA512_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x093e0fae,
+    0xa6c4,
+    0x4f50,
+    0x9f,
+    0x1b,
+    &[0xd4, 0x1e, 0x2b, 0x89, 0xc1, 0x9a],
+);
+
+pub const CERT_RSA2048_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x3c5766e8,
+    0x269c,
+    0x4e34,
+    0xaa,
+    0x14,
+    &[0xed, 0x77, 0x6e, 0x85, 0xb3, 0xb6],
+);
+
+pub const CERT_RSA2048_SHA1_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x67f8444f,
+    0x8743,
+    0x48f1,
+    0xa3,
+    0x28,
+    &[0x1e, 0xaa, 0xb8, 0x73, 0x60, 0x80],
+);
+
+pub const CERT_RSA2048_SHA256_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xe2b36190,
+    0x879b,
+    0x4a3d,
+    0xad,
+    0x8d,
+    &[0xf2, 0xe7, 0xbb, 0xa3, 0x27, 0x84],
+);
+
+pub const CERT_X509_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0xa5c059a1,
+    0x94e4,
+    0x4aa7,
+    0x87,
+    0xb5,
+    &[0xab, 0x15, 0x5c, 0x2b, 0xf0, 0x72],
+);
+
+pub const CERT_X509_SHA256_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x3bd2a492,
+    0x96c0,
+    0x4079,
+    0xb4,
+    0x20,
+    &[0xfc, 0xf9, 0x8e, 0xf1, 0x03, 0xed],
+);
+
+pub const CERT_X509_SHA384_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x7076876e,
+    0x80c2,
+    0x4ee6,
+    0xaa,
+    0xd2,
+    &[0x28, 0xb3, 0x49, 0xa6, 0x86, 0x5b],
+);
+
+pub const CERT_X509_SHA512_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x446dbf63,
+    0x2502,
+    0x4cda,
+    0xbc,
+    0xfa,
+    &[0x24, 0x65, 0xd2, 0xb0, 0xfe, 0x9d],
+);
+
+pub const CERT_EXTERNAL_MANAGEMENT_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x452e8ced,
+    0xdfff,
+    0x4b8c,
+    0xae,
+    0x01,
+    &[0x51, 0x18, 0x86, 0x2e, 0x68, 0x2c],
+);
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct SignatureData<const N: usize = 0> {
+    pub signature_owner: crate::base::Guid,
+    pub signature_data: [u8; N],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct SignatureList {
+    pub signature_type: crate::base::Guid,
+    pub signature_list_size: u32,
+    pub signature_header_size: u32,
+    pub signature_size: u32,
+    // Followed by `signature_header_size` bytes of header, and an array of
+    // `SignatureData` entries of `signature_size` bytes each.
+}
+
+/// Return the Signature Data Size of a Certificate Type
+///
+/// Return the size of the signature data (i.e., without the owner GUID) of
+/// the fixed-size signature types, or `None` for variable-size or unknown
+/// types. The X.509 hash types include the `Time` of revocation.
+pub fn signature_data_size(signature_type: &crate::base::Guid) -> Option<usize> {
+    const SIZES: &[(crate::base::Guid, usize)] = &[
+        (CERT_SHA1_GUID, 20),
+        (CERT_SHA224_GUID, 28),
+        (CERT_SHA256_GUID, 32),
+        (CERT_SHA384_GUID, 48),
+        (CERT_SHA512_GUID, 64),
+        (CERT_RSA2048_GUID, 256),
+        (CERT_RSA2048_SHA1_GUID, 256),
+        (CERT_RSA2048_SHA256_GUID, 256),
+        (CERT_X509_SHA256_GUID, 32 + 16),
+        (CERT_X509_SHA384_GUID, 48 + 16),
+        (CERT_X509_SHA512_GUID, 64 + 16),
+    ];
+
+    SIZES.iter().find(|v| v.0 == *signature_type).map(|v| v.1)
+}
+
+/// Signature List Errors
+///
+/// This is returned when parsing or building signature databases fails.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum SignatureListError {
+    /// The data ends within a list header.
+    Truncated,
+    /// The list size is too small for the header, or exceeds the data.
+    ListSize,
+    /// The signature size is smaller than the owner GUID, does not divide
+    /// the list, or does not match the signature type.
+    SignatureSize,
+    /// The output buffer is too small, or a list would exceed its maximum
+    /// size. The required buffer size is included.
+    BufferTooSmall(usize),
+}
+
+/// Signature Database
+///
+/// This is a validated sequence of `SignatureList` structures, as stored in
+/// `db`, `dbx`, `KEK`, `PK` and related variables.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct SignatureDatabase<'a> {
+    data: &'a [u8],
+}
+
+/// Signature List View
+///
+/// This is a single list of a `SignatureDatabase`.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct SignatureListView<'a> {
+    pub signature_type: crate::base::Guid,
+    pub header: &'a [u8],
+    signature_size: usize,
+    signatures: &'a [u8],
+}
+
+/// Signature Entry
+///
+/// This is a single `SignatureData` entry of a signature list.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct SignatureEntry<'a> {
+    pub owner: crate::base::Guid,
+    pub data: &'a [u8],
+}
+
+/// Signature List Iterator
+///
+/// This iterates the lists of a `SignatureDatabase`.
+#[derive(Clone, Debug)]
+pub struct SignatureLists<'a> {
+    data: &'a [u8],
+}
+
+/// Signature Entry Iterator
+///
+/// This iterates the entries of a `SignatureListView`.
+#[derive(Clone, Debug)]
+pub struct SignatureEntries<'a> {
+    signatures: &'a [u8],
+    signature_size: usize,
+}
+
+/// Signature Database Builder
+///
+/// This builds a signature database in a caller-provided buffer. Entries
+/// are appended with the semantics of `VARIABLE_APPEND_WRITE`: entries
+/// already present (same type, owner, and data) are skipped, others are
+/// added to the first compatible list, or to a new list at the end.
+#[derive(Debug)]
+pub struct SignatureDatabaseBuilder<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+const SIGNATURE_LIST_HEADER_SIZE: usize = core::mem::size_of::<SignatureList>();
+const SIGNATURE_OWNER_SIZE: usize = core::mem::size_of::<crate::base::Guid>();
+
+fn read_u32_le(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]])
+}
+
+fn read_guid(data: &[u8], offset: usize) -> crate::base::Guid {
+    let mut v = [0u8; 16];
+    v.copy_from_slice(&data[offset..offset + 16]);
+    crate::base::Guid::from_bytes(&v)
+}
+
+// Parse and validate the list at the start of `data`, returning it and its
+// total size.
+fn parse_signature_list(data: &[u8]) -> Result<(SignatureListView<'_>, usize), SignatureListError> {
+    if data.len() < SIGNATURE_LIST_HEADER_SIZE {
+        return Err(SignatureListError::Truncated);
+    }
+
+    let signature_type = read_guid(data, 0);
+    let list_size = read_u32_le(data, 16) as usize;
+    let header_size = read_u32_le(data, 20) as usize;
+    let signature_size = read_u32_le(data, 24) as usize;
+
+    if list_size > data.len()
+        || list_size < SIGNATURE_LIST_HEADER_SIZE
+        || list_size - SIGNATURE_LIST_HEADER_SIZE < header_size
+    {
+        return Err(SignatureListError::ListSize);
+    }
+
+    let header = &data[SIGNATURE_LIST_HEADER_SIZE..SIGNATURE_LIST_HEADER_SIZE + header_size];
+    let signatures = &data[SIGNATURE_LIST_HEADER_SIZE + header_size..list_size];
+    if signature_size < SIGNATURE_OWNER_SIZE
+        || signatures.len() % signature_size != 0
+        || signature_data_size(&signature_type)
+            .is_some_and(|v| v + SIGNATURE_OWNER_SIZE != signature_size)
+    {
+        return Err(SignatureListError::SignatureSize);
+    }
+
+    Ok((
+        SignatureListView {
+            signature_type,
+            header,
+            signature_size,
+            signatures,
+        },
+        list_size,
+    ))
+}
+
+impl<'a> SignatureDatabase<'a> {
+    /// Parse a Signature Database
+    ///
+    /// Validate the size fields of all lists in `data`. The lists must cover
+    /// `data` exactly. Known signature types must use their fixed size.
+    pub fn parse(data: &'a [u8]) -> Result<Self, SignatureListError> {
+        let mut rest = data;
+        while !rest.is_empty() {
+            let (_, size) = parse_signature_list(rest)?;
+            rest = &rest[size..];
+        }
+
+        Ok(Self { data })
+    }
+
+    /// Return the Raw Database
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Iterate the Signature Lists
+    pub fn lists(&self) -> SignatureLists<'a> {
+        SignatureLists { data: self.data }
+    }
+
+    /// Check whether an Entry is Present
+    ///
+    /// Check whether any list of type `signature_type` contains an entry
+    /// with the given owner and data.
+    pub fn contains(
+        &self,
+        signature_type: &crate::base::Guid,
+        owner: &crate::base::Guid,
+        data: &[u8],
+    ) -> bool {
+        self.lists()
+            .filter(|v| v.signature_type == *signature_type)
+            .flat_map(|v| v.entries())
+            .any(|v| v.owner == *owner && v.data == data)
+    }
+}
+
+impl<'a> SignatureListView<'a> {
+    /// Return the Size of each Entry
+    ///
+    /// This is the size of a `SignatureData` entry in bytes, including the
+    /// owner GUID.
+    pub fn signature_size(&self) -> usize {
+        self.signature_size
+    }
+
+    /// Return the Number of Entries
+    pub fn len(&self) -> usize {
+        self.signatures.len() / self.signature_size
+    }
+
+    /// Check whether the List has no Entries
+    pub fn is_empty(&self) -> bool {
+        self.signatures.is_empty()
+    }
+
+    /// Iterate the Entries
+    pub fn entries(&self) -> SignatureEntries<'a> {
+        SignatureEntries {
+            signatures: self.signatures,
+            signature_size: self.signature_size,
+        }
+    }
+}
+
+impl<'a> SignatureDatabaseBuilder<'a> {
+    /// Create an Empty Database Builder
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self { buffer, len: 0 }
+    }
+
+    /// Create a Database Builder with Existing Contents
+    ///
+    /// Continue a database whose first `len` bytes are already in `buffer`.
+    /// The contents are validated as by `SignatureDatabase::parse()`.
+    pub fn with_contents(buffer: &'a mut [u8], len: usize) -> Result<Self, SignatureListError> {
+        SignatureDatabase::parse(buffer.get(..len).ok_or(SignatureListError::Truncated)?)?;
+        Ok(Self { buffer, len })
+    }
+
+    /// Return the Database Size
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the Database is Empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the Database
+    pub fn database(&self) -> SignatureDatabase<'_> {
+        SignatureDatabase {
+            data: &self.buffer[..self.len],
+        }
+    }
+
+    /// Append an Entry
+    ///
+    /// Append an entry of type `signature_type`, unless it is already
+    /// present. Returns whether the entry was added. The entry is added to
+    /// the first list of the same type and size without list header, or to
+    /// a new list otherwise.
+    pub fn append(
+        &mut self,
+        signature_type: &crate::base::Guid,
+        owner: &crate::base::Guid,
+        data: &[u8],
+    ) -> Result<bool, SignatureListError> {
+        let signature_size = SIGNATURE_OWNER_SIZE + data.len();
+        if signature_data_size(signature_type).is_some_and(|v| v != data.len())
+            || signature_size > u32::MAX as usize
+        {
+            return Err(SignatureListError::SignatureSize);
+        }
+
+        if self.database().contains(signature_type, owner, data) {
+            return Ok(false);
+        }
+
+        // Find a compatible list and the offset right past it.
+        let mut offset = 0;
+        let mut target = None;
+        for v in self.database().lists() {
+            let size = SIGNATURE_LIST_HEADER_SIZE + v.header.len() + v.signatures.len();
+            if target.is_none()
+                && v.signature_type == *signature_type
+                && v.header.is_empty()
+                && v.signature_size == signature_size
+                && size + signature_size <= u32::MAX as usize
+            {
+                target = Some((offset, offset + size));
+            }
+            offset += size;
+        }
+
+        let insert = match target {
+            Some((_, end)) => end,
+            None => self.len,
+        };
+        let grow = signature_size + if target.is_some() { 0 } else { SIGNATURE_LIST_HEADER_SIZE };
+        if self.buffer.len() < self.len + grow {
+            return Err(SignatureListError::BufferTooSmall(self.len + grow));
+        }
+
+        self.buffer.copy_within(insert..self.len, insert + grow);
+        let mut at = insert;
+
+        match target {
+            Some((start, end)) => {
+                let size = (end - start + signature_size) as u32;
+                self.buffer[start + 16..start + 20].copy_from_slice(&size.to_le_bytes());
+            }
+            None => {
+                let size = (SIGNATURE_LIST_HEADER_SIZE + signature_size) as u32;
+                self.buffer[at..at + 16].copy_from_slice(signature_type.as_bytes());
+                self.buffer[at + 16..at + 20].copy_from_slice(&size.to_le_bytes());
+                self.buffer[at + 20..at + 24].copy_from_slice(&0u32.to_le_bytes());
+                self.buffer[at + 24..at + 28]
+                    .copy_from_slice(&(signature_size as u32).to_le_bytes());
+                at += SIGNATURE_LIST_HEADER_SIZE;
+            }
+        }
+
+        self.buffer[at..at + SIGNATURE_OWNER_SIZE].copy_from_slice(owner.as_bytes());
+        self.buffer[at + SIGNATURE_OWNER_SIZE..at + signature_size].copy_from_slice(data);
+        self.len += grow;
+
+        Ok(true)
+    }
+
+    /// Append a Database
+    ///
+    /// Append all entries of `database` as by `append()`, and return the
+    /// number of entries added. Lists with a list header cannot be merged,
+    /// so they are appended verbatim, unless an identical list is present.
+    /// If the buffer runs out of space, the entries appended so far are kept.
+    pub fn append_database(
+        &mut self,
+        database: &SignatureDatabase<'_>,
+    ) -> Result<usize, SignatureListError> {
+        let mut n = 0;
+
+        for list in database.lists() {
+            if list.header.is_empty() {
+                for v in list.entries() {
+                    if self.append(&list.signature_type, &v.owner, v.data)? {
+                        n += 1;
+                    }
+                }
+            } else if !self.database().lists().any(|v| v == list) {
+                let size = SIGNATURE_LIST_HEADER_SIZE + list.header.len() + list.signatures.len();
+                if self.buffer.len() < self.len + size {
+                    return Err(SignatureListError::BufferTooSmall(self.len + size));
+                }
+
+                let start = list_offset(database, &list);
+                self.buffer[self.len..self.len + size]
+                    .copy_from_slice(&database.data[start..start + size]);
+                self.len += size;
+                n += list.len();
+            }
+        }
+
+        Ok(n)
+    }
+}
+
+// Return the offset of `list` within `database`, which it must be part of.
+fn list_offset(database: &SignatureDatabase<'_>, list: &SignatureListView<'_>) -> usize {
+    list.header.as_ptr() as usize - database.data.as_ptr() as usize - SIGNATURE_LIST_HEADER_SIZE
+}
+
+impl<'a> Iterator for SignatureLists<'a> {
+    type Item = SignatureListView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (list, size) = parse_signature_list(self.data).ok()?;
+        self.data = &self.data[size..];
+        Some(list)
+    }
+}
+
+impl<'a> Iterator for SignatureEntries<'a> {
+    type Item = SignatureEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.signatures.is_empty() {
+            return None;
+        }
+
+        let (entry, rest) = self.signatures.split_at(self.signature_size);
+        self.signatures = rest;
+        Some(SignatureEntry {
+            owner: read_guid(entry, 0),
+            data: &entry[SIGNATURE_OWNER_SIZE..],
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.signatures.len() / self.signature_size;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for SignatureEntries<'_> {}
+
+impl core::fmt::Display for SignatureListError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            SignatureListError::Truncated => write!(f, "signature list truncated"),
+            SignatureListError::ListSize => write!(f, "invalid signature list size"),
+            SignatureListError::SignatureSize => write!(f, "invalid signature size"),
+            SignatureListError::BufferTooSmall(v) => {
+                write!(f, "buffer too small, {} bytes required", v)
+            }
+        }
+    }
+}
+
+impl core::error::Error for SignatureListError {}
+
 pub const VARIABLE_AUTHENTICATION_3_TIMESTAMP_TYPE: u32 = 0x1u32;
 pub const VARIABLE_AUTHENTICATION_3_NONCE_TYPE: u32 = 0x2u32;
 
@@ -123,6 +1198,181 @@ pub const GLOBAL_VARIABLE: crate::base::Guid = crate::base::Guid::from_fields(
     &[0x00, 0xE0, 0x98, 0x03, 0x2B, 0x8C],
 );
 
+/// Global Variable Value Formats
+///
+/// This describes the format of the value of a global variable, as defined
+/// by the specification.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum GlobalVariableFormat {
+    /// A single `u8`, usually a boolean flag.
+    Uint8,
+    /// A single `u16`.
+    Uint16,
+    /// A single `u32`.
+    Uint32,
+    /// A single `u64`.
+    Uint64,
+    /// An array of `u16`, usually load option numbers.
+    Uint16Array,
+    /// An array of GUIDs.
+    GuidArray,
+    /// An ASCII string, possibly NUL-terminated.
+    AsciiString,
+    /// A device path, possibly with multiple instances.
+    DevicePath,
+    /// An `EFI_LOAD_OPTION`, see `LoadOption`.
+    LoadOption,
+    /// An `EFI_KEY_OPTION`.
+    KeyOption,
+    /// A list of `EFI_SIGNATURE_LIST` structures.
+    SignatureList,
+}
+
+/// Global Variable Description
+///
+/// This describes a variable defined by the specification in the
+/// `GLOBAL_VARIABLE` namespace. Numbered variables use `####` as placeholder
+/// for the four uppercase hex digits in their name (e.g., `Boot####`). The
+/// attributes are the ones the specification mandates for the variable.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct GlobalVariable {
+    pub name: &'static str,
+    pub attributes: u32,
+    pub format: GlobalVariableFormat,
+}
+
+const GLOBAL_VARIABLE_BS_RT: u32 = VARIABLE_BOOTSERVICE_ACCESS | VARIABLE_RUNTIME_ACCESS;
+const GLOBAL_VARIABLE_NV_BS_RT: u32 = VARIABLE_NON_VOLATILE | GLOBAL_VARIABLE_BS_RT;
+const GLOBAL_VARIABLE_NV_BS_RT_AT: u32 =
+    GLOBAL_VARIABLE_NV_BS_RT | VARIABLE_TIME_BASED_AUTHENTICATED_WRITE_ACCESS;
+
+macro_rules! global_variables {
+    ($($name:literal, $attributes:ident, $format:ident;)*) => {
+        &[$(GlobalVariable {
+            name: $name,
+            attributes: $attributes,
+            format: GlobalVariableFormat::$format,
+        },)*]
+    };
+}
+
+/// Catalog of all global variables defined by the specification.
+pub const GLOBAL_VARIABLES: &[GlobalVariable] = global_variables![
+    "AuditMode", GLOBAL_VARIABLE_BS_RT, Uint8;
+    "Boot####", GLOBAL_VARIABLE_NV_BS_RT, LoadOption;
+    "BootCurrent", GLOBAL_VARIABLE_BS_RT, Uint16;
+    "BootNext", GLOBAL_VARIABLE_NV_BS_RT, Uint16;
+    "BootOptionSupport", GLOBAL_VARIABLE_BS_RT, Uint32;
+    "BootOrder", GLOBAL_VARIABLE_NV_BS_RT, Uint16Array;
+    "ConIn", GLOBAL_VARIABLE_NV_BS_RT, DevicePath;
+    "ConInDev", GLOBAL_VARIABLE_BS_RT, DevicePath;
+    "ConOut", GLOBAL_VARIABLE_NV_BS_RT, DevicePath;
+    "ConOutDev", GLOBAL_VARIABLE_BS_RT, DevicePath;
+    "CryptoIndications", GLOBAL_VARIABLE_NV_BS_RT, Uint64;
+    "CryptoIndicationsActivated", GLOBAL_VARIABLE_BS_RT, Uint64;
+    "CryptoIndicationsSupported", GLOBAL_VARIABLE_BS_RT, Uint64;
+    "dbDefault", GLOBAL_VARIABLE_BS_RT, SignatureList;
+    "dbrDefault", GLOBAL_VARIABLE_BS_RT, SignatureList;
+    "dbtDefault", GLOBAL_VARIABLE_BS_RT, SignatureList;
+    "dbxDefault", GLOBAL_VARIABLE_BS_RT, SignatureList;
+    "DeployedMode", GLOBAL_VARIABLE_BS_RT, Uint8;
+    "devAuthBoot", GLOBAL_VARIABLE_BS_RT, Uint8;
+    "devdbDefault", GLOBAL_VARIABLE_BS_RT, SignatureList;
+    "Driver####", GLOBAL_VARIABLE_NV_BS_RT, LoadOption;
+    "DriverOrder", GLOBAL_VARIABLE_NV_BS_RT, Uint16Array;
+    "ErrOut", GLOBAL_VARIABLE_NV_BS_RT, DevicePath;
+    "ErrOutDev", GLOBAL_VARIABLE_BS_RT, DevicePath;
+    "HwErrRecSupport", GLOBAL_VARIABLE_NV_BS_RT, Uint16;
+    "KEK", GLOBAL_VARIABLE_NV_BS_RT_AT, SignatureList;
+    "KEKDefault", GLOBAL_VARIABLE_BS_RT, SignatureList;
+    "Key####", GLOBAL_VARIABLE_NV_BS_RT, KeyOption;
+    "Lang", GLOBAL_VARIABLE_NV_BS_RT, AsciiString;
+    "LangCodes", GLOBAL_VARIABLE_BS_RT, AsciiString;
+    "OsIndications", GLOBAL_VARIABLE_NV_BS_RT, Uint64;
+    "OsIndicationsSupported", GLOBAL_VARIABLE_BS_RT, Uint64;
+    "OsRecoveryOrder", GLOBAL_VARIABLE_NV_BS_RT_AT, GuidArray;
+    "PK", GLOBAL_VARIABLE_NV_BS_RT_AT, SignatureList;
+    "PKDefault", GLOBAL_VARIABLE_BS_RT, SignatureList;
+    "PlatformLang", GLOBAL_VARIABLE_NV_BS_RT, AsciiString;
+    "PlatformLangCodes", GLOBAL_VARIABLE_BS_RT, AsciiString;
+    "PlatformRecovery####", GLOBAL_VARIABLE_BS_RT, LoadOption;
+    "SecureBoot", GLOBAL_VARIABLE_BS_RT, Uint8;
+    "SetupMode", GLOBAL_VARIABLE_BS_RT, Uint8;
+    "SignatureSupport", GLOBAL_VARIABLE_BS_RT, GuidArray;
+    "SysPrep####", GLOBAL_VARIABLE_NV_BS_RT, LoadOption;
+    "SysPrepOrder", GLOBAL_VARIABLE_NV_BS_RT, Uint16Array;
+    "Timeout", GLOBAL_VARIABLE_NV_BS_RT, Uint16;
+    "VendorKeys", GLOBAL_VARIABLE_BS_RT, Uint8;
+];
+
+impl GlobalVariable {
+    /// Look up a Global Variable
+    ///
+    /// Find the catalog entry of the global variable `name`. Numbered
+    /// variables (e.g., `Boot0001`) match their `####` entry.
+    pub fn lookup(name: &str) -> Option<&'static GlobalVariable> {
+        let prefix = parse_numbered_variable_name(name).map(|(v, _)| v);
+
+        GLOBAL_VARIABLES.iter().find(|v| match v.name.strip_suffix("####") {
+            Some(p) => Some(p) == prefix,
+            None => v.name == name,
+        })
+    }
+
+    /// Check whether the Variable is Numbered
+    pub fn is_numbered(&self) -> bool {
+        self.name.ends_with("####")
+    }
+}
+
+/// Format a Numbered Variable Name
+///
+/// Write `prefix` followed by `number` as four uppercase hex digits (e.g.,
+/// `Boot002A`) into `buffer`, and return the resulting string. `None` is
+/// returned if the buffer is too small.
+pub fn format_numbered_variable_name<'a>(
+    prefix: &str,
+    number: u16,
+    buffer: &'a mut [u8],
+) -> Option<&'a str> {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+    let len = prefix.len() + 4;
+    let buffer = buffer.get_mut(..len)?;
+    let (head, tail) = buffer.split_at_mut(prefix.len());
+
+    head.copy_from_slice(prefix.as_bytes());
+    for (i, v) in tail.iter_mut().enumerate() {
+        *v = DIGITS[(number >> (12 - i * 4)) as usize & 0xf];
+    }
+
+    core::str::from_utf8(buffer).ok()
+}
+
+/// Parse a Numbered Variable Name
+///
+/// Split a variable name like `Boot002A` into its prefix and number. The
+/// name must end in exactly four uppercase hex digits, preceded by a
+/// non-empty prefix that does not end in a hex digit itself.
+pub fn parse_numbered_variable_name(name: &str) -> Option<(&str, u16)> {
+    let split = name.len().checked_sub(4)?;
+    if !name.is_char_boundary(split) {
+        return None;
+    }
+
+    let (prefix, digits) = name.split_at(split);
+    if prefix.is_empty()
+        || prefix.ends_with(|c: char| c.is_ascii_digit() || ('A'..='F').contains(&c))
+        || !digits.bytes().all(|c| c.is_ascii_digit() || (b'A'..=b'F').contains(&c))
+    {
+        return None;
+    }
+
+    u16::from_str_radix(digits, 16).ok().map(|v| (prefix, v))
+}
+
 //
 // Boot Manager
 //
@@ -138,6 +1388,301 @@ pub const LOAD_OPTION_CATEGORY: u32 = 0x00001F00u32;
 pub const LOAD_OPTION_CATEGORY_BOOT: u32 = 0x00000000u32;
 pub const LOAD_OPTION_CATEGORY_APP: u32 = 0x00000100u32;
 
+/// Load Option Errors
+///
+/// This is returned when parsing or serializing `EFI_LOAD_OPTION`
+/// descriptors fails.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum LoadOptionError {
+    /// The data ends before the fixed header, the description, or the file
+    /// path list.
+    Truncated,
+    /// The description is not NUL-terminated, or contains characters that
+    /// cannot be encoded as UCS-2 (including interior NULs).
+    Description,
+    /// The file path list is empty, contains malformed device path nodes, or
+    /// does not end with an end-of-entire-path node.
+    FilePathList,
+    /// The output buffer is too small. The required size is included.
+    BufferTooSmall(usize),
+}
+
+/// Load Option Descriptor
+///
+/// This is a parsed `EFI_LOAD_OPTION` as stored in `Boot####`, `Driver####`,
+/// `SysPrep####` and `PlatformRecovery####` variables. The variable-length
+/// fields borrow from the raw variable data. The description is kept as raw
+/// little-endian UCS-2 without the terminating NUL, since variable data
+/// carries no alignment guarantees.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct LoadOption<'a> {
+    pub attributes: u32,
+    pub description: &'a [u8],
+    pub file_path_list: &'a [u8],
+    pub optional_data: &'a [u8],
+}
+
+/// Load Option File Path Iterator
+///
+/// This iterates the device path instances of a load option file path list.
+/// See `LoadOption::file_paths()`.
+#[derive(Clone, Debug)]
+pub struct LoadOptionFilePaths<'a> {
+    list: &'a [u8],
+}
+
+// Split little-endian UCS-2 data at the first NUL character, returning the
+// string without the NUL and the data following it.
+fn split_ucs2_le(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    let nul = data.chunks_exact(2).position(|v| v == [0, 0])?;
+    Some((&data[..nul * 2], &data[(nul + 1) * 2..]))
+}
+
+fn ucs2_le_units(data: &[u8]) -> impl Iterator<Item = crate::base::Char16> + '_ {
+    data.chunks_exact(2).map(|v| u16::from_le_bytes([v[0], v[1]]))
+}
+
+fn ucs2_le_chars(data: &[u8]) -> impl Iterator<Item = char> + '_ {
+    core::char::decode_utf16(ucs2_le_units(data))
+        .map(|v| v.unwrap_or(core::char::REPLACEMENT_CHARACTER))
+}
+
+// Return the length of the device-path node at the start of `list`, or `None`
+// if it is truncated or malformed.
+fn device_path_node_len(list: &[u8]) -> Option<usize> {
+    let len = u16::from_le_bytes([*list.get(2)?, *list.get(3)?]) as usize;
+    if len < 4 || len > list.len() {
+        None
+    } else {
+        Some(len)
+    }
+}
+
+fn verify_file_path_list(mut list: &[u8]) -> Result<(), LoadOptionError> {
+    let mut last = None;
+
+    while !list.is_empty() {
+        let len = device_path_node_len(list).ok_or(LoadOptionError::FilePathList)?;
+        last = Some((list[0], list[1]));
+        list = &list[len..];
+    }
+
+    match last {
+        Some((
+            crate::protocols::device_path::TYPE_END,
+            crate::protocols::device_path::End::SUBTYPE_ENTIRE,
+        )) => Ok(()),
+        _ => Err(LoadOptionError::FilePathList),
+    }
+}
+
+impl<'a> LoadOption<'a> {
+    const OFFSET_DESCRIPTION: usize = 6;
+
+    /// Parse a Load Option
+    ///
+    /// Parse the raw contents of a load option variable. The file path list
+    /// is verified to consist of well-formed device path nodes, terminated by
+    /// an end-of-entire-path node. Everything past the file path list is
+    /// returned as optional data.
+    pub fn parse(data: &'a [u8]) -> Result<Self, LoadOptionError> {
+        if data.len() < Self::OFFSET_DESCRIPTION {
+            return Err(LoadOptionError::Truncated);
+        }
+
+        let attributes = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+        let file_path_list_length = u16::from_le_bytes([data[4], data[5]]) as usize;
+
+        let (description, rest) = split_ucs2_le(&data[Self::OFFSET_DESCRIPTION..])
+            .ok_or(LoadOptionError::Description)?;
+        if rest.len() < file_path_list_length {
+            return Err(LoadOptionError::Truncated);
+        }
+        let (file_path_list, optional_data) = rest.split_at(file_path_list_length);
+        verify_file_path_list(file_path_list)?;
+
+        Ok(Self {
+            attributes,
+            description,
+            file_path_list,
+            optional_data,
+        })
+    }
+
+    /// Return the Load Option Category
+    ///
+    /// Return the category bits of the attributes, to be compared against
+    /// `LOAD_OPTION_CATEGORY_BOOT` and `LOAD_OPTION_CATEGORY_APP`.
+    pub fn category(&self) -> u32 {
+        self.attributes & LOAD_OPTION_CATEGORY
+    }
+
+    /// Check whether `LOAD_OPTION_ACTIVE` is Set
+    pub fn is_active(&self) -> bool {
+        self.attributes & LOAD_OPTION_ACTIVE != 0
+    }
+
+    /// Check whether `LOAD_OPTION_HIDDEN` is Set
+    pub fn is_hidden(&self) -> bool {
+        self.attributes & LOAD_OPTION_HIDDEN != 0
+    }
+
+    /// Iterate the Description as UCS-2
+    pub fn description_ucs2(&self) -> impl Iterator<Item = crate::base::Char16> + 'a {
+        ucs2_le_units(self.description)
+    }
+
+    /// Iterate the Description as Characters
+    ///
+    /// Decode the description, replacing invalid code units (i.e.,
+    /// unpaired surrogates) with `U+FFFD`.
+    pub fn description_chars(&self) -> impl Iterator<Item = char> + 'a {
+        ucs2_le_chars(self.description)
+    }
+
+    /// Iterate the Device Path Instances
+    ///
+    /// Split the file path list at its end-of-instance and end-of-entire-path
+    /// nodes, and yield each device path instance without its end node. The
+    /// first instance describes the image to load.
+    pub fn file_paths(&self) -> LoadOptionFilePaths<'a> {
+        LoadOptionFilePaths {
+            list: self.file_path_list,
+        }
+    }
+
+    /// Return the Serialized Size
+    pub fn size(&self) -> usize {
+        Self::OFFSET_DESCRIPTION
+            + self.description.len()
+            + 2
+            + self.file_path_list.len()
+            + self.optional_data.len()
+    }
+
+    /// Serialize a Load Option
+    ///
+    /// Write the load option into `buffer` and return the number of bytes
+    /// written. The description must be UCS-2 without NULs, and the file path
+    /// list must be valid, as verified by `parse()`.
+    pub fn write(&self, buffer: &mut [u8]) -> Result<usize, LoadOptionError> {
+        if self.description.len() % 2 != 0 || self.description_ucs2().any(|v| v == 0) {
+            return Err(LoadOptionError::Description);
+        }
+
+        let mut offset =
+            write_load_option_header(buffer, self.attributes, self.file_path_list, self.size())?;
+        buffer[offset..offset + self.description.len()].copy_from_slice(self.description);
+        offset += self.description.len();
+        Ok(write_load_option_tail(buffer, offset, self.file_path_list, self.optional_data))
+    }
+}
+
+// Verify the file path list and buffer size, and write the fixed header.
+fn write_load_option_header(
+    buffer: &mut [u8],
+    attributes: u32,
+    file_path_list: &[u8],
+    size: usize,
+) -> Result<usize, LoadOptionError> {
+    verify_file_path_list(file_path_list)?;
+    if file_path_list.len() > u16::MAX as usize {
+        return Err(LoadOptionError::FilePathList);
+    }
+    if buffer.len() < size {
+        return Err(LoadOptionError::BufferTooSmall(size));
+    }
+
+    buffer[0..4].copy_from_slice(&attributes.to_le_bytes());
+    buffer[4..6].copy_from_slice(&(file_path_list.len() as u16).to_le_bytes());
+    Ok(LoadOption::OFFSET_DESCRIPTION)
+}
+
+// Write the description terminator, file path list and optional data.
+fn write_load_option_tail(
+    buffer: &mut [u8],
+    mut offset: usize,
+    file_path_list: &[u8],
+    optional_data: &[u8],
+) -> usize {
+    buffer[offset..offset + 2].copy_from_slice(&[0, 0]);
+    offset += 2;
+    buffer[offset..offset + file_path_list.len()].copy_from_slice(file_path_list);
+    offset += file_path_list.len();
+    buffer[offset..offset + optional_data.len()].copy_from_slice(optional_data);
+    offset + optional_data.len()
+}
+
+/// Serialize a Load Option from a String Description
+///
+/// Build a load option into `buffer` with the description encoded from
+/// `description`, and return the number of bytes written. The description
+/// must not contain NULs or characters outside the basic multilingual plane.
+/// If the buffer is too small, `LoadOptionError::BufferTooSmall` reports the
+/// required size.
+pub fn write_load_option(
+    buffer: &mut [u8],
+    attributes: u32,
+    description: &str,
+    file_path_list: &[u8],
+    optional_data: &[u8],
+) -> Result<usize, LoadOptionError> {
+    if description.chars().any(|c| c == '\0' || c.len_utf16() != 1) {
+        return Err(LoadOptionError::Description);
+    }
+
+    let description_length = description.chars().count() * 2;
+    let size = LoadOption::OFFSET_DESCRIPTION
+        + description_length
+        + 2
+        + file_path_list.len()
+        + optional_data.len();
+
+    let mut offset = write_load_option_header(buffer, attributes, file_path_list, size)?;
+    for c in description.chars() {
+        buffer[offset..offset + 2].copy_from_slice(&(c as u16).to_le_bytes());
+        offset += 2;
+    }
+    Ok(write_load_option_tail(buffer, offset, file_path_list, optional_data))
+}
+
+impl<'a> Iterator for LoadOptionFilePaths<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut offset = 0;
+
+        while offset < self.list.len() {
+            let len = device_path_node_len(&self.list[offset..])?;
+            if self.list[offset] == crate::protocols::device_path::TYPE_END {
+                let instance = &self.list[..offset];
+                self.list = &self.list[offset + len..];
+                return Some(instance);
+            }
+            offset += len;
+        }
+
+        None
+    }
+}
+
+impl core::fmt::Display for LoadOptionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            LoadOptionError::Truncated => write!(f, "load option truncated"),
+            LoadOptionError::Description => write!(f, "invalid load option description"),
+            LoadOptionError::FilePathList => write!(f, "invalid load option file path list"),
+            LoadOptionError::BufferTooSmall(v) => {
+                write!(f, "buffer too small, {} bytes required", v)
+            }
+        }
+    }
+}
+
+impl core::error::Error for LoadOptionError {}
+
 //
 // Virtual Mappings
 //
@@ -196,6 +1741,242 @@ pub struct CapsuleHeader {
     pub capsule_image_size: u32,
 }
 
+/// Mask of capsule flags defined by the specification. The lower 16 bits are
+/// defined by the capsule type, the remaining upper bits are reserved.
+pub const CAPSULE_FLAGS_MASK: u32 = 0x0000ffffu32
+    | CAPSULE_FLAGS_PERSIST_ACROSS_RESET
+    | CAPSULE_FLAGS_POPULATE_SYSTEM_TABLE
+    | CAPSULE_FLAGS_INITIATE_RESET;
+
+/// Capsule Errors
+///
+/// This is returned when capsules or their scatter-gather lists are invalid.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum CapsuleError {
+    /// Reserved flags are set, or `CAPSULE_FLAGS_POPULATE_SYSTEM_TABLE` or
+    /// `CAPSULE_FLAGS_INITIATE_RESET` are set without
+    /// `CAPSULE_FLAGS_PERSIST_ACROSS_RESET`.
+    Flags,
+    /// The header size is smaller than `CapsuleHeader`, or exceeds the
+    /// capsule image size.
+    HeaderSize,
+    /// The fragments are empty, or do not add up to the capsule image size.
+    ImageSize,
+    /// The descriptor pages are empty, or have no space left.
+    Descriptors,
+    /// No capsule was added.
+    Empty,
+}
+
+impl CapsuleHeader {
+    /// Verify a Capsule Header
+    ///
+    /// Verify that no reserved flags are set, that the flags which require
+    /// `CAPSULE_FLAGS_PERSIST_ACROSS_RESET` are accompanied by it, and that
+    /// the header size is consistent.
+    pub fn verify(&self) -> Result<(), CapsuleError> {
+        let persist = self.flags & CAPSULE_FLAGS_PERSIST_ACROSS_RESET != 0;
+        let needs_persist =
+            self.flags & (CAPSULE_FLAGS_POPULATE_SYSTEM_TABLE | CAPSULE_FLAGS_INITIATE_RESET) != 0;
+
+        if self.flags & !CAPSULE_FLAGS_MASK != 0 || (needs_persist && !persist) {
+            Err(CapsuleError::Flags)
+        } else if (self.header_size as usize) < core::mem::size_of::<Self>()
+            || self.header_size > self.capsule_image_size
+        {
+            Err(CapsuleError::HeaderSize)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl CapsuleBlockDescriptor {
+    /// Create a Data Block Descriptor
+    pub fn data_block(address: crate::base::PhysicalAddress, length: u64) -> Self {
+        Self {
+            length,
+            data: CapsuleBlockDescriptorUnion { data_block: address },
+        }
+    }
+
+    /// Create a Continuation Descriptor
+    ///
+    /// Create a descriptor that continues the list at the descriptor array
+    /// at `address`.
+    pub fn continuation(address: crate::base::PhysicalAddress) -> Self {
+        Self {
+            length: 0,
+            data: CapsuleBlockDescriptorUnion { continuation_pointer: address },
+        }
+    }
+
+    /// Create a Terminating Descriptor
+    pub fn terminator() -> Self {
+        Self::continuation(0)
+    }
+
+    /// Return the Address
+    ///
+    /// Return the data block address, or the continuation pointer if the
+    /// length is 0. Both share the same representation.
+    pub fn address(&self) -> crate::base::PhysicalAddress {
+        // SAFETY: Both union members are plain physical addresses.
+        unsafe { self.data.data_block }
+    }
+
+    /// Check whether this Terminates the List
+    pub fn is_terminator(&self) -> bool {
+        self.length == 0 && self.address() == 0
+    }
+}
+
+impl core::fmt::Debug for CapsuleBlockDescriptor {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("CapsuleBlockDescriptor")
+            .field("length", &self.length)
+            .field("address", &self.address())
+            .finish()
+    }
+}
+
+/// Capsule Fragment
+///
+/// This describes a physically contiguous piece of a capsule image, usually
+/// a single page.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct CapsuleFragment {
+    pub address: crate::base::PhysicalAddress,
+    pub length: u64,
+}
+
+/// Capsule Descriptor Page
+///
+/// This is a physically contiguous array of block descriptors to be filled
+/// by `CapsuleBuilder`, together with its physical address. When a page is
+/// full, its last descriptor continues the list on the next page.
+#[derive(Debug)]
+pub struct CapsuleDescriptorPage<'a> {
+    pub address: crate::base::PhysicalAddress,
+    pub descriptors: &'a mut [CapsuleBlockDescriptor],
+}
+
+/// Capsule Scatter-Gather List Builder
+///
+/// This fills descriptor pages with the block descriptor list passed as
+/// `ScatterGatherList` to `update_capsule()`. Capsules are added in order,
+/// each as a list of fragments that make up the capsule image (including its
+/// header). Continuation descriptors are inserted when a page is full, and
+/// `finish()` terminates the list. All addresses are physical addresses
+/// provided by the caller, so the builder does not access the memory they
+/// describe and can run on any host.
+#[derive(Debug)]
+pub struct CapsuleBuilder<'a, 'b> {
+    pages: &'b mut [CapsuleDescriptorPage<'a>],
+    page: usize,
+    index: usize,
+    capsules: usize,
+}
+
+impl<'a, 'b> CapsuleBuilder<'a, 'b> {
+    /// Create a Capsule Builder
+    ///
+    /// Create a builder that fills `pages` in order. Every page must provide
+    /// at least one descriptor.
+    pub fn new(pages: &'b mut [CapsuleDescriptorPage<'a>]) -> Result<Self, CapsuleError> {
+        if pages.is_empty() || pages.iter().any(|v| v.descriptors.is_empty()) {
+            return Err(CapsuleError::Descriptors);
+        }
+
+        Ok(Self {
+            pages,
+            page: 0,
+            index: 0,
+            capsules: 0,
+        })
+    }
+
+    // Return the next free descriptor slot, keeping the last slot of each
+    // page for the continuation or terminating descriptor.
+    fn slot(&mut self) -> Result<&mut CapsuleBlockDescriptor, CapsuleError> {
+        while self.index + 1 >= self.pages[self.page].descriptors.len() {
+            let next = self
+                .pages
+                .get(self.page + 1)
+                .ok_or(CapsuleError::Descriptors)?
+                .address;
+            self.pages[self.page].descriptors[self.index] =
+                CapsuleBlockDescriptor::continuation(next);
+            self.page += 1;
+            self.index = 0;
+        }
+
+        self.index += 1;
+        Ok(&mut self.pages[self.page].descriptors[self.index - 1])
+    }
+
+    /// Add a Capsule
+    ///
+    /// Verify `header` and append descriptors for `fragments`, which must be
+    /// non-empty and add up to the capsule image size. On error, the list is
+    /// left unchanged, unless the descriptor pages ran out of space.
+    pub fn add_capsule(
+        &mut self,
+        header: &CapsuleHeader,
+        fragments: &[CapsuleFragment],
+    ) -> Result<(), CapsuleError> {
+        header.verify()?;
+
+        let mut total = 0u64;
+        for v in fragments {
+            if v.length == 0 {
+                return Err(CapsuleError::ImageSize);
+            }
+            total = total.saturating_add(v.length);
+        }
+        if fragments.is_empty() || total != header.capsule_image_size as u64 {
+            return Err(CapsuleError::ImageSize);
+        }
+
+        for v in fragments {
+            *self.slot()? = CapsuleBlockDescriptor::data_block(v.address, v.length);
+        }
+
+        self.capsules += 1;
+        Ok(())
+    }
+
+    /// Finish the List
+    ///
+    /// Terminate the descriptor list and return the physical address of its
+    /// first descriptor, to be passed as `ScatterGatherList`.
+    pub fn finish(self) -> Result<crate::base::PhysicalAddress, CapsuleError> {
+        if self.capsules == 0 {
+            return Err(CapsuleError::Empty);
+        }
+
+        // `slot()` always leaves the last descriptor of a page free.
+        self.pages[self.page].descriptors[self.index] = CapsuleBlockDescriptor::terminator();
+        Ok(self.pages[0].address)
+    }
+}
+
+impl core::fmt::Display for CapsuleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            CapsuleError::Flags => write!(f, "invalid capsule flags"),
+            CapsuleError::HeaderSize => write!(f, "invalid capsule header size"),
+            CapsuleError::ImageSize => write!(f, "capsule fragments do not match image size"),
+            CapsuleError::Descriptors => write!(f, "insufficient capsule descriptor space"),
+            CapsuleError::Empty => write!(f, "no capsules"),
+        }
+    }
+}
+
+impl core::error::Error for CapsuleError {}
+
 pub const OS_INDICATIONS_BOOT_TO_FW_UI: u64 = 0x0000000000000001u64;
 pub const OS_INDICATIONS_TIMESTAMP_REVOCATION: u64 = 0x0000000000000002u64;
 pub const OS_INDICATIONS_FILE_CAPSULE_DELIVERY_SUPPORTED: u64 = 0x0000000000000004u64;
@@ -233,6 +2014,255 @@ pub struct CapsuleResultVariableFMP<const N: usize = 0> {
     pub capsule_file_name_and_target: [crate::base::Char16; N],
 }
 
+pub const FIRMWARE_MANAGEMENT_CAPSULE_ID_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x6dcbd5ed,
+    0xe82d,
+    0x4c44,
+    0xbd,
+    0xa1,
+    &[0x71, 0x94, 0x19, 0x9a, 0xd9, 0x2a],
+);
+
+pub const JSON_CAPSULE_ID_GUID: crate::base::Guid = crate::base::Guid::from_fields(
+    0x67d6f4cd,
+    0xd6b8,
+    0x4573,
+    0xbf,
+    0x4a,
+    &[0xde, 0x5e, 0x25, 0x2d, 0x61, 0xae],
+);
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct CapsuleResultVariableJSON<const N: usize = 0> {
+    pub version: u32,
+    pub capsule_id: u32,
+    pub resp_length: u32,
+    pub resp: [u8; N],
+}
+
+/// Name prefix of capsule result variables (`Capsule####`).
+pub const CAPSULE_RESULT_VARIABLE_PREFIX: &str = "Capsule";
+/// Name of the variable referring to the last written capsule result.
+pub const CAPSULE_LAST_VARIABLE_NAME: &str = "CapsuleLast";
+/// Name of the variable referring to the highest capsule result number.
+pub const CAPSULE_MAX_VARIABLE_NAME: &str = "CapsuleMax";
+
+/// Capsule Result Errors
+///
+/// This is returned when decoding a capsule result variable fails.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum CapsuleResultError {
+    /// The data is shorter than the result header.
+    Truncated,
+    /// The total size is smaller than the header, or exceeds the data.
+    TotalSize,
+    /// The FMP result body is truncated, or its strings are not
+    /// NUL-terminated.
+    Fmp,
+    /// The JSON result body is truncated.
+    Json,
+}
+
+/// Capsule Result
+///
+/// This is a decoded `Capsule####` variable in the `CAPSULE_REPORT_GUID`
+/// namespace. The header uses the native layout of `Status`, so the data
+/// must stem from firmware of the same word size.
+#[derive(Clone, Copy, Debug)]
+pub struct CapsuleResult<'a> {
+    pub header: CapsuleResultVariableHeader,
+    pub body: CapsuleResultBody<'a>,
+}
+
+/// Capsule Result Body
+///
+/// This is the capsule-type specific part of a capsule result, selected by
+/// the capsule GUID of the header.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum CapsuleResultBody<'a> {
+    /// Result of a `FIRMWARE_MANAGEMENT_CAPSULE_ID_GUID` capsule.
+    Fmp(CapsuleResultFmp<'a>),
+    /// Result of a `JSON_CAPSULE_ID_GUID` capsule.
+    Json(CapsuleResultJson<'a>),
+    /// Result of any other capsule, with the raw body.
+    Other(&'a [u8]),
+}
+
+/// FMP Capsule Result
+///
+/// This is a decoded `CapsuleResultVariableFMP`. The strings are kept as raw
+/// little-endian UCS-2 without their terminating NUL. The file name is empty
+/// if the capsule was not loaded from disk. The target is the text
+/// representation of the device path of the device that published the
+/// firmware management protocol, if any.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct CapsuleResultFmp<'a> {
+    pub version: u16,
+    pub payload_index: u8,
+    pub update_image_index: u8,
+    pub update_image_type_id: crate::base::Guid,
+    pub capsule_file_name: &'a [u8],
+    pub capsule_target: &'a [u8],
+}
+
+/// JSON Capsule Result
+///
+/// This is a decoded `CapsuleResultVariableJSON`.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub struct CapsuleResultJson<'a> {
+    pub version: u32,
+    pub capsule_id: u32,
+    pub response: &'a [u8],
+}
+
+impl<'a> CapsuleResult<'a> {
+    /// Decode a Capsule Result Variable
+    ///
+    /// Decode the raw contents of a `Capsule####` variable. The body is
+    /// limited to `variable_total_size`, and decoded according to the
+    /// capsule GUID. Unknown capsule types are returned as raw body.
+    pub fn parse(data: &'a [u8]) -> Result<Self, CapsuleResultError> {
+        let header_size = core::mem::size_of::<CapsuleResultVariableHeader>();
+        if data.len() < header_size {
+            return Err(CapsuleResultError::Truncated);
+        }
+
+        // SAFETY: The header is plain-old-data and the range was
+        //         bounds-checked above. No alignment is required.
+        let header = unsafe {
+            core::ptr::read_unaligned(data.as_ptr() as *const CapsuleResultVariableHeader)
+        };
+
+        let total = header.variable_total_size as usize;
+        if total < header_size || total > data.len() {
+            return Err(CapsuleResultError::TotalSize);
+        }
+        let body = &data[header_size..total];
+
+        let body = if header.capsule_guid == FIRMWARE_MANAGEMENT_CAPSULE_ID_GUID {
+            CapsuleResultBody::Fmp(CapsuleResultFmp::parse(body)?)
+        } else if header.capsule_guid == JSON_CAPSULE_ID_GUID {
+            CapsuleResultBody::Json(CapsuleResultJson::parse(body)?)
+        } else {
+            CapsuleResultBody::Other(body)
+        };
+
+        Ok(Self { header, body })
+    }
+}
+
+impl<'a> CapsuleResultFmp<'a> {
+    /// Decode an FMP Capsule Result Body
+    pub fn parse(data: &'a [u8]) -> Result<Self, CapsuleResultError> {
+        if data.len() < 20 {
+            return Err(CapsuleResultError::Fmp);
+        }
+
+        let mut guid = [0u8; 16];
+        guid.copy_from_slice(&data[4..20]);
+        let (capsule_file_name, rest) = split_ucs2_le(&data[20..]).ok_or(CapsuleResultError::Fmp)?;
+        let (capsule_target, _) = split_ucs2_le(rest).ok_or(CapsuleResultError::Fmp)?;
+
+        Ok(Self {
+            version: u16::from_le_bytes([data[0], data[1]]),
+            payload_index: data[2],
+            update_image_index: data[3],
+            update_image_type_id: crate::base::Guid::from_bytes(&guid),
+            capsule_file_name,
+            capsule_target,
+        })
+    }
+
+    /// Iterate the Capsule File Name as Characters
+    pub fn file_name_chars(&self) -> impl Iterator<Item = char> + 'a {
+        ucs2_le_chars(self.capsule_file_name)
+    }
+
+    /// Iterate the Capsule Target as Characters
+    pub fn target_chars(&self) -> impl Iterator<Item = char> + 'a {
+        ucs2_le_chars(self.capsule_target)
+    }
+}
+
+impl<'a> CapsuleResultJson<'a> {
+    /// Decode a JSON Capsule Result Body
+    pub fn parse(data: &'a [u8]) -> Result<Self, CapsuleResultError> {
+        if data.len() < 12 {
+            return Err(CapsuleResultError::Json);
+        }
+
+        let field = |i: usize| u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+        let response = data[12..]
+            .get(..field(8) as usize)
+            .ok_or(CapsuleResultError::Json)?;
+
+        Ok(Self {
+            version: field(0),
+            capsule_id: field(4),
+            response,
+        })
+    }
+}
+
+/// Decode a Capsule Result Reference
+///
+/// Decode the contents of `CapsuleLast` or `CapsuleMax`, which hold the
+/// UCS-2 name of a result variable (e.g., `Capsule000A`), and return its
+/// number. A terminating NUL is accepted but not required.
+pub fn parse_capsule_result_reference(data: &[u8]) -> Option<u16> {
+    let mut name = [0u8; 11];
+
+    let mut n = 0;
+    for c in ucs2_le_chars(data) {
+        if c == '\0' {
+            break;
+        }
+        if !c.is_ascii() {
+            return None;
+        }
+        *name.get_mut(n)? = c as u8;
+        n += 1;
+    }
+
+    match parse_numbered_variable_name(core::str::from_utf8(&name[..n]).ok()?) {
+        Some((CAPSULE_RESULT_VARIABLE_PREFIX, v)) => Some(v),
+        _ => None,
+    }
+}
+
+/// Iterate Capsule Result Numbers
+///
+/// Return the numbers of all `Capsule####` variables in the order they were
+/// written, oldest first, given the numbers referenced by `CapsuleLast` and
+/// `CapsuleMax`. The firmware uses the variables as ring buffer, so the
+/// iteration starts after `last`, wraps at `max`, and ends with `last`.
+/// Variables that were not written yet are included, so callers must skip
+/// missing variables.
+pub fn capsule_result_numbers(last: u16, max: u16) -> impl Iterator<Item = u16> {
+    let max = max.max(last);
+    (last as u32 + 1..=max as u32)
+        .chain(0..=last as u32)
+        .map(|v| v as u16)
+}
+
+impl core::fmt::Display for CapsuleResultError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            CapsuleResultError::Truncated => write!(f, "capsule result truncated"),
+            CapsuleResultError::TotalSize => write!(f, "invalid capsule result size"),
+            CapsuleResultError::Fmp => write!(f, "invalid FMP capsule result"),
+            CapsuleResultError::Json => write!(f, "invalid JSON capsule result"),
+        }
+    }
+}
+
+impl core::error::Error for CapsuleResultError {}
+
 //
 // Tasks
 //
@@ -404,6 +2434,300 @@ pub struct MemoryDescriptor {
     pub attribute: u64,
 }
 
+/// Size of a page as used by memory descriptors and page allocations.
+pub const PAGE_SIZE: u64 = 0x0000000000001000u64;
+
+impl MemoryDescriptor {
+    /// Return the Size in Bytes
+    ///
+    /// Return the size of the described range in bytes, saturating on
+    /// overflow.
+    pub fn size(&self) -> u64 {
+        self.number_of_pages.saturating_mul(PAGE_SIZE)
+    }
+
+    /// Return the Physical End Address
+    ///
+    /// Return the first physical address past the described range, saturating
+    /// on overflow.
+    pub fn physical_end(&self) -> crate::base::PhysicalAddress {
+        self.physical_start.saturating_add(self.size())
+    }
+
+    /// Check whether a Physical Address is Contained
+    pub fn contains(&self, address: crate::base::PhysicalAddress) -> bool {
+        address >= self.physical_start && address < self.physical_end()
+    }
+}
+
+/// Memory Map Errors
+///
+/// This is returned by `MemoryMap::new()` if the parameters reported by
+/// `get_memory_map()` do not describe a valid memory map.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum MemoryMapError {
+    /// The descriptor size is smaller than `MemoryDescriptor`.
+    DescriptorSize,
+    /// The descriptor version is not `MEMORY_DESCRIPTOR_VERSION`.
+    DescriptorVersion,
+    /// The map size exceeds the buffer, or is not a multiple of the
+    /// descriptor size.
+    MapSize,
+}
+
+/// Memory Map View
+///
+/// This wraps a memory map as returned by `get_memory_map()`. The firmware
+/// reports the size of each descriptor separately, and it can be larger than
+/// `MemoryDescriptor` (future revisions append new fields). Therefore, the
+/// descriptors must not be accessed as a slice of `MemoryDescriptor`, but
+/// stepped over by the reported descriptor size. This view does that, and
+/// never requires the buffer to be aligned.
+///
+/// The view can be created over shared or mutable byte buffers. The latter
+/// allows sorting and coalescing the map in place.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryMap<B> {
+    buffer: B,
+    len: usize,
+    descriptor_size: usize,
+}
+
+/// Memory Map Iterator
+///
+/// This iterates the descriptors of a `MemoryMap`. See `MemoryMap::iter()`.
+#[derive(Clone, Debug)]
+pub struct MemoryMapIter<'a> {
+    buffer: &'a [u8],
+    descriptor_size: usize,
+}
+
+impl<B: AsRef<[u8]>> MemoryMap<B> {
+    /// Create Memory Map View
+    ///
+    /// Create a view over the memory map in `buffer`, using the map size,
+    /// descriptor size, and descriptor version as reported by
+    /// `get_memory_map()`. Trailing bytes of the buffer beyond `map_size`
+    /// are ignored.
+    pub fn new(
+        buffer: B,
+        map_size: usize,
+        descriptor_size: usize,
+        descriptor_version: u32,
+    ) -> Result<Self, MemoryMapError> {
+        if descriptor_version != MEMORY_DESCRIPTOR_VERSION {
+            return Err(MemoryMapError::DescriptorVersion);
+        }
+        if descriptor_size < core::mem::size_of::<MemoryDescriptor>() {
+            return Err(MemoryMapError::DescriptorSize);
+        }
+        if map_size > buffer.as_ref().len() || map_size % descriptor_size != 0 {
+            return Err(MemoryMapError::MapSize);
+        }
+
+        Ok(Self {
+            buffer,
+            len: map_size / descriptor_size,
+            descriptor_size,
+        })
+    }
+
+    /// Return the Number of Descriptors
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the Map is Empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the Descriptor Size
+    pub fn descriptor_size(&self) -> usize {
+        self.descriptor_size
+    }
+
+    /// Return the Map Size
+    ///
+    /// Return the size of the map in bytes. This shrinks when the map is
+    /// coalesced.
+    pub fn map_size(&self) -> usize {
+        self.len * self.descriptor_size
+    }
+
+    /// Return a Descriptor
+    ///
+    /// Return a copy of the descriptor at `index`, or `None` if out of
+    /// bounds.
+    pub fn get(&self, index: usize) -> Option<MemoryDescriptor> {
+        if index < self.len {
+            Some(read_descriptor(self.buffer.as_ref(), index * self.descriptor_size))
+        } else {
+            None
+        }
+    }
+
+    /// Iterate the Descriptors
+    pub fn iter(&self) -> MemoryMapIter<'_> {
+        MemoryMapIter {
+            buffer: &self.buffer.as_ref()[..self.map_size()],
+            descriptor_size: self.descriptor_size,
+        }
+    }
+
+    /// Find the Descriptor of an Address
+    ///
+    /// Return the first descriptor whose range contains the physical address
+    /// `address`, or `None` if none does.
+    pub fn find(&self, address: crate::base::PhysicalAddress) -> Option<MemoryDescriptor> {
+        self.iter().find(|v| v.contains(address))
+    }
+
+    /// Return the Total Pages of a Memory Type
+    ///
+    /// Return the sum of the page counts of all descriptors of type
+    /// `r#type`, saturating on overflow.
+    pub fn total_pages(&self, r#type: MemoryType) -> u64 {
+        self.iter()
+            .filter(|v| v.r#type == r#type)
+            .fold(0u64, |acc, v| acc.saturating_add(v.number_of_pages))
+    }
+
+    /// Return the Total Pages of all Memory Types
+    ///
+    /// Return an array indexed by memory type with the total number of pages
+    /// of each type defined by the specification. Descriptors of other types
+    /// (e.g., OEM or OS-loader reserved ranges) are not accounted. See
+    /// `total_pages()` for those.
+    pub fn totals(&self) -> [u64; UNACCEPTED_MEMORY_TYPE as usize + 1] {
+        let mut totals = [0u64; UNACCEPTED_MEMORY_TYPE as usize + 1];
+
+        for v in self.iter() {
+            if let Some(total) = totals.get_mut(v.r#type as usize) {
+                *total = total.saturating_add(v.number_of_pages);
+            }
+        }
+
+        totals
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> MemoryMap<B> {
+    /// Sort by Physical Address
+    ///
+    /// Sort the descriptors in place by their physical start address. The
+    /// sort is stable, and moves entire descriptors including any trailing
+    /// bytes beyond `MemoryDescriptor`. Memory maps are usually nearly
+    /// sorted, so this uses an insertion sort and does not allocate.
+    pub fn sort(&mut self) {
+        let ds = self.descriptor_size;
+        let buffer = self.buffer.as_mut();
+
+        for i in 1..self.len {
+            let start = read_descriptor(buffer, i * ds).physical_start;
+            let mut j = i;
+            while j > 0 && read_descriptor(buffer, (j - 1) * ds).physical_start > start {
+                j -= 1;
+            }
+            if j < i {
+                buffer[j * ds..(i + 1) * ds].rotate_right(ds);
+            }
+        }
+    }
+
+    /// Coalesce Adjacent Descriptors
+    ///
+    /// Merge neighboring descriptors if they have the same type and
+    /// attributes, and the second starts where the first ends. If virtual
+    /// addresses are assigned (i.e., non-zero), they must be contiguous as
+    /// well. Only neighbors are merged, so the map should be sorted first.
+    /// The map is compacted in place and its length updated.
+    pub fn coalesce(&mut self) {
+        let ds = self.descriptor_size;
+        let buffer = self.buffer.as_mut();
+        let mut len = 0;
+
+        for i in 0..self.len {
+            let next = read_descriptor(buffer, i * ds);
+
+            if len > 0 {
+                let mut prev = read_descriptor(buffer, (len - 1) * ds);
+                let virtual_ok = (prev.virtual_start == 0 && next.virtual_start == 0)
+                    || prev.virtual_start.checked_add(prev.size()) == Some(next.virtual_start);
+
+                if prev.r#type == next.r#type
+                    && prev.attribute == next.attribute
+                    && prev.physical_start.checked_add(prev.size()) == Some(next.physical_start)
+                    && virtual_ok
+                {
+                    if let Some(pages) = prev.number_of_pages.checked_add(next.number_of_pages) {
+                        prev.number_of_pages = pages;
+                        write_descriptor(buffer, (len - 1) * ds, &prev);
+                        continue;
+                    }
+                }
+            }
+
+            if len != i {
+                buffer.copy_within(i * ds..(i + 1) * ds, len * ds);
+            }
+            len += 1;
+        }
+
+        self.len = len;
+    }
+}
+
+fn read_descriptor(buffer: &[u8], offset: usize) -> MemoryDescriptor {
+    let bytes = &buffer[offset..offset + core::mem::size_of::<MemoryDescriptor>()];
+
+    // SAFETY: `MemoryDescriptor` is plain-old-data and the range was
+    //         bounds-checked above. No alignment is required.
+    unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const MemoryDescriptor) }
+}
+
+fn write_descriptor(buffer: &mut [u8], offset: usize, descriptor: &MemoryDescriptor) {
+    let bytes = &mut buffer[offset..offset + core::mem::size_of::<MemoryDescriptor>()];
+
+    // SAFETY: `MemoryDescriptor` is plain-old-data and the range was
+    //         bounds-checked above. No alignment is required.
+    unsafe { core::ptr::write_unaligned(bytes.as_mut_ptr() as *mut MemoryDescriptor, *descriptor) }
+}
+
+impl<'a> Iterator for MemoryMapIter<'a> {
+    type Item = MemoryDescriptor;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() {
+            None
+        } else {
+            let v = read_descriptor(self.buffer, 0);
+            self.buffer = &self.buffer[self.descriptor_size..];
+            Some(v)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.buffer.len() / self.descriptor_size;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for MemoryMapIter<'_> {}
+
+impl core::fmt::Display for MemoryMapError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            MemoryMapError::DescriptorSize => write!(f, "memory descriptor size too small"),
+            MemoryMapError::DescriptorVersion => write!(f, "unsupported memory descriptor version"),
+            MemoryMapError::MapSize => write!(f, "invalid memory map size"),
+        }
+    }
+}
+
+impl core::error::Error for MemoryMapError {}
+
 //
 // Protocol Management
 //
@@ -531,6 +2855,229 @@ pub struct MemoryAttributesTable<const N: usize = 0> {
     pub entry: [MemoryDescriptor; N],
 }
 
+/// Memory Attributes Table Errors
+///
+/// This is returned when a memory attributes table cannot be walked.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum MemoryAttributesTableError {
+    /// The data ends before the header or the last entry.
+    Truncated,
+    /// The version is neither 1 nor 2.
+    Version,
+    /// The descriptor size is smaller than `MemoryDescriptor`.
+    DescriptorSize,
+}
+
+/// Memory Attributes Table Violations
+///
+/// This describes an entry of a memory attributes table that violates the
+/// invariants of the specification.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum MemoryAttributesViolation {
+    /// The type is neither `RUNTIME_SERVICES_CODE` nor
+    /// `RUNTIME_SERVICES_DATA`.
+    Type,
+    /// Neither `MEMORY_RO` nor `MEMORY_XP` is set, so the range would be
+    /// writable and executable.
+    Attributes,
+    /// The entry starts below the previous entry.
+    Order,
+    /// The entry overlaps the previous entry.
+    Overlap,
+}
+
+/// Memory Attributes Table View
+///
+/// This wraps a memory attributes table, stepping over its entries by the
+/// reported descriptor size. The entries can be checked against the
+/// invariants of the specification via `violations()`, for instance to
+/// decide whether runtime services can be mapped with strict W^X
+/// permissions.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryAttributesTableView<'a> {
+    version: u32,
+    flags: u32,
+    entries: &'a [u8],
+    descriptor_size: usize,
+}
+
+/// Memory Attributes Table Violation Iterator
+///
+/// This iterates the violations of a memory attributes table as pairs of
+/// entry index and violation. See `MemoryAttributesTableView::violations()`.
+#[derive(Clone, Debug)]
+pub struct MemoryAttributesViolations<'a> {
+    entries: core::iter::Enumerate<MemoryMapIter<'a>>,
+    prev: Option<MemoryDescriptor>,
+    pending: [Option<MemoryAttributesViolation>; 3],
+    index: usize,
+}
+
+impl<'a> MemoryAttributesTableView<'a> {
+    /// Parse a Memory Attributes Table
+    ///
+    /// Parse the table in `data`, which starts with the `MemoryAttributesTable`
+    /// header and must include all entries. The bytes need not be aligned.
+    pub fn parse(data: &'a [u8]) -> Result<Self, MemoryAttributesTableError> {
+        let header_size = core::mem::size_of::<MemoryAttributesTable>();
+        if data.len() < header_size {
+            return Err(MemoryAttributesTableError::Truncated);
+        }
+
+        let field = |i: usize| u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+        let version = field(0);
+        let number_of_entries = field(4) as usize;
+        let descriptor_size = field(8) as usize;
+
+        if version != 1 && version != MEMORY_ATTRIBUTES_TABLE_VERSION {
+            return Err(MemoryAttributesTableError::Version);
+        }
+        if descriptor_size < core::mem::size_of::<MemoryDescriptor>() {
+            return Err(MemoryAttributesTableError::DescriptorSize);
+        }
+
+        let entries = number_of_entries
+            .checked_mul(descriptor_size)
+            .and_then(|v| data[header_size..].get(..v))
+            .ok_or(MemoryAttributesTableError::Truncated)?;
+
+        Ok(Self {
+            version,
+            flags: field(12),
+            entries,
+            descriptor_size,
+        })
+    }
+
+    /// Wrap an In-Memory Memory Attributes Table
+    ///
+    /// # Safety
+    ///
+    /// The table must be followed by `number_of_entries` entries of
+    /// `descriptor_size` bytes each, valid for `'a`. This holds for tables
+    /// installed by the firmware, unless they are corrupted.
+    pub unsafe fn from_table(
+        table: &'a MemoryAttributesTable,
+    ) -> Result<Self, MemoryAttributesTableError> {
+        let size = (table.number_of_entries as usize)
+            .saturating_mul(table.descriptor_size as usize)
+            .saturating_add(core::mem::size_of::<MemoryAttributesTable>());
+        Self::parse(core::slice::from_raw_parts(table as *const _ as *const u8, size))
+    }
+
+    /// Return the Table Version
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Return the Table Flags
+    ///
+    /// Return the `MEMORY_ATTRIBUTES_FLAGS_*` bits. These are only defined
+    /// for version 2, so 0 is returned for version 1 tables.
+    pub fn flags(&self) -> u32 {
+        if self.version >= MEMORY_ATTRIBUTES_TABLE_VERSION {
+            self.flags
+        } else {
+            0
+        }
+    }
+
+    /// Return the Number of Entries
+    pub fn len(&self) -> usize {
+        self.entries.len() / self.descriptor_size
+    }
+
+    /// Check whether the Table has no Entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate the Entries
+    pub fn entries(&self) -> MemoryMapIter<'a> {
+        MemoryMapIter {
+            buffer: self.entries,
+            descriptor_size: self.descriptor_size,
+        }
+    }
+
+    /// Iterate the Violations
+    ///
+    /// Check every entry against the invariants of the specification: only
+    /// runtime services code and data are described, every entry is either
+    /// read-only or non-executable, and entries are sorted by physical
+    /// address without overlaps. Note that firmware commonly reports the
+    /// data sections of runtime images as non-executable
+    /// `RUNTIME_SERVICES_CODE`, so the type alone does not imply either
+    /// attribute. Each violation is reported with the index
+    /// of its entry. Ordering is checked against the preceding entry only.
+    pub fn violations(&self) -> MemoryAttributesViolations<'a> {
+        MemoryAttributesViolations {
+            entries: self.entries().enumerate(),
+            prev: None,
+            pending: [None; 3],
+            index: 0,
+        }
+    }
+
+    /// Verify the Table
+    ///
+    /// Return the first violation, if any. See `violations()`.
+    pub fn verify(&self) -> Result<(), (usize, MemoryAttributesViolation)> {
+        match self.violations().next() {
+            Some(v) => Err(v),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Iterator for MemoryAttributesViolations<'a> {
+    type Item = (usize, MemoryAttributesViolation);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(v) = self.pending.iter_mut().find_map(|v| v.take()) {
+                return Some((self.index, v));
+            }
+
+            let (index, d) = self.entries.next()?;
+            self.index = index;
+
+            if d.r#type != RUNTIME_SERVICES_CODE && d.r#type != RUNTIME_SERVICES_DATA {
+                self.pending[0] = Some(MemoryAttributesViolation::Type);
+            }
+            if d.attribute & (MEMORY_RO | MEMORY_XP) == 0 {
+                self.pending[1] = Some(MemoryAttributesViolation::Attributes);
+            }
+            if let Some(prev) = self.prev {
+                if d.physical_start < prev.physical_start {
+                    self.pending[2] = Some(MemoryAttributesViolation::Order);
+                } else if d.physical_start < prev.physical_end() {
+                    self.pending[2] = Some(MemoryAttributesViolation::Overlap);
+                }
+            }
+            self.prev = Some(d);
+        }
+    }
+}
+
+impl core::fmt::Display for MemoryAttributesTableError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            MemoryAttributesTableError::Truncated => write!(f, "memory attributes table truncated"),
+            MemoryAttributesTableError::Version => {
+                write!(f, "unsupported memory attributes table version")
+            }
+            MemoryAttributesTableError::DescriptorSize => {
+                write!(f, "memory descriptor size too small")
+            }
+        }
+    }
+}
+
+impl core::error::Error for MemoryAttributesTableError {}
+
 pub const CONFORMANCE_PROFILES_TABLE_GUID: crate::base::Guid = crate::base::Guid::from_fields(
     0x36122546,
     0xf7e7,
@@ -726,6 +3273,98 @@ pub struct TableHeader {
     pub reserved: u32,
 }
 
+/// Table Header Errors
+///
+/// This is returned when verification of a table header fails.
+#[derive(Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq)]
+pub enum TableHeaderError {
+    /// The signature does not match the expected table.
+    Signature,
+    /// The major revision is 0, or the minor revision exceeds 99.
+    Revision,
+    /// The header size is smaller than `TableHeader`, or exceeds the table.
+    HeaderSize,
+    /// The checksum does not match.
+    Crc32,
+}
+
+impl TableHeader {
+    const OFFSET_CRC32: usize = 16;
+
+    /// Return the Major Revision
+    pub fn revision_major(&self) -> u16 {
+        (self.revision >> 16) as u16
+    }
+
+    /// Return the Minor Revision
+    ///
+    /// The minor revision encodes two decimal digits, so 2.3 is reported as
+    /// 30, and 2.31 as 31.
+    pub fn revision_minor(&self) -> u16 {
+        self.revision as u16
+    }
+
+    /// Verify a Table
+    ///
+    /// Verify the table in `table`, which starts with a table header. The
+    /// signature must match `signature`, the revision must be sane, and the
+    /// CRC32 of the first `header_size` bytes with the `crc32` field zeroed
+    /// must match the `crc32` field. The bytes need not be aligned. On
+    /// success, a copy of the header is returned.
+    pub fn verify(table: &[u8], signature: u64) -> Result<Self, TableHeaderError> {
+        if table.len() < core::mem::size_of::<Self>() {
+            return Err(TableHeaderError::HeaderSize);
+        }
+
+        // SAFETY: `TableHeader` is plain-old-data and the range was
+        //         bounds-checked above. No alignment is required.
+        let header = unsafe { core::ptr::read_unaligned(table.as_ptr() as *const Self) };
+
+        if header.signature != signature {
+            return Err(TableHeaderError::Signature);
+        }
+        if header.revision_major() == 0 || header.revision_minor() > 99 {
+            return Err(TableHeaderError::Revision);
+        }
+
+        let header_size = header.header_size as usize;
+        if header_size < core::mem::size_of::<Self>() || header_size > table.len() {
+            return Err(TableHeaderError::HeaderSize);
+        }
+
+        let mut crc = crate::base::crc32(0, &table[..Self::OFFSET_CRC32]);
+        crc = crate::base::crc32(crc, &[0; 4]);
+        crc = crate::base::crc32(crc, &table[Self::OFFSET_CRC32 + 4..header_size]);
+        if crc != header.crc32 {
+            return Err(TableHeaderError::Crc32);
+        }
+
+        Ok(header)
+    }
+
+    // Verify the table that starts with this header, trusting `header_size`
+    // to describe accessible memory.
+    unsafe fn verify_in_place(&self, signature: u64) -> Result<(), TableHeaderError> {
+        let size = (self.header_size as usize).max(core::mem::size_of::<Self>());
+        let table = core::slice::from_raw_parts(self as *const Self as *const u8, size);
+        Self::verify(table, signature).map(|_| ())
+    }
+}
+
+impl core::fmt::Display for TableHeaderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            TableHeaderError::Signature => write!(f, "invalid table signature"),
+            TableHeaderError::Revision => write!(f, "invalid table revision"),
+            TableHeaderError::HeaderSize => write!(f, "invalid table header size"),
+            TableHeaderError::Crc32 => write!(f, "table checksum mismatch"),
+        }
+    }
+}
+
+impl core::error::Error for TableHeaderError {}
+
 pub const RUNTIME_SERVICES_SIGNATURE: u64 = 0x56524553544e5552u64; // "RUNTSERV"
 pub const RUNTIME_SERVICES_REVISION: u32 = SPECIFICATION_REVISION;
 
@@ -838,6 +3477,21 @@ pub struct RuntimeServices {
     pub query_variable_info: RuntimeQueryVariableInfo,
 }
 
+impl RuntimeServices {
+    /// Verify the Table Header
+    ///
+    /// Verify the runtime services table as described in `TableHeader::verify()`, expecting
+    /// `RUNTIME_SERVICES_SIGNATURE`.
+    ///
+    /// # Safety
+    ///
+    /// `header_size` bytes starting at `self` must be accessible. This holds
+    /// for tables passed by the firmware, unless they are corrupted.
+    pub unsafe fn verify(&self) -> Result<(), TableHeaderError> {
+        self.hdr.verify_in_place(RUNTIME_SERVICES_SIGNATURE)
+    }
+}
+
 pub const BOOT_SERVICES_SIGNATURE: u64 = 0x56524553544f4f42u64; // "BOOTSERV"
 pub const BOOT_SERVICES_REVISION: u32 = SPECIFICATION_REVISION;
 
@@ -1169,6 +3823,21 @@ pub struct BootServices {
     pub create_event_ex: BootCreateEventEx,
 }
 
+impl BootServices {
+    /// Verify the Table Header
+    ///
+    /// Verify the boot services table as described in `TableHeader::verify()`, expecting
+    /// `BOOT_SERVICES_SIGNATURE`.
+    ///
+    /// # Safety
+    ///
+    /// `header_size` bytes starting at `self` must be accessible. This holds
+    /// for tables passed by the firmware, unless they are corrupted.
+    pub unsafe fn verify(&self) -> Result<(), TableHeaderError> {
+        self.hdr.verify_in_place(BOOT_SERVICES_SIGNATURE)
+    }
+}
+
 pub const SYSTEM_TABLE_REVISION_2_70: u32 = (2 << 16) | (70);
 pub const SYSTEM_TABLE_REVISION_2_60: u32 = (2 << 16) | (60);
 pub const SYSTEM_TABLE_REVISION_2_50: u32 = (2 << 16) | (50);
@@ -1203,3 +3872,1123 @@ pub struct SystemTable {
     pub number_of_table_entries: usize,
     pub configuration_table: *mut ConfigurationTable,
 }
+
+impl SystemTable {
+    /// Verify the Table Header
+    ///
+    /// Verify the system table as described in `TableHeader::verify()`,
+    /// expecting `SYSTEM_TABLE_SIGNATURE`. The boot services and runtime
+    /// services tables are not verified, see their `verify()` functions.
+    ///
+    /// # Safety
+    ///
+    /// `header_size` bytes starting at `self` must be accessible. This holds
+    /// for tables passed by the firmware, unless they are corrupted.
+    pub unsafe fn verify(&self) -> Result<(), TableHeaderError> {
+        self.hdr.verify_in_place(SYSTEM_TABLE_SIGNATURE)
+    }
+
+    /// Access the Configuration Tables
+    ///
+    /// Return a view of the configuration tables referenced by this system
+    /// table. A null table pointer yields an empty view.
+    ///
+    /// # Safety
+    ///
+    /// The system table must be valid, as passed by the firmware. That is,
+    /// `configuration_table` must point to `number_of_table_entries` entries,
+    /// and the entries must satisfy the requirements of
+    /// `ConfigurationTables::from_slice()` for the lifetime of `self`.
+    pub unsafe fn configuration_tables(&self) -> ConfigurationTables<'_> {
+        if self.configuration_table.is_null() || self.number_of_table_entries == 0 {
+            ConfigurationTables::from_slice(&[])
+        } else {
+            ConfigurationTables::from_slice(core::slice::from_raw_parts(
+                self.configuration_table,
+                self.number_of_table_entries,
+            ))
+        }
+    }
+}
+
+/// Configuration Table View
+///
+/// This wraps the configuration tables of a system table, allowing to
+/// iterate them and look them up by GUID. Typed accessors are provided for
+/// the tables defined in this module. Tables of external standards (ACPI,
+/// SMBIOS, device trees) are returned as raw pointers.
+#[derive(Clone, Copy, Debug)]
+pub struct ConfigurationTables<'a> {
+    tables: &'a [ConfigurationTable],
+}
+
+impl<'a> ConfigurationTables<'a> {
+    /// Create Configuration Table View
+    ///
+    /// # Safety
+    ///
+    /// The `vendor_table` pointer of every entry whose GUID has a typed
+    /// accessor on this view must either be null, or point to a valid,
+    /// properly aligned table of the respective type that outlives `'a` and
+    /// is not modified during `'a`.
+    pub unsafe fn from_slice(tables: &'a [ConfigurationTable]) -> Self {
+        Self { tables }
+    }
+
+    /// Return the Configuration Tables as Slice
+    pub fn as_slice(&self) -> &'a [ConfigurationTable] {
+        self.tables
+    }
+
+    /// Iterate the Configuration Tables
+    pub fn iter(&self) -> core::slice::Iter<'a, ConfigurationTable> {
+        self.tables.iter()
+    }
+
+    /// Find a Configuration Table
+    ///
+    /// Return the vendor table pointer of the first entry with the GUID
+    /// `guid`, or `None` if there is none or it is null.
+    pub fn find(&self, guid: &crate::base::Guid) -> Option<*mut core::ffi::c_void> {
+        self.tables
+            .iter()
+            .find(|v| v.vendor_guid == *guid)
+            .map(|v| v.vendor_table)
+            .filter(|v| !v.is_null())
+    }
+
+    unsafe fn find_as<T>(&self, guid: &crate::base::Guid) -> Option<&'a T> {
+        self.find(guid).map(|v| &*(v as *const T))
+    }
+
+    /// Return the `RT_PROPERTIES_TABLE_GUID` Table
+    pub fn rt_properties(&self) -> Option<&'a RtPropertiesTable> {
+        // SAFETY: Guaranteed by `from_slice()`.
+        unsafe { self.find_as(&RT_PROPERTIES_TABLE_GUID) }
+    }
+
+    /// Return the `MEMORY_ATTRIBUTES_TABLE_GUID` Table
+    ///
+    /// Only the header is accessible via the returned reference. The entries
+    /// follow it in memory.
+    pub fn memory_attributes(&self) -> Option<&'a MemoryAttributesTable> {
+        // SAFETY: Guaranteed by `from_slice()`.
+        unsafe { self.find_as(&MEMORY_ATTRIBUTES_TABLE_GUID) }
+    }
+
+    /// Return the `CONFORMANCE_PROFILES_TABLE_GUID` Table
+    ///
+    /// Only the header is accessible via the returned reference. The profile
+    /// GUIDs follow it in memory.
+    pub fn conformance_profiles(&self) -> Option<&'a ConformanceProfilesTable> {
+        // SAFETY: Guaranteed by `from_slice()`.
+        unsafe { self.find_as(&CONFORMANCE_PROFILES_TABLE_GUID) }
+    }
+
+    /// Return the `DEBUG_IMAGE_INFO_TABLE_GUID` Table
+    pub fn debug_image_info(&self) -> Option<&'a DebugImageInfoTableHeader> {
+        // SAFETY: Guaranteed by `from_slice()`.
+        unsafe { self.find_as(&DEBUG_IMAGE_INFO_TABLE_GUID) }
+    }
+
+    /// Return the ACPI RSDP
+    ///
+    /// Return the pointer to the ACPI root system description pointer. The
+    /// ACPI 2.0+ table is preferred over the ACPI 1.0 table.
+    pub fn acpi(&self) -> Option<*mut core::ffi::c_void> {
+        self.find(&ACPI_20_TABLE_GUID)
+            .or_else(|| self.find(&ACPI_10_TABLE_GUID))
+    }
+
+    /// Return the SMBIOS 2.x Entry Point
+    pub fn smbios(&self) -> Option<*mut core::ffi::c_void> {
+        self.find(&SMBIOS_TABLE_GUID)
+    }
+
+    /// Return the SMBIOS 3.x Entry Point
+    pub fn smbios3(&self) -> Option<*mut core::ffi::c_void> {
+        self.find(&SMBIOS3_TABLE_GUID)
+    }
+
+    /// Return the Flattened Device Tree
+    pub fn dtb(&self) -> Option<*mut core::ffi::c_void> {
+        self.find(&DTB_TABLE_GUID)
+    }
+}
+
+impl<'a> IntoIterator for ConfigurationTables<'a> {
+    type Item = &'a ConfigurationTable;
+    type IntoIter = core::slice::Iter<'a, ConfigurationTable>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tables.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn time(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        timezone: i16,
+    ) -> Time {
+        Time {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            timezone,
+            ..Default::default()
+        }
+    }
+
+    // Verify that the spec ranges are enforced, including leap years.
+    #[test]
+    fn time_validate() {
+        assert!(time(2024, 2, 29, 23, 59, 59, 0).is_valid());
+        assert!(time(2000, 2, 29, 0, 0, 0, 1440).is_valid());
+        assert!(time(1900, 1, 1, 0, 0, 0, -1440).is_valid());
+        assert!(time(9999, 12, 31, 0, 0, 0, UNSPECIFIED_TIMEZONE).is_valid());
+
+        assert_eq!(time(1899, 1, 1, 0, 0, 0, 0).validate(), Err(TimeError::Year));
+        assert_eq!(time(2024, 13, 1, 0, 0, 0, 0).validate(), Err(TimeError::Month));
+        assert_eq!(time(2023, 2, 29, 0, 0, 0, 0).validate(), Err(TimeError::Day));
+        assert_eq!(time(1900, 2, 29, 0, 0, 0, 0).validate(), Err(TimeError::Day));
+        assert_eq!(time(2024, 4, 31, 0, 0, 0, 0).validate(), Err(TimeError::Day));
+        assert_eq!(time(2024, 1, 1, 24, 0, 0, 0).validate(), Err(TimeError::Hour));
+        assert_eq!(time(2024, 1, 1, 0, 60, 0, 0).validate(), Err(TimeError::Minute));
+        assert_eq!(time(2024, 1, 1, 0, 0, 60, 0).validate(), Err(TimeError::Second));
+        assert_eq!(time(2024, 1, 1, 0, 0, 0, 1441).validate(), Err(TimeError::TimeZone));
+
+        let mut t = time(2024, 1, 1, 0, 0, 0, 0);
+        t.nanosecond = 1_000_000_000;
+        assert_eq!(t.validate(), Err(TimeError::Nanosecond));
+        t.nanosecond = 0;
+        t.daylight = TIME_ADJUST_DAYLIGHT | TIME_IN_DAYLIGHT;
+        assert!(t.is_valid());
+        t.daylight = 0x04;
+        assert_eq!(t.validate(), Err(TimeError::Daylight));
+    }
+
+    // Verify conversions to and from Unix time, and normalization to UTC.
+    #[test]
+    fn time_unix() {
+        assert_eq!(time(1970, 1, 1, 0, 0, 0, 0).to_unix(), Ok(0));
+        assert_eq!(time(2000, 3, 1, 0, 0, 0, 0).to_unix(), Ok(951868800));
+        assert_eq!(time(2024, 2, 29, 12, 0, 0, 0).to_unix(), Ok(1709208000));
+        assert_eq!(time(2024, 2, 29, 13, 0, 0, 60).to_unix(), Ok(1709208000));
+        assert_eq!(time(2024, 2, 29, 12, 0, 0, UNSPECIFIED_TIMEZONE).to_unix(), Ok(1709208000));
+        assert_eq!(time(1900, 1, 1, 0, 0, 0, 0).to_unix(), Ok(-2208988800));
+        assert_eq!(time(2024, 2, 30, 0, 0, 0, 0).to_unix(), Err(TimeError::Day));
+
+        let t = Time::from_unix(1709208000, 500).unwrap();
+        assert_eq!((t.year, t.month, t.day, t.hour, t.minute, t.second), (2024, 2, 29, 12, 0, 0));
+        assert_eq!((t.nanosecond, t.timezone, t.daylight), (500, 0, 0));
+
+        let t = Time::from_unix(-2208988800 - 1, 0);
+        assert_eq!(t.unwrap_err(), TimeError::Year);
+        let t = Time::from_unix(253402300799, 0).unwrap();
+        assert_eq!(
+            (t.year, t.month, t.day, t.hour, t.minute, t.second),
+            (9999, 12, 31, 23, 59, 59)
+        );
+        assert_eq!(
+            Time::from_unix(253402300800, 0).unwrap_err(),
+            TimeError::Year
+        );
+
+        // Round-trip every day across several leap-year boundaries.
+        for d in -800..800 {
+            let s = 951868800 + d * 86400 + 3723;
+            assert_eq!(Time::from_unix(s, 0).unwrap().to_unix(), Ok(s));
+        }
+
+        let mut t = time(2024, 1, 1, 0, 30, 0, 90);
+        t.daylight = TIME_IN_DAYLIGHT;
+        let u = t.to_utc().unwrap();
+        assert_eq!((u.year, u.month, u.day, u.hour, u.minute), (2023, 12, 31, 23, 0));
+        assert_eq!((u.timezone, u.daylight), (0, 0));
+    }
+
+    // Verify ordering takes time zones into account, and the RFC 3339
+    // formatting of all time zone variants.
+    #[test]
+    fn time_order_and_format() {
+        use core::cmp::Ordering;
+
+        let a = time(2024, 6, 1, 12, 0, 0, 120);
+        let b = time(2024, 6, 1, 11, 0, 0, 0);
+        let c = time(2024, 6, 1, 10, 0, 0, 0);
+        assert_eq!(a.cmp_instant(&b), Ordering::Less);
+        assert_eq!(a.cmp_instant(&c), Ordering::Equal);
+        assert_eq!(b.cmp_instant(&a), Ordering::Greater);
+
+        let mut d = c;
+        d.nanosecond = 1;
+        assert_eq!(c.cmp_instant(&d), Ordering::Less);
+
+        assert_eq!(format!("{}", a.rfc3339()), "2024-06-01T12:00:00+02:00");
+        assert_eq!(format!("{}", b.rfc3339()), "2024-06-01T11:00:00Z");
+        assert_eq!(
+            format!("{}", time(1999, 12, 31, 23, 59, 59, UNSPECIFIED_TIMEZONE).rfc3339()),
+            "1999-12-31T23:59:59-00:00",
+        );
+        d.nanosecond = 500_000_000;
+        assert_eq!(format!("{}", d.rfc3339()), "2024-06-01T10:00:00.5Z");
+        d.nanosecond = 1_000;
+        assert_eq!(format!("{}", d.rfc3339()), "2024-06-01T10:00:00.000001Z");
+    }
+
+    fn memory_map_buffer(descriptors: &[MemoryDescriptor], descriptor_size: usize) -> Vec<u8> {
+        let mut buffer = vec![0xffu8; descriptors.len() * descriptor_size];
+        for (i, d) in descriptors.iter().enumerate() {
+            write_descriptor(&mut buffer, i * descriptor_size, d);
+        }
+        buffer
+    }
+
+    fn desc(r#type: MemoryType, physical_start: u64, number_of_pages: u64) -> MemoryDescriptor {
+        MemoryDescriptor {
+            r#type,
+            physical_start,
+            virtual_start: 0,
+            number_of_pages,
+            attribute: MEMORY_WB,
+        }
+    }
+
+    // Verify parameter validation, and that descriptors are stepped by the
+    // reported size rather than the size of `MemoryDescriptor`.
+    #[test]
+    fn memory_map_iter() {
+        let ds = core::mem::size_of::<MemoryDescriptor>() + 8;
+        let descs = [
+            desc(CONVENTIONAL_MEMORY, 0x0000, 1),
+            desc(LOADER_DATA, 0x1000, 2),
+            desc(CONVENTIONAL_MEMORY, 0x10000, 16),
+        ];
+        let buffer = memory_map_buffer(&descs, ds);
+
+        assert_eq!(
+            MemoryMap::new(&buffer[..], buffer.len(), ds, 2).unwrap_err(),
+            MemoryMapError::DescriptorVersion,
+        );
+        assert_eq!(
+            MemoryMap::new(&buffer[..], buffer.len(), 24, MEMORY_DESCRIPTOR_VERSION).unwrap_err(),
+            MemoryMapError::DescriptorSize,
+        );
+        assert_eq!(
+            MemoryMap::new(
+                &buffer[..],
+                buffer.len() + ds,
+                ds,
+                MEMORY_DESCRIPTOR_VERSION
+            )
+            .unwrap_err(),
+            MemoryMapError::MapSize,
+        );
+        assert_eq!(
+            MemoryMap::new(&buffer[..], buffer.len() - 1, ds, MEMORY_DESCRIPTOR_VERSION)
+                .unwrap_err(),
+            MemoryMapError::MapSize,
+        );
+
+        // Unaligned buffer, too.
+        let mut unaligned = vec![0u8; 1];
+        unaligned.extend_from_slice(&buffer);
+        let map =
+            MemoryMap::new(&unaligned[1..], buffer.len(), ds, MEMORY_DESCRIPTOR_VERSION).unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.iter().len(), 3);
+        assert_eq!(
+            map.iter().map(|v| v.physical_start).collect::<Vec<_>>(),
+            [0x0000, 0x1000, 0x10000]
+        );
+        assert_eq!(map.get(1).unwrap().r#type, LOADER_DATA);
+        assert!(map.get(3).is_none());
+
+        assert_eq!(map.find(0x0fff).unwrap().r#type, CONVENTIONAL_MEMORY);
+        assert_eq!(map.find(0x2fff).unwrap().r#type, LOADER_DATA);
+        assert!(map.find(0x3000).is_none());
+        assert_eq!(map.find(0x1ffff).unwrap().physical_start, 0x10000);
+        assert!(map.find(0x20000).is_none());
+
+        assert_eq!(map.total_pages(CONVENTIONAL_MEMORY), 17);
+        assert_eq!(map.total_pages(0x80000000), 0);
+        let totals = map.totals();
+        assert_eq!(totals[CONVENTIONAL_MEMORY as usize], 17);
+        assert_eq!(totals[LOADER_DATA as usize], 2);
+        assert_eq!(totals.iter().sum::<u64>(), 19);
+    }
+
+    // Verify sorting and coalescing, preserving trailing descriptor bytes.
+    #[test]
+    fn memory_map_coalesce() {
+        let ds = core::mem::size_of::<MemoryDescriptor>() + 8;
+        let mut other = desc(CONVENTIONAL_MEMORY, 0x5000, 1);
+        other.attribute |= MEMORY_RUNTIME;
+        let descs = [
+            desc(CONVENTIONAL_MEMORY, 0x3000, 2),
+            desc(CONVENTIONAL_MEMORY, 0x0000, 1),
+            desc(BOOT_SERVICES_DATA, 0x2000, 1),
+            desc(CONVENTIONAL_MEMORY, 0x1000, 1),
+            other,
+            desc(CONVENTIONAL_MEMORY, 0x6000, 1),
+            desc(CONVENTIONAL_MEMORY, 0x8000, 1),
+        ];
+        let mut buffer = memory_map_buffer(&descs, ds);
+        let mut map = MemoryMap::new(
+            &mut buffer[..],
+            descs.len() * ds,
+            ds,
+            MEMORY_DESCRIPTOR_VERSION,
+        )
+        .unwrap();
+
+        map.sort();
+        assert_eq!(
+            map.iter().map(|v| v.physical_start).collect::<Vec<_>>(),
+            [0x0000, 0x1000, 0x2000, 0x3000, 0x5000, 0x6000, 0x8000],
+        );
+
+        map.coalesce();
+        assert_eq!(
+            map.iter().map(|v| (v.physical_start, v.number_of_pages)).collect::<Vec<_>>(),
+            [(0x0000, 2), (0x2000, 1), (0x3000, 2), (0x5000, 1), (0x6000, 1), (0x8000, 1)],
+        );
+        assert_eq!(map.map_size(), 6 * ds);
+        assert_eq!(map.total_pages(CONVENTIONAL_MEMORY), 7);
+
+        for i in 0..map.len() {
+            assert_eq!(buffer[i * ds + ds - 8..(i + 1) * ds], [0xff; 8]);
+        }
+    }
+
+    // A file path list with two instances: a hard-drive node plus a file
+    // node, and a bare vendor hardware node.
+    const FILE_PATH_LIST: [u8; 20] = [
+        0x04, 0x04, 0x08, 0x00, b'a', 0x00, 0x00, 0x00,
+        0x7f, 0x01, 0x04, 0x00,
+        0x01, 0x04, 0x04, 0x00,
+        0x7f, 0xff, 0x04, 0x00,
+    ];
+
+    // Verify load options round-trip through the serializer and parser, and
+    // that malformed data is rejected.
+    #[test]
+    fn load_option() {
+        let mut buffer = [0u8; 64];
+
+        assert_eq!(
+            write_load_option(
+                &mut buffer[..8],
+                LOAD_OPTION_ACTIVE,
+                "Linux",
+                &FILE_PATH_LIST,
+                b"xyz"
+            ),
+            Err(LoadOptionError::BufferTooSmall(6 + 12 + 20 + 3)),
+        );
+        assert_eq!(
+            write_load_option(&mut buffer, 0, "a\0b", &FILE_PATH_LIST, &[]),
+            Err(LoadOptionError::Description),
+        );
+        assert_eq!(
+            write_load_option(&mut buffer, 0, "\u{1f600}", &FILE_PATH_LIST, &[]),
+            Err(LoadOptionError::Description),
+        );
+        assert_eq!(
+            write_load_option(&mut buffer, 0, "a", &FILE_PATH_LIST[..16], &[]),
+            Err(LoadOptionError::FilePathList),
+        );
+
+        let attributes = LOAD_OPTION_ACTIVE | LOAD_OPTION_HIDDEN | LOAD_OPTION_CATEGORY_APP;
+        let n =
+            write_load_option(&mut buffer, attributes, "Lïnux", &FILE_PATH_LIST, b"xyz").unwrap();
+        assert_eq!(n, 41);
+        assert_eq!(&buffer[..8], &[0x09, 0x01, 0x00, 0x00, 20, 0x00, b'L', 0x00]);
+
+        let option = LoadOption::parse(&buffer[..n]).unwrap();
+        assert_eq!(option.attributes, attributes);
+        assert!(option.is_active());
+        assert!(option.is_hidden());
+        assert_eq!(option.category(), LOAD_OPTION_CATEGORY_APP);
+        assert_eq!(option.description_chars().collect::<String>(), "Lïnux");
+        assert_eq!(option.description_ucs2().count(), 5);
+        assert_eq!(option.file_path_list, &FILE_PATH_LIST);
+        assert_eq!(option.optional_data, b"xyz");
+        assert_eq!(
+            option.file_paths().collect::<Vec<_>>(),
+            [&FILE_PATH_LIST[..8], &FILE_PATH_LIST[12..16]],
+        );
+
+        let mut copy = [0u8; 64];
+        assert_eq!(option.size(), n);
+        assert_eq!(option.write(&mut copy), Ok(n));
+        assert_eq!(copy[..n], buffer[..n]);
+
+        assert_eq!(LoadOption::parse(&buffer[..5]), Err(LoadOptionError::Truncated));
+        assert_eq!(LoadOption::parse(&buffer[..12]), Err(LoadOptionError::Description));
+        assert_eq!(LoadOption::parse(&buffer[..30]), Err(LoadOptionError::Truncated));
+
+        // Node length pointing past the list, and a list not terminated by an
+        // end-of-entire-path node.
+        buffer[20] = 0x40;
+        assert_eq!(LoadOption::parse(&buffer[..n]), Err(LoadOptionError::FilePathList));
+        buffer[20] = 0x08;
+        buffer[35] = 0x01;
+        assert_eq!(LoadOption::parse(&buffer[..n]), Err(LoadOptionError::FilePathList));
+    }
+
+    // Verify the global variable catalog lookup, and the handling of
+    // numbered variable names.
+    #[test]
+    fn global_variables() {
+        let v = GlobalVariable::lookup("BootOrder").unwrap();
+        assert_eq!(
+            v.attributes,
+            VARIABLE_NON_VOLATILE | VARIABLE_BOOTSERVICE_ACCESS | VARIABLE_RUNTIME_ACCESS
+        );
+        assert_eq!(v.format, GlobalVariableFormat::Uint16Array);
+        assert!(!v.is_numbered());
+
+        let v = GlobalVariable::lookup("Boot00A1").unwrap();
+        assert_eq!(v.name, "Boot####");
+        assert_eq!(v.format, GlobalVariableFormat::LoadOption);
+        assert!(v.is_numbered());
+
+        assert_eq!(
+            GlobalVariable::lookup("PlatformRecovery0000")
+                .unwrap()
+                .attributes
+                & VARIABLE_NON_VOLATILE,
+            0
+        );
+        assert_ne!(
+            GlobalVariable::lookup("PK").unwrap().attributes
+                & VARIABLE_TIME_BASED_AUTHENTICATED_WRITE_ACCESS,
+            0
+        );
+        assert_eq!(
+            GlobalVariable::lookup("Key0001").unwrap().format,
+            GlobalVariableFormat::KeyOption
+        );
+        assert!(GlobalVariable::lookup("Boot00a1").is_none());
+        assert!(GlobalVariable::lookup("Boot####").is_none());
+        assert!(GlobalVariable::lookup("Foo0001").is_none());
+
+        for v in GLOBAL_VARIABLES {
+            assert!(v.attributes & VARIABLE_BOOTSERVICE_ACCESS != 0);
+            assert_eq!(GlobalVariable::lookup(v.name).unwrap_or(v), v);
+        }
+
+        // The catalog is sorted case-insensitively.
+        let key = |v: &GlobalVariable| v.name.bytes().map(|c| c.to_ascii_lowercase());
+        assert!(GLOBAL_VARIABLES.windows(2).all(|v| key(&v[0]).lt(key(&v[1]))));
+
+        let mut buffer = [0u8; 20];
+        assert_eq!(
+            format_numbered_variable_name("Boot", 0x2a, &mut buffer),
+            Some("Boot002A")
+        );
+        assert_eq!(
+            format_numbered_variable_name("SysPrep", 0xffff, &mut buffer),
+            Some("SysPrepFFFF")
+        );
+        assert_eq!(
+            format_numbered_variable_name("PlatformRecovery", 1, &mut buffer),
+            Some("PlatformRecovery0001")
+        );
+        assert_eq!(
+            format_numbered_variable_name("PlatformRecovery", 1, &mut buffer[..19]),
+            None
+        );
+
+        assert_eq!(parse_numbered_variable_name("Boot002A"), Some(("Boot", 0x2a)));
+        assert_eq!(parse_numbered_variable_name("DriverFFFF"), Some(("Driver", 0xffff)));
+        assert_eq!(parse_numbered_variable_name("Boot002a"), None);
+        assert_eq!(parse_numbered_variable_name("Boot02A"), None);
+        assert_eq!(parse_numbered_variable_name("Boot1002A"), None);
+        assert_eq!(parse_numbered_variable_name("0001"), None);
+        assert_eq!(parse_numbered_variable_name("Boot+01A"), None);
+        assert_eq!(parse_numbered_variable_name("Bootä001"), None);
+    }
+
+    // Verify configuration table lookup and the typed accessors.
+    #[test]
+    fn configuration_tables() {
+        let mut rt = RtPropertiesTable {
+            version: RT_PROPERTIES_TABLE_VERSION,
+            length: core::mem::size_of::<RtPropertiesTable>() as u16,
+            runtime_services_supported: RT_SUPPORTED_GET_TIME,
+        };
+        let mut acpi10 = 10u64;
+        let mut acpi20 = 20u64;
+        let entries = [
+            ConfigurationTable {
+                vendor_guid: ACPI_10_TABLE_GUID,
+                vendor_table: &mut acpi10 as *mut _ as *mut core::ffi::c_void,
+            },
+            ConfigurationTable {
+                vendor_guid: RT_PROPERTIES_TABLE_GUID,
+                vendor_table: &mut rt as *mut _ as *mut core::ffi::c_void,
+            },
+            ConfigurationTable {
+                vendor_guid: ACPI_20_TABLE_GUID,
+                vendor_table: &mut acpi20 as *mut _ as *mut core::ffi::c_void,
+            },
+            ConfigurationTable {
+                vendor_guid: DTB_TABLE_GUID,
+                vendor_table: core::ptr::null_mut(),
+            },
+        ];
+
+        let tables = unsafe { ConfigurationTables::from_slice(&entries) };
+        assert_eq!(tables.iter().count(), 4);
+        assert_eq!(tables.into_iter().count(), 4);
+        assert_eq!(tables.find(&ACPI_10_TABLE_GUID), Some(entries[0].vendor_table));
+        assert_eq!(tables.acpi(), Some(entries[2].vendor_table));
+        assert_eq!(
+            tables.rt_properties().unwrap().runtime_services_supported,
+            RT_SUPPORTED_GET_TIME
+        );
+        assert!(tables.memory_attributes().is_none());
+        assert!(tables.smbios().is_none());
+        assert!(tables.dtb().is_none());
+
+        let tables = unsafe { ConfigurationTables::from_slice(&entries[..2]) };
+        assert_eq!(tables.acpi(), Some(entries[0].vendor_table));
+    }
+
+    // Verify table header checks on a hand-crafted table, and on an in-place
+    // table via the typed helpers.
+    #[test]
+    fn table_header() {
+        let mut table = [0u8; 40];
+        table[0..8].copy_from_slice(&SYSTEM_TABLE_SIGNATURE.to_le_bytes());
+        table[8..12].copy_from_slice(&SYSTEM_TABLE_REVISION_2_70.to_le_bytes());
+        table[12..16].copy_from_slice(&32u32.to_le_bytes());
+        table[24..32].copy_from_slice(&[0x5a; 8]);
+        table[32..40].copy_from_slice(&[0xa5; 8]);
+        let crc = crate::base::crc32(0, &table[..32]);
+        table[16..20].copy_from_slice(&crc.to_le_bytes());
+
+        let header = TableHeader::verify(&table, SYSTEM_TABLE_SIGNATURE).unwrap();
+        assert_eq!((header.revision_major(), header.revision_minor()), (2, 70));
+        assert_eq!(header.crc32, crc);
+
+        // Bytes beyond the header size are not covered.
+        table[39] = 0;
+        assert!(TableHeader::verify(&table, SYSTEM_TABLE_SIGNATURE).is_ok());
+
+        assert_eq!(
+            TableHeader::verify(&table, BOOT_SERVICES_SIGNATURE).unwrap_err(),
+            TableHeaderError::Signature
+        );
+        assert_eq!(
+            TableHeader::verify(&table[..23], SYSTEM_TABLE_SIGNATURE).unwrap_err(),
+            TableHeaderError::HeaderSize
+        );
+        assert_eq!(
+            TableHeader::verify(&table[..31], SYSTEM_TABLE_SIGNATURE).unwrap_err(),
+            TableHeaderError::HeaderSize
+        );
+        table[31] = 0;
+        assert_eq!(
+            TableHeader::verify(&table, SYSTEM_TABLE_SIGNATURE).unwrap_err(),
+            TableHeaderError::Crc32
+        );
+        table[8..12].copy_from_slice(&((2u32 << 16) | 100).to_le_bytes());
+        assert_eq!(
+            TableHeader::verify(&table, SYSTEM_TABLE_SIGNATURE).unwrap_err(),
+            TableHeaderError::Revision
+        );
+        table[8..12].copy_from_slice(&70u32.to_le_bytes());
+        assert_eq!(
+            TableHeader::verify(&table, SYSTEM_TABLE_SIGNATURE).unwrap_err(),
+            TableHeaderError::Revision
+        );
+
+        let mut st = SystemTable {
+            hdr: TableHeader {
+                signature: SYSTEM_TABLE_SIGNATURE,
+                revision: SYSTEM_TABLE_REVISION,
+                header_size: core::mem::size_of::<SystemTable>() as u32,
+                crc32: 0,
+                reserved: 0,
+            },
+            firmware_vendor: core::ptr::null_mut(),
+            firmware_revision: 0x1234,
+            console_in_handle: core::ptr::null_mut(),
+            con_in: core::ptr::null_mut(),
+            console_out_handle: core::ptr::null_mut(),
+            con_out: core::ptr::null_mut(),
+            standard_error_handle: core::ptr::null_mut(),
+            std_err: core::ptr::null_mut(),
+            runtime_services: core::ptr::null_mut(),
+            boot_services: core::ptr::null_mut(),
+            number_of_table_entries: 0,
+            configuration_table: core::ptr::null_mut(),
+        };
+        let bytes = unsafe {
+            core::slice::from_raw_parts(
+                &st as *const SystemTable as *const u8,
+                core::mem::size_of::<SystemTable>(),
+            )
+        };
+        st.hdr.crc32 = crate::base::crc32(0, bytes);
+        assert_eq!(unsafe { st.verify() }, Ok(()));
+        st.firmware_revision = 0;
+        assert_eq!(unsafe { st.verify() }, Err(TableHeaderError::Crc32));
+    }
+
+    fn capsule(flags: u32, capsule_image_size: u32) -> CapsuleHeader {
+        CapsuleHeader {
+            capsule_guid: crate::base::Guid::from_fields(0, 0, 0, 0, 0, &[0; 6]),
+            header_size: core::mem::size_of::<CapsuleHeader>() as u32,
+            flags,
+            capsule_image_size,
+        }
+    }
+
+    // Verify the capsule flag rules, and that the scatter-gather builder
+    // chains descriptor pages and terminates the list.
+    #[test]
+    fn capsule_builder() {
+        let persist = CAPSULE_FLAGS_PERSIST_ACROSS_RESET;
+
+        assert_eq!(capsule(0x1234, 0x2000).verify(), Ok(()));
+        assert_eq!(
+            capsule(persist | CAPSULE_FLAGS_INITIATE_RESET, 0x2000).verify(),
+            Ok(())
+        );
+        assert_eq!(
+            capsule(CAPSULE_FLAGS_INITIATE_RESET, 0x2000).verify(),
+            Err(CapsuleError::Flags)
+        );
+        assert_eq!(
+            capsule(CAPSULE_FLAGS_POPULATE_SYSTEM_TABLE, 0x2000).verify(),
+            Err(CapsuleError::Flags)
+        );
+        assert_eq!(
+            capsule(persist | 0x00080000, 0x2000).verify(),
+            Err(CapsuleError::Flags)
+        );
+        assert_eq!(capsule(0, 16).verify(), Err(CapsuleError::HeaderSize));
+
+        let mut d0 = [CapsuleBlockDescriptor::data_block(0xdead, 1); 3];
+        let mut d1 = [CapsuleBlockDescriptor::data_block(0xdead, 1); 1];
+        let mut d2 = [CapsuleBlockDescriptor::data_block(0xdead, 1); 4];
+        let mut pages = [
+            CapsuleDescriptorPage { address: 0x10000, descriptors: &mut d0 },
+            CapsuleDescriptorPage { address: 0x20000, descriptors: &mut d1 },
+            CapsuleDescriptorPage { address: 0x30000, descriptors: &mut d2 },
+        ];
+
+        let frag = |address, length| CapsuleFragment { address, length };
+        let mut b = CapsuleBuilder::new(&mut pages).unwrap();
+        assert_eq!(
+            b.add_capsule(&capsule(persist, 0x2000), &[frag(0x1000, 0x1000)]),
+            Err(CapsuleError::ImageSize),
+        );
+        assert_eq!(
+            b.add_capsule(&capsule(persist, 0x2000), &[frag(0x1000, 0x2000), frag(0x9000, 0)]),
+            Err(CapsuleError::ImageSize),
+        );
+        b.add_capsule(
+            &capsule(persist, 0x2000),
+            &[frag(0x1000, 0x1000), frag(0x5000, 0x1000)],
+        )
+        .unwrap();
+        b.add_capsule(
+            &capsule(persist, 0x1800),
+            &[frag(0x7000, 0x1000), frag(0x3000, 0x800)],
+        )
+        .unwrap();
+        b.add_capsule(&capsule(0, 0x1000), &[frag(0xa000, 0x1000)])
+            .unwrap();
+        assert_eq!(b.finish(), Ok(0x10000));
+
+        let list = |d: &[CapsuleBlockDescriptor]| {
+            d.iter()
+                .map(|v| (v.length, v.address()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            list(&d0),
+            [(0x1000, 0x1000), (0x1000, 0x5000), (0, 0x20000)]
+        );
+        assert_eq!(list(&d1), [(0, 0x30000)]);
+        assert_eq!(list(&d2), [(0x1000, 0x7000), (0x800, 0x3000), (0x1000, 0xa000), (0, 0)]);
+        assert!(d2[3].is_terminator());
+
+        // Out of descriptor space, and an empty list.
+        let mut d0 = [CapsuleBlockDescriptor::terminator(); 2];
+        let mut pages = [CapsuleDescriptorPage { address: 0x10000, descriptors: &mut d0 }];
+        let mut b = CapsuleBuilder::new(&mut pages).unwrap();
+        assert_eq!(
+            b.add_capsule(&capsule(0, 0x2000), &[frag(0x1000, 0x1000), frag(0x2000, 0x1000)]),
+            Err(CapsuleError::Descriptors),
+        );
+        let mut d0 = [CapsuleBlockDescriptor::terminator(); 2];
+        let mut pages = [CapsuleDescriptorPage { address: 0x10000, descriptors: &mut d0 }];
+        assert_eq!(CapsuleBuilder::new(&mut pages).unwrap().finish(), Err(CapsuleError::Empty));
+        assert_eq!(CapsuleBuilder::new(&mut []).unwrap_err(), CapsuleError::Descriptors);
+    }
+
+    fn ucs2_bytes(s: &str, nul: bool) -> Vec<u8> {
+        let mut v: Vec<u8> = s.encode_utf16().flat_map(|c| c.to_le_bytes()).collect();
+        if nul {
+            v.extend_from_slice(&[0, 0]);
+        }
+        v
+    }
+
+    fn capsule_result(guid: crate::base::Guid, body: &[u8]) -> Vec<u8> {
+        let header = CapsuleResultVariableHeader {
+            variable_total_size: (core::mem::size_of::<CapsuleResultVariableHeader>() + body.len())
+                as u32,
+            reserved: 0,
+            capsule_guid: guid,
+            capsule_processed: Time { year: 2024, month: 5, day: 17, ..Default::default() },
+            capsule_status: crate::base::Status::DEVICE_ERROR,
+        };
+        let mut data = unsafe {
+            core::slice::from_raw_parts(
+                &header as *const _ as *const u8,
+                core::mem::size_of::<CapsuleResultVariableHeader>(),
+            )
+        }
+        .to_vec();
+        data.extend_from_slice(body);
+        data
+    }
+
+    // Verify decoding of FMP, JSON and unknown capsule results, and the
+    // resolution of the result ring buffer.
+    #[test]
+    fn capsule_results() {
+        let image_type = crate::base::Guid::from_fields(1, 2, 3, 4, 5, &[6; 6]);
+        let mut fmp = vec![0x01, 0x00, 0x02, 0x03];
+        fmp.extend_from_slice(image_type.as_bytes());
+        fmp.extend(ucs2_bytes("fw.cap", true));
+        fmp.extend(ucs2_bytes("PciRoot(0x0)", true));
+
+        let mut data = capsule_result(FIRMWARE_MANAGEMENT_CAPSULE_ID_GUID, &fmp);
+        data.extend_from_slice(&[0xff; 8]);
+        let r = CapsuleResult::parse(&data).unwrap();
+        assert_eq!(r.header.capsule_processed.year, 2024);
+        assert_eq!(r.header.capsule_status, crate::base::Status::DEVICE_ERROR);
+        let CapsuleResultBody::Fmp(f) = r.body else { panic!() };
+        assert_eq!((f.version, f.payload_index, f.update_image_index), (1, 2, 3));
+        assert_eq!(f.update_image_type_id, image_type);
+        assert_eq!(f.file_name_chars().collect::<String>(), "fw.cap");
+        assert_eq!(f.target_chars().collect::<String>(), "PciRoot(0x0)");
+
+        // Strings must be terminated within the total size.
+        let end = data.len() - 8;
+        let data = &mut data[..end];
+        data[0] -= 2;
+        assert_eq!(CapsuleResult::parse(data).unwrap_err(), CapsuleResultError::Fmp);
+        data[0] += 4;
+        assert_eq!(CapsuleResult::parse(data).unwrap_err(), CapsuleResultError::TotalSize);
+        assert_eq!(CapsuleResult::parse(&data[..20]).unwrap_err(), CapsuleResultError::Truncated);
+
+        let mut json = vec![1, 0, 0, 0, 7, 0, 0, 0, 2, 0, 0, 0];
+        json.extend_from_slice(b"{}");
+        let data = capsule_result(JSON_CAPSULE_ID_GUID, &json);
+        let r = CapsuleResult::parse(&data).unwrap();
+        assert_eq!(
+            r.body,
+            CapsuleResultBody::Json(CapsuleResultJson {
+                version: 1,
+                capsule_id: 7,
+                response: b"{}"
+            }),
+        );
+        json[8] = 3;
+        assert_eq!(
+            CapsuleResult::parse(&capsule_result(JSON_CAPSULE_ID_GUID, &json)).unwrap_err(),
+            CapsuleResultError::Json,
+        );
+
+        let data = capsule_result(image_type, b"abc");
+        let r = CapsuleResult::parse(&data).unwrap();
+        assert_eq!(r.body, CapsuleResultBody::Other(b"abc"));
+
+        assert_eq!(parse_capsule_result_reference(&ucs2_bytes("Capsule000A", false)), Some(10));
+        assert_eq!(parse_capsule_result_reference(&ucs2_bytes("Capsule00FF", true)), Some(255));
+        assert_eq!(parse_capsule_result_reference(&ucs2_bytes("Boot0001", true)), None);
+        assert_eq!(parse_capsule_result_reference(&ucs2_bytes("Capsule00001", false)), None);
+        assert_eq!(parse_capsule_result_reference(&ucs2_bytes("Capsüle0001", false)), None);
+
+        assert_eq!(capsule_result_numbers(1, 3).collect::<Vec<_>>(), [2, 3, 0, 1]);
+        assert_eq!(capsule_result_numbers(3, 3).collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert_eq!(capsule_result_numbers(0xffff, 0xffff).count(), 0x10000);
+    }
+
+    // Verify the layout of time-based authenticated variable payloads and
+    // their signed data.
+    #[test]
+    fn variable_authentication_2() {
+        assert_eq!(core::mem::size_of::<WinCertificate>(), 8);
+        assert_eq!(core::mem::size_of::<WinCertificateUefiGuid>(), 24);
+        assert_eq!(core::mem::size_of::<VariableAuthentication2>(), 16);
+
+        let ts = Time {
+            year: 2024,
+            month: 1,
+            day: 2,
+            hour: 3,
+            minute: 4,
+            second: 5,
+            ..Default::default()
+        };
+        let mut buffer = [0u8; 64];
+
+        let n = write_variable_authentication_2(&mut buffer, &ts, b"SIG", b"data").unwrap();
+        assert_eq!(n, 16 + 24 + 3 + 4);
+        assert_eq!(buffer[..16], ts.to_bytes());
+        assert_eq!(buffer[..8], [0xe8, 0x07, 1, 2, 3, 4, 5, 0]);
+        assert_eq!(buffer[16..24], [27, 0, 0, 0, 0x00, 0x02, 0xf1, 0x0e]);
+        assert_eq!(&buffer[24..40], CERT_TYPE_PKCS7_GUID.as_bytes());
+        assert_eq!(&buffer[40..n], b"SIGdata");
+
+        assert_eq!(
+            write_variable_authentication_2(&mut buffer[..46], &ts, b"SIG", b"data"),
+            Err(AuthenticatedVariableError::BufferTooSmall(47)),
+        );
+        assert_eq!(
+            write_variable_authentication_2(&mut buffer, &ts, b"", b"data"),
+            Err(AuthenticatedVariableError::Signature),
+        );
+        let mut bad = ts;
+        bad.timezone = 60;
+        assert_eq!(
+            write_variable_authentication_2(&mut buffer, &bad, b"SIG", b""),
+            Err(AuthenticatedVariableError::Timestamp),
+        );
+        bad = ts;
+        bad.nanosecond = 1;
+        assert_eq!(
+            write_variable_authentication_2(&mut buffer, &bad, b"SIG", b""),
+            Err(AuthenticatedVariableError::Timestamp),
+        );
+
+        let attributes = VARIABLE_NON_VOLATILE
+            | VARIABLE_BOOTSERVICE_ACCESS
+            | VARIABLE_RUNTIME_ACCESS
+            | VARIABLE_TIME_BASED_AUTHENTICATED_WRITE_ACCESS;
+        let n = write_variable_authentication_2_signed_data(
+            &mut buffer,
+            "db",
+            &GLOBAL_VARIABLE,
+            attributes,
+            &ts,
+            b"xy",
+        )
+        .unwrap();
+        assert_eq!(n, 4 + 16 + 4 + 16 + 2);
+        assert_eq!(buffer[..4], [b'd', 0, b'b', 0]);
+        assert_eq!(&buffer[4..20], GLOBAL_VARIABLE.as_bytes());
+        assert_eq!(buffer[20..24], [0x27, 0, 0, 0]);
+        assert_eq!(buffer[24..40], ts.to_bytes());
+        assert_eq!(&buffer[40..n], b"xy");
+
+        assert_eq!(
+            write_variable_authentication_2_signed_data(
+                &mut buffer,
+                "",
+                &GLOBAL_VARIABLE,
+                attributes,
+                &ts,
+                b""
+            ),
+            Err(AuthenticatedVariableError::Name),
+        );
+        assert_eq!(
+            write_variable_authentication_2_signed_data(
+                &mut buffer,
+                "db",
+                &GLOBAL_VARIABLE,
+                VARIABLE_NON_VOLATILE,
+                &ts,
+                b""
+            ),
+            Err(AuthenticatedVariableError::Attributes),
+        );
+        assert_eq!(
+            write_variable_authentication_2_signed_data(
+                &mut buffer[..41],
+                "db",
+                &GLOBAL_VARIABLE,
+                attributes,
+                &ts,
+                b"xy"
+            ),
+            Err(AuthenticatedVariableError::BufferTooSmall(42)),
+        );
+    }
+
+    // Verify parsing of signature databases, and appending with
+    // deduplication.
+    #[test]
+    fn signature_database() {
+        assert_eq!(core::mem::size_of::<SignatureList>(), 28);
+        assert_eq!(core::mem::size_of::<SignatureData>(), 16);
+
+        let owner =
+            crate::base::Guid::from_fields(0x11111111, 0x2222, 0x3333, 0x44, 0x55, &[0x66; 6]);
+        let other =
+            crate::base::Guid::from_fields(0xaaaaaaaa, 0xbbbb, 0xcccc, 0xdd, 0xee, &[0xff; 6]);
+        let mut buffer = [0u8; 512];
+        let mut b = SignatureDatabaseBuilder::new(&mut buffer);
+
+        assert_eq!(
+            b.append(&CERT_SHA256_GUID, &owner, &[1; 31]),
+            Err(SignatureListError::SignatureSize)
+        );
+        assert_eq!(b.append(&CERT_SHA256_GUID, &owner, &[1; 32]), Ok(true));
+        assert_eq!(b.append(&CERT_X509_GUID, &owner, b"cert"), Ok(true));
+        assert_eq!(b.append(&CERT_SHA256_GUID, &owner, &[2; 32]), Ok(true));
+        assert_eq!(b.append(&CERT_SHA256_GUID, &owner, &[1; 32]), Ok(false));
+        assert_eq!(b.append(&CERT_SHA256_GUID, &other, &[1; 32]), Ok(true));
+        assert_eq!(b.append(&CERT_X509_GUID, &owner, b"longer cert"), Ok(true));
+        assert_eq!(b.len(), (28 + 3 * 48) + (28 + 20) + (28 + 27));
+
+        let db = SignatureDatabase::parse(b.database().as_bytes()).unwrap();
+        let lists: Vec<_> = db.lists().collect();
+        assert_eq!(lists.len(), 3);
+        assert_eq!((lists[0].signature_type, lists[0].len()), (CERT_SHA256_GUID, 3));
+        assert_eq!(lists[0].signature_size(), 16 + 32);
+        assert_eq!(lists[0].entries().map(|v| v.data[0]).collect::<Vec<_>>(), [1, 2, 1]);
+        assert_eq!(lists[0].entries().nth(2).unwrap().owner, other);
+        assert_eq!(lists[1].entries().next().unwrap().data, b"cert");
+        assert_eq!(lists[2].entries().next().unwrap().data, b"longer cert");
+        assert!(db.contains(&CERT_X509_GUID, &owner, b"cert"));
+        assert!(!db.contains(&CERT_SHA256_GUID, &owner, b"cert"));
+
+        // Append-write semantics: merge a second database into a copy.
+        let mut update = [0u8; 256];
+        let mut u = SignatureDatabaseBuilder::new(&mut update);
+        u.append(&CERT_SHA256_GUID, &owner, &[2; 32]).unwrap();
+        u.append(&CERT_SHA256_GUID, &owner, &[3; 32]).unwrap();
+        let n = u.len();
+
+        let len = b.len();
+        let mut copy = buffer;
+        let mut c = SignatureDatabaseBuilder::with_contents(&mut copy, len).unwrap();
+        assert_eq!(c.append_database(&SignatureDatabase::parse(&update[..n]).unwrap()), Ok(1));
+        assert_eq!(c.len(), len + 48);
+        assert_eq!(c.database().lists().next().unwrap().len(), 4);
+
+        let mut small = [0u8; 100];
+        let mut s = SignatureDatabaseBuilder::new(&mut small);
+        assert_eq!(s.append(&CERT_SHA256_GUID, &owner, &[1; 32]), Ok(true));
+        assert_eq!(
+            s.append(&CERT_SHA256_GUID, &owner, &[2; 32]),
+            Err(SignatureListError::BufferTooSmall(124)),
+        );
+
+        // Size field validation.
+        let data = &buffer[..len];
+        assert_eq!(SignatureDatabase::parse(&data[..20]), Err(SignatureListError::Truncated));
+        assert_eq!(SignatureDatabase::parse(&data[..100]), Err(SignatureListError::ListSize));
+        let mut bad = data.to_vec();
+        bad[24] = 47;
+        assert_eq!(SignatureDatabase::parse(&bad), Err(SignatureListError::SignatureSize));
+        bad[24] = 48;
+        bad[20] = 200;
+        assert_eq!(SignatureDatabase::parse(&bad), Err(SignatureListError::ListSize));
+        bad[20] = 4;
+        assert_eq!(SignatureDatabase::parse(&bad), Err(SignatureListError::SignatureSize));
+    }
+
+    // Verify walking memory attributes tables with extended descriptors, and
+    // the reported invariant violations.
+    #[test]
+    fn memory_attributes_table() {
+        let ds = core::mem::size_of::<MemoryDescriptor>() + 16;
+        let entry = |r#type, physical_start, number_of_pages, attribute| MemoryDescriptor {
+            r#type,
+            physical_start,
+            virtual_start: 0,
+            number_of_pages,
+            attribute: MEMORY_RUNTIME | attribute,
+        };
+        let entries = [
+            entry(RUNTIME_SERVICES_CODE, 0x10000, 1, MEMORY_RO),
+            entry(RUNTIME_SERVICES_DATA, 0x11000, 2, MEMORY_XP),
+            entry(RUNTIME_SERVICES_DATA, 0x13000, 1, MEMORY_RO | MEMORY_XP),
+        ];
+
+        let mut data = vec![0u8; 16];
+        data[0..4].copy_from_slice(&MEMORY_ATTRIBUTES_TABLE_VERSION.to_le_bytes());
+        data[4..8].copy_from_slice(&3u32.to_le_bytes());
+        data[8..12].copy_from_slice(&(ds as u32).to_le_bytes());
+        data[12..16]
+            .copy_from_slice(&MEMORY_ATTRIBUTES_FLAGS_RT_FORWARD_CONTROL_FLOW_GUARD.to_le_bytes());
+        data.extend(memory_map_buffer(&entries, ds));
+
+        let t = MemoryAttributesTableView::parse(&data).unwrap();
+        assert_eq!((t.version(), t.len()), (2, 3));
+        assert_eq!(
+            t.flags(),
+            MEMORY_ATTRIBUTES_FLAGS_RT_FORWARD_CONTROL_FLOW_GUARD
+        );
+        assert_eq!(
+            t.entries().map(|v| v.physical_start).collect::<Vec<_>>(),
+            [0x10000, 0x11000, 0x13000]
+        );
+        assert_eq!(t.verify(), Ok(()));
+
+        assert_eq!(
+            MemoryAttributesTableView::parse(&data[..data.len() - 1]).unwrap_err(),
+            MemoryAttributesTableError::Truncated,
+        );
+        let mut bad = data.clone();
+        bad[0] = 3;
+        assert_eq!(
+            MemoryAttributesTableView::parse(&bad).unwrap_err(),
+            MemoryAttributesTableError::Version
+        );
+        bad[0] = 1;
+        assert_eq!(MemoryAttributesTableView::parse(&bad).unwrap().flags(), 0);
+        bad[8] = 8;
+        assert_eq!(
+            MemoryAttributesTableView::parse(&bad).unwrap_err(),
+            MemoryAttributesTableError::DescriptorSize
+        );
+
+        let entries = [
+            entry(RUNTIME_SERVICES_CODE, 0x10000, 2, 0),
+            entry(RUNTIME_SERVICES_DATA, 0x11000, 1, MEMORY_XP),
+            entry(RUNTIME_SERVICES_CODE, 0x12000, 1, MEMORY_XP),
+            entry(RUNTIME_SERVICES_DATA, 0x13000, 1, 0),
+            entry(CONVENTIONAL_MEMORY, 0x0f000, 1, MEMORY_XP),
+        ];
+        data.truncate(16);
+        data[4..8].copy_from_slice(&5u32.to_le_bytes());
+        data.extend(memory_map_buffer(&entries, ds));
+        let t = MemoryAttributesTableView::parse(&data).unwrap();
+        assert_eq!(
+            t.violations().collect::<Vec<_>>(),
+            [
+                (0, MemoryAttributesViolation::Attributes),
+                (1, MemoryAttributesViolation::Overlap),
+                (3, MemoryAttributesViolation::Attributes),
+                (4, MemoryAttributesViolation::Type),
+                (4, MemoryAttributesViolation::Order),
+            ],
+        );
+        assert_eq!(t.verify(), Err((0, MemoryAttributesViolation::Attributes)));
+    }
+}